@@ -7,6 +7,7 @@ import (
 	"fmt"
 	"log"
 	"net/http"
+	"regexp"
 	"time"
 )
 
@@ -69,6 +70,18 @@ var (
 	staticPath   = "/assets/static"
 )
 
+// Regex patterns
+var (
+	emailRegex   = regexp.MustCompile(`^[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}$`)
+	versionRegex = regexp.MustCompile("^v?(\\d+)\\.(\\d+)\\.(\\d+)$")
+	headerRegex  = regexp.MustCompile(`(?i)^(?P<name>[\w-]+):\s*(?P<value>.*)$`)
+)
+
+func isValidUserPath(path string) bool {
+	matched, _ := regexp.MatchString(`^/api/users/\d+$`, path)
+	return matched
+}
+
 // String values
 var message = fmt.Sprintf("User %d connected", len(userIDs))
 
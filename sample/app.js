@@ -39,6 +39,18 @@ const userIds = [101, 102, 103, 204, 305];
 // Regex patterns in strings
 const emailPattern = /\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Z|a-z]{2,}\b/;
 const phonePattern = /^\+?[\d\s\-\(\)]+$/;
+const versionPattern = new RegExp('^v(\\d+)\\.(\\d+)$', 'i');
+const logLinePattern = new RegExp(
+  '^(\\d{4}-\\d{2}-\\d{2}) ' +
+    '(INFO|WARN|ERROR) ' +
+    '(.*)$',
+  'm'
+);
+
+// Slashes that are not regex literals
+const halfAverage = userIds.reduce((sum, id) => sum + id, 0) / userIds.length / 2;
+const docsUrl = '/docs/guide/'; // see /docs/ for details
+const isDocsPath = (path) => /^\/docs\/[^/]+\/?$/.test(path);
 
 // Object with paths
 const config = {
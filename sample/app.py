@@ -2,6 +2,7 @@
 # Test patterns: /def\s+(\w+)/g, /class\s+(\w+)/g, /['"]([^'"`]+)['"`]/g
 
 import os
+import re
 import json
 from typing import List, Dict, Optional
 from datetime import datetime
@@ -56,6 +57,27 @@ user_ids = [101, 102, 103, 204, 305]
 email_pattern = r'\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Z|a-z]{2,}\b'
 phone_pattern = r'^\+?[\d\s\-\(\)]+$'
 
+EMAIL_RE = re.compile(email_pattern)
+PHONE_RE = re.compile(r'^\+?[\d\s\-\(\)]+$', re.MULTILINE)
+LOG_LINE_RE = re.compile(
+    r'^(?P<date>\d{4}-\d{2}-\d{2})\s+'  # ISO date
+    r'(?P<level>INFO|WARN|ERROR)\s+'
+    r'(?P<message>.*)$',
+    re.IGNORECASE | re.M,
+)
+VERSION_RE = re.compile(r"""
+    v?(?P<major>\d+)   # major
+    \.(?P<minor>\d+)   # minor
+""", re.VERBOSE)
+
+def normalize_whitespace(value: str) -> str:
+    """Collapse runs of whitespace."""
+    return re.sub(r'\s+', ' ', value).strip()
+
+def find_user_ids(text: str) -> List[str]:
+    """Find user references such as user_42."""
+    return re.findall(r'user_(\d+)', text, flags=re.I)
+
 # Configuration
 config = {
     'static_path': './static',
@@ -4,6 +4,10 @@
 use std::io;
 use std::net::{TcpListener, TcpStream};
 
+use lazy_static::lazy_static;
+use once_cell::sync::Lazy;
+use regex::{Regex, RegexBuilder, RegexSet};
+
 // Struct definitions
 struct User {
     id: u32,
@@ -50,6 +54,23 @@ async fn fetch_data(url: &str) -> Result<String, io::Error> {
     Ok("data".to_string())
 }
 
+// Regex construction sites
+static EMAIL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[\w.+-]+@[\w-]+\.[\w.]+$").unwrap());
+
+lazy_static! {
+    static ref VERSION_RE: Regex = Regex::new(r#"^"?(\d+)\.(\d+)\.(\d+)"?$"#).unwrap();
+}
+
+fn build_matchers() -> Result<(Regex, RegexSet), regex::Error> {
+    let header = RegexBuilder::new("^x-[a-z]+:\\s*(.*)$")
+        .case_insensitive(true)
+        .multi_line(true)
+        .build()?;
+    let routes = RegexSet::new([r"^/api/users/\d+$", r"^/api/v1/.*"])?;
+    Ok((header, routes))
+}
+
 // Implementation blocks
 impl User {
     fn get_name(&self) -> &str {
@@ -57,6 +57,7 @@ const users: User[] = [];
 // Template literals
 const message = `User ${users.length} connected`;
 const path = `/api/v1/users/${userId}`;
+const depth = path.split(/\/+/).length / 2;
 
 // Imports to extract
 // Pattern: /import\s+.*?\s+from\s+['"](.+?)['"]/g
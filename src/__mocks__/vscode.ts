@@ -25,11 +25,18 @@ export const window = {
   }),
   withProgress: mockFn,
   showTextDocument: mockFn,
+  showInputBox: mockFn,
+  showQuickPick: mockFn,
 }
 
 export const workspace = {
+  workspaceFolders: undefined,
+  textDocuments: [],
+  findFiles: mockFn,
   openTextDocument: mockFn,
   applyEdit: mockFn,
+  asRelativePath: (uri: { path?: string }) => uri.path ?? '',
+  registerTextDocumentContentProvider: () => ({ dispose: mockFn }),
   getConfiguration: () => ({
     get: (key: string, defaultValue?: unknown) => defaultValue,
     update: mockFn,
@@ -79,8 +86,18 @@ export const WorkspaceEdit = class WorkspaceEdit {
   replace(_uri: unknown, _range: unknown, _text: string) {}
 }
 
+export const RelativePattern = class RelativePattern {
+  constructor(
+    public base: unknown,
+    public pattern: string,
+  ) {}
+}
+
 export const Uri = {
   file: (path: string) => ({ fsPath: path, path }),
+  joinPath: (base: { path: string }, ...segments: string[]) => ({
+    path: [base.path, ...segments].join('/'),
+  }),
   parse: (uri: string) => ({ toString: () => uri }),
 }
 
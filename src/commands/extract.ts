@@ -2,7 +2,9 @@ import * as vscode from 'vscode';
 import * as nls from 'vscode-nls';
 import { getConfiguration } from '../config/config';
 import { extractRegexPatterns } from '../extraction/regex/extractPatterns';
+import { formatExtractedPatterns } from '../extraction/regex/outputFormats';
 import type { Telemetry } from '../telemetry/telemetry';
+import type { ExtractOutputFormat } from '../types';
 import type { Notifier } from '../ui/notifier';
 import type { StatusBar } from '../ui/statusBar';
 import { handleSafetyChecks } from '../utils/safety';
@@ -51,6 +53,14 @@ export function registerExtractCommand(
 				return;
 			}
 
+			const format =
+				config.extractOutputFormat === 'ask'
+					? await pickOutputFormat()
+					: config.extractOutputFormat;
+			if (!format) {
+				return;
+			}
+
 			try {
 				await vscode.window.withProgress(
 					{
@@ -59,7 +69,7 @@ export function registerExtractCommand(
 							'runtime.extract.progress',
 							'Extracting regex patterns...',
 						),
-						cancellable: false,
+						cancellable: true,
 					},
 					async (_progress, token): Promise<void> => {
 						if (token.isCancellationRequested) return;
@@ -67,7 +77,10 @@ export function registerExtractCommand(
 						const text = document.getText();
 
 						// Extract regex patterns from the file
-						const patterns = extractRegexPatterns(text);
+						const patterns = extractRegexPatterns(
+							text,
+							document.languageId,
+						);
 
 						if (token.isCancellationRequested) return;
 
@@ -81,20 +94,17 @@ export function registerExtractCommand(
 							return;
 						}
 
-						// Format results - one pattern per line
-						const outputLines: string[] = [];
-						for (const pattern of patterns) {
-							// Format as /pattern/flags with line number
-							const formatted = `/${pattern.pattern}/${pattern.flags}`;
-							outputLines.push(formatted);
-						}
-
-						const output = outputLines.join('\n');
+						const formatted = formatExtractedPatterns(
+							patterns,
+							format,
+							vscode.workspace.asRelativePath(document.uri),
+						);
+						const output = formatted.content;
 
 						// Open result document side-by-side
 						const doc = await vscode.workspace.openTextDocument({
 							content: output,
-							language: 'plaintext',
+							language: formatted.language,
 						});
 
 						const viewColumn = config.openResultsSideBySide
@@ -129,6 +139,7 @@ export function registerExtractCommand(
 
 						deps.telemetry.event('extract-completed', {
 							matchCount: patterns.length,
+							format,
 						});
 
 						if (config.notificationsLevel === 'all') {
@@ -159,3 +170,63 @@ export function registerExtractCommand(
 
 	context.subscriptions.push(disposable);
 }
+
+interface FormatChoice extends vscode.QuickPickItem {
+	readonly format: ExtractOutputFormat;
+}
+
+/**
+ * Ask which format to write when extract.outputFormat is "ask"
+ */
+async function pickOutputFormat(): Promise<ExtractOutputFormat | undefined> {
+	const choices: FormatChoice[] = [
+		{
+			label: 'Text',
+			description: localize(
+				'runtime.extract.format.text',
+				'Each pattern followed by the lines where it occurs',
+			),
+			format: 'text',
+		},
+		{
+			label: 'JSON',
+			description: localize(
+				'runtime.extract.format.json',
+				'File, range, source form and dialect per occurrence',
+			),
+			format: 'json',
+		},
+		{
+			label: 'CSV',
+			description: localize(
+				'runtime.extract.format.csv',
+				'Comma-separated, one row per occurrence',
+			),
+			format: 'csv',
+		},
+		{
+			label: 'TSV',
+			description: localize(
+				'runtime.extract.format.tsv',
+				'Tab-separated, one row per occurrence',
+			),
+			format: 'tsv',
+		},
+		{
+			label: 'Markdown',
+			description: localize(
+				'runtime.extract.format.markdown',
+				'Table with one row per occurrence',
+			),
+			format: 'markdown',
+		},
+	];
+
+	const selected = await vscode.window.showQuickPick(choices, {
+		placeHolder: localize(
+			'runtime.extract.format.prompt',
+			'Select an output format for the extracted patterns',
+		),
+	});
+	return selected?.format;
+}
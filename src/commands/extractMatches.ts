@@ -0,0 +1,265 @@
+import * as vscode from 'vscode';
+import * as nls from 'vscode-nls';
+import { getConfiguration } from '../config/config';
+import { formatMatches } from '../extraction/regex/outputFormats';
+import {
+	isCancelled,
+	isTimedOut,
+	runRegexTest,
+} from '../extraction/regex/regexRunner';
+import { captureGroupNames } from '../extraction/regex/regexTest';
+import type { Telemetry } from '../telemetry/telemetry';
+import type { MatchOutputFormat } from '../types';
+import type { Notifier } from '../ui/notifier';
+import type { StatusBar } from '../ui/statusBar';
+import { handleSafetyChecks } from '../utils/safety';
+
+const localize = nls.config({ messageFormat: nls.MessageFormat.file })();
+
+/**
+ * Register the extract matches command
+ * Runs a pattern over the active editor and writes every match with its
+ * capture groups as CSV, TSV or JSON rows
+ */
+export function registerExtractMatchesCommand(
+	context: vscode.ExtensionContext,
+	deps: Readonly<{
+		telemetry: Telemetry;
+		notifier: Notifier;
+		statusBar: StatusBar;
+	}>,
+): void {
+	const disposable = vscode.commands.registerCommand(
+		'regex-le.extractMatches',
+		async (): Promise<void> => {
+			deps.telemetry.event('command-extract-matches');
+
+			const editor = vscode.window.activeTextEditor;
+			if (!editor) {
+				deps.notifier.showWarning(
+					localize(
+						'runtime.extract.no-editor',
+						'No active editor. Please open a file first.',
+					),
+				);
+				return;
+			}
+
+			const config = getConfiguration();
+			const document = editor.document;
+
+			const safetyResult = handleSafetyChecks(document, config);
+			if (!safetyResult.proceed) {
+				if (safetyResult.error) {
+					await deps.notifier.showEnhancedError(safetyResult.error);
+				} else {
+					deps.notifier.showError(safetyResult.message);
+				}
+				return;
+			}
+
+			const patternInput = await vscode.window.showInputBox({
+				prompt: localize(
+					'runtime.extract.pattern.prompt',
+					'Enter regex pattern',
+				),
+				placeHolder: localize(
+					'runtime.extract.pattern.placeholder',
+					'e.g., /\\d+/',
+				),
+				validateInput: (value) => {
+					if (!value || value.trim().length === 0) {
+						return localize(
+							'runtime.extract.pattern.invalid',
+							'Pattern cannot be empty',
+						);
+					}
+					return null;
+				},
+			});
+			if (!patternInput) {
+				return;
+			}
+
+			// Parse the input pattern; every match is extracted, not just the first
+			const patternMatch = patternInput.match(/^\/(.+)\/([dgimsuvy]*)$/);
+			const pattern = patternMatch?.[1] ?? patternInput.trim();
+			const inputFlags = patternMatch?.[2] ?? '';
+			const flags = inputFlags.includes('g') ? inputFlags : `${inputFlags}g`;
+
+			// The Extract format setting applies when it is one of these formats
+			const format =
+				config.extractOutputFormat === 'csv' ||
+				config.extractOutputFormat === 'tsv' ||
+				config.extractOutputFormat === 'json'
+					? config.extractOutputFormat
+					: await pickMatchFormat();
+			if (!format) {
+				return;
+			}
+
+			try {
+				const result = await vscode.window.withProgress(
+					{
+						location: vscode.ProgressLocation.Notification,
+						title: localize(
+							'runtime.extract-matches.progress',
+							'Extracting matches...',
+						),
+						cancellable: true,
+					},
+					(_progress, token) =>
+						runRegexTest(
+							pattern,
+							flags,
+							document.getText(),
+							config.regexMaxMatchLimit,
+							config.performanceMaxDuration,
+							undefined,
+							token,
+						),
+				);
+
+				const stopped = isTimedOut(result) || isCancelled(result);
+				if (!result.success && !stopped) {
+					deps.notifier.showError(
+						localize(
+							'runtime.extract.error',
+							'Extraction failed: {0}',
+							result.errors.map((error) => error.message).join('; '),
+						),
+					);
+					return;
+				}
+				if (result.matches.length === 0) {
+					deps.notifier.showInfo(
+						localize('runtime.extract.no-matches', 'No matches found.'),
+					);
+					return;
+				}
+
+				const formatted = formatMatches(
+					result.matches,
+					captureGroupNames(pattern, flags),
+					format,
+				);
+				const doc = await vscode.workspace.openTextDocument({
+					content: formatted.content,
+					language: formatted.language,
+				});
+				const viewColumn = config.openResultsSideBySide
+					? vscode.ViewColumn.Beside
+					: vscode.ViewColumn.Active;
+				await vscode.window.showTextDocument(doc, viewColumn);
+
+				// The rows written so far are still useful when matching stopped
+				if (stopped) {
+					deps.notifier.showWarning(
+						localize(
+							'runtime.extract-matches.stopped',
+							'Matching stopped early; only the first {0} matches were extracted.',
+							result.matches.length,
+						),
+					);
+				} else if (result.truncated) {
+					deps.notifier.showWarning(
+						localize(
+							'runtime.extract-matches.truncated',
+							'The match limit was reached; only the first {0} of {1} matches were extracted.',
+							result.matches.length,
+							result.totalMatches ?? `${result.matches.length}+`,
+						),
+					);
+				}
+
+				if (config.copyToClipboardEnabled) {
+					try {
+						await vscode.env.clipboard.writeText(formatted.content);
+						deps.statusBar.updateText(
+							localize(
+								'runtime.extract.copied',
+								'Extracted {0} matches to clipboard',
+								result.matches.length,
+							),
+						);
+					} catch {
+						// Ignore clipboard errors
+					}
+				} else {
+					deps.statusBar.updateText(
+						localize(
+							'runtime.extract.complete',
+							'Extracted {0} matches',
+							result.matches.length,
+						),
+					);
+				}
+
+				deps.telemetry.event('extract-matches-completed', {
+					matchCount: result.matches.length,
+					format,
+					stopped,
+				});
+			} catch (error) {
+				const errorMessage =
+					error instanceof Error ? error.message : String(error);
+				deps.notifier.showError(
+					localize(
+						'runtime.extract.error',
+						'Extraction failed: {0}',
+						errorMessage,
+					),
+				);
+				deps.telemetry.event('extract-matches-failed', {
+					error: errorMessage,
+				});
+			}
+		},
+	);
+
+	context.subscriptions.push(disposable);
+}
+
+interface MatchFormatChoice extends vscode.QuickPickItem {
+	readonly format: MatchOutputFormat;
+}
+
+/**
+ * Ask which format to write when extract.outputFormat is not a row format
+ */
+async function pickMatchFormat(): Promise<MatchOutputFormat | undefined> {
+	const choices: MatchFormatChoice[] = [
+		{
+			label: 'CSV',
+			description: localize(
+				'runtime.extract-matches.format.csv',
+				'Comma-separated, one row per match with a column per group',
+			),
+			format: 'csv',
+		},
+		{
+			label: 'TSV',
+			description: localize(
+				'runtime.extract-matches.format.tsv',
+				'Tab-separated, one row per match with a column per group',
+			),
+			format: 'tsv',
+		},
+		{
+			label: 'JSON',
+			description: localize(
+				'runtime.extract-matches.format.json',
+				'One object per match, keyed by group name or number',
+			),
+			format: 'json',
+		},
+	];
+
+	const selected = await vscode.window.showQuickPick(choices, {
+		placeHolder: localize(
+			'runtime.extract-matches.format.prompt',
+			'Select an output format for the matches',
+		),
+	});
+	return selected?.format;
+}
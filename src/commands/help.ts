@@ -38,7 +38,7 @@ function buildHelpContent(): string {
 	);
 	const commands = localize(
 		'runtime.help.commands',
-		'**Test**: Test a regex pattern against the active editor content\n**Extract**: Extract all matches from the active editor\n**Validate**: Validate a regex pattern and check for ReDoS vulnerabilities\n**Settings**: Configure extension options',
+		'**Test**: Test a regex pattern against the active editor content\n**Test Match Frequency**: Count how often each distinct match or capture group value occurs, with CSV export\n**Test Lines**: List the lines that match (or, inverted, do not match) a pattern with context, like grep\n**Test Replace**: Preview a replacement ($1, $<name>, $&) as a diff and apply it in one undoable edit\n**Extract Patterns**: List the regex patterns found in the active editor\n**Extract Matches**: Write the matches of a pattern and their capture groups as CSV, TSV or JSON rows\n**Validate**: Validate a regex pattern and check for ReDoS vulnerabilities\n**Scan Workspace**: Inventory and risk-rate the regex patterns of every file in the workspace\n**Search and Replace in Workspace**: Replace matches across the workspace, choosing them in a preview tree\n**Settings**: Configure extension options',
 	);
 	const troubleshooting = localize(
 		'runtime.help.troubleshooting',
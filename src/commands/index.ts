@@ -4,9 +4,17 @@ import type { Notifier } from '../ui/notifier';
 import type { StatusBar } from '../ui/statusBar';
 import type { PerformanceMonitor } from '../utils/performance';
 import { registerExtractCommand } from './extract';
+import { registerExtractMatchesCommand } from './extractMatches';
 import { registerHelpCommand } from './help';
+import { registerInventoryView } from './inventory';
+import { registerReplaceCommand, registerReplacePreview } from './replace';
+import { registerScanWorkspaceCommand } from './scanWorkspace';
+import { registerSpecTests } from './specTests';
 import { registerTestCommand } from './test';
+import { registerTestAggregateCommand } from './testAggregate';
+import { registerTestLinesCommand } from './testLines';
 import { registerValidateCommand } from './validate';
+import { registerWorkspaceReplaceCommand } from './workspaceReplace';
 
 export function registerCommands(
 	context: vscode.ExtensionContext,
@@ -18,7 +26,16 @@ export function registerCommands(
 	}>,
 ): void {
 	registerTestCommand(context, deps);
+	registerTestAggregateCommand(context, deps);
+	registerTestLinesCommand(context, deps);
+	const preview = registerReplacePreview(context);
+	registerReplaceCommand(context, { ...deps, preview });
 	registerExtractCommand(context, deps);
+	registerExtractMatchesCommand(context, deps);
 	registerValidateCommand(context, deps);
+	const inventory = registerInventoryView(context, deps);
+	registerScanWorkspaceCommand(context, { ...deps, inventory });
+	registerWorkspaceReplaceCommand(context, { ...deps, preview });
+	registerSpecTests(context, deps);
 	registerHelpCommand(context, deps.telemetry);
 }
@@ -0,0 +1,214 @@
+import * as vscode from 'vscode';
+import * as nls from 'vscode-nls';
+import { getConfiguration } from '../config/config';
+import type { InventoryEntry } from '../extraction/regex/inventory';
+import {
+	createInventoryTree,
+	type InventoryNode,
+	type InventoryTree,
+	revealArguments,
+} from '../providers/inventoryTree';
+import type { Telemetry } from '../telemetry/telemetry';
+import type { Notifier } from '../ui/notifier';
+import type { StatusBar } from '../ui/statusBar';
+import { scanWorkspace } from './scanWorkspace';
+import { testSinglePattern } from './test';
+import { validateAllPatterns } from './validate';
+
+const localize = nls.config({ messageFormat: nls.MessageFormat.file })();
+
+const VIEW_ID = 'regex-le.inventory';
+
+/**
+ * Register the regex inventory view and its actions
+ * The view is filled by its refresh action or by Scan Workspace, and saved
+ * files already listed are re-analysed in place.
+ */
+export function registerInventoryView(
+	context: vscode.ExtensionContext,
+	deps: Readonly<{
+		telemetry: Telemetry;
+		notifier: Notifier;
+		statusBar: StatusBar;
+	}>,
+): InventoryTree {
+	const tree = createInventoryTree();
+	const view = vscode.window.createTreeView(VIEW_ID, {
+		treeDataProvider: tree,
+		showCollapseAll: true,
+	});
+
+	const refresh = vscode.commands.registerCommand(
+		'regex-le.inventory.refresh',
+		async (): Promise<void> => {
+			deps.telemetry.event('command-inventory-refresh');
+			if (!vscode.workspace.workspaceFolders?.length) {
+				deps.notifier.showWarning(
+					localize(
+						'runtime.scan.no-workspace',
+						'No workspace folder is open. Please open a folder first.',
+					),
+				);
+				return;
+			}
+
+			const config = getConfiguration();
+			try {
+				const result = await vscode.window.withProgress(
+					{
+						location: { viewId: VIEW_ID },
+						title: localize(
+							'runtime.scan.progress',
+							'Scanning workspace for regex patterns...',
+						),
+					},
+					(progress, token) => scanWorkspace(config, progress, token),
+				);
+				tree.setFiles(result.files);
+				deps.telemetry.event('inventory-refreshed', {
+					filesScanned: result.summary.filesScanned,
+				});
+			} catch (error) {
+				const errorMessage =
+					error instanceof Error ? error.message : String(error);
+				deps.notifier.showError(
+					localize('runtime.scan.error', 'Scan failed: {0}', errorMessage),
+				);
+			}
+		},
+	);
+
+	const reveal = vscode.commands.registerCommand(
+		'regex-le.inventory.reveal',
+		async (node?: InventoryNode): Promise<void> => {
+			const target = node && occurrenceOf(node);
+			if (target) {
+				await vscode.commands.executeCommand(
+					'vscode.open',
+					...revealArguments(target.uri, target.entry),
+				);
+			}
+		},
+	);
+
+	const test = vscode.commands.registerCommand(
+		'regex-le.inventory.test',
+		async (node?: InventoryNode): Promise<void> => {
+			const target = node && occurrenceOf(node);
+			if (!target) {
+				return;
+			}
+			deps.telemetry.event('command-inventory-test');
+
+			// Like Test Regex, the pattern runs against its own file
+			const config = getConfiguration();
+			const p = target.entry.pattern;
+			try {
+				const document = await vscode.workspace.openTextDocument(target.uri);
+				await testSinglePattern(
+					p.pattern,
+					p.flags,
+					p.dialect,
+					document.getText(),
+					config,
+					deps,
+				);
+			} catch (error) {
+				const errorMessage =
+					error instanceof Error ? error.message : String(error);
+				deps.notifier.showError(
+					localize('runtime.test.error', 'Testing failed: {0}', errorMessage),
+				);
+			}
+		},
+	);
+
+	const validate = vscode.commands.registerCommand(
+		'regex-le.inventory.validate',
+		async (node?: InventoryNode): Promise<void> => {
+			const entries = node ? entriesOf(node) : [];
+			if (entries.length === 0) {
+				return;
+			}
+			deps.telemetry.event('command-inventory-validate');
+
+			try {
+				await vscode.window.withProgress(
+					{
+						location: vscode.ProgressLocation.Notification,
+						title: localize(
+							'runtime.validate.progress',
+							'Validating regex patterns...',
+						),
+						cancellable: true,
+					},
+					(progress, token) =>
+						validateAllPatterns(
+							entries.map((entry) => entry.pattern),
+							deps,
+							progress,
+							token,
+						),
+				);
+			} catch (error) {
+				const errorMessage =
+					error instanceof Error ? error.message : String(error);
+				deps.notifier.showError(
+					localize(
+						'runtime.validate.error',
+						'Validation failed: {0}',
+						errorMessage,
+					),
+				);
+			}
+		},
+	);
+
+	const onSave = vscode.workspace.onDidSaveTextDocument((document) => {
+		tree.updateDocument(
+			document,
+			getConfiguration().regexRedosDetectionEnabled,
+		);
+	});
+
+	context.subscriptions.push(
+		tree,
+		view,
+		refresh,
+		reveal,
+		test,
+		validate,
+		onSave,
+	);
+	return tree;
+}
+
+interface NodeOccurrence {
+	readonly uri: vscode.Uri;
+	readonly entry: InventoryEntry;
+}
+
+/**
+ * The occurrence an action applies to; for a pattern, its first one
+ */
+function occurrenceOf(node: InventoryNode): NodeOccurrence | undefined {
+	if (node.kind === 'occurrence') {
+		return { uri: node.uri, entry: node.entry };
+	}
+	const first = node.kind === 'pattern' ? node.entries[0] : undefined;
+	if (node.kind === 'pattern' && first) {
+		return { uri: node.uri, entry: first };
+	}
+	return undefined;
+}
+
+function entriesOf(node: InventoryNode): readonly InventoryEntry[] {
+	switch (node.kind) {
+		case 'file':
+			return node.file.inventory.entries;
+		case 'pattern':
+			return node.entries;
+		default:
+			return [node.entry];
+	}
+}
@@ -0,0 +1,347 @@
+import * as vscode from 'vscode';
+import * as nls from 'vscode-nls';
+import { getConfiguration } from '../config/config';
+import {
+	checkDialectSyntax,
+	type DialectCheckResult,
+	type JavaScriptTranslation,
+	toJavaScriptPattern,
+} from '../extraction/regex/dialects';
+import {
+	type ExtractedRegexPattern,
+	extractRegexPatterns,
+	patternsWithin,
+} from '../extraction/regex/extractPatterns';
+import { detectReDoS, type ReDoSResult } from '../extraction/regex/redos';
+import { isCancelled, isTimedOut } from '../extraction/regex/regexRunner';
+import type { TextRange } from '../extraction/regex/regexTest';
+import type { Telemetry } from '../telemetry/telemetry';
+import type { RegexDialect, RegexGroup, RegexTestResult } from '../types';
+import type { Notifier } from '../ui/notifier';
+import { handleSafetyChecks } from '../utils/safety';
+import {
+	nonEmptySelections,
+	pickSelectionScope,
+	toSourceLocation,
+	toTextRange,
+} from '../utils/selection';
+
+const localize = nls.config({ messageFormat: nls.MessageFormat.file })();
+
+export interface TestPattern {
+	readonly pattern: string;
+	readonly flags: string;
+	readonly dialect: RegexDialect;
+}
+
+/**
+ * What a test command runs against, picked from the active editor
+ */
+export interface TestSubject {
+	readonly text: string;
+	readonly config: ReturnType<typeof getConfiguration>;
+	readonly ranges?: readonly TextRange[] | undefined; // Selections used as input
+	readonly patterns: readonly ExtractedRegexPattern[]; // Offered in the pick
+	readonly selected: TestPattern | 'all';
+}
+
+/**
+ * A pattern checked against its dialect and ready to run as JavaScript
+ */
+export interface PreparedTest extends TestPattern {
+	readonly dialectCheck: DialectCheckResult;
+	readonly translation: JavaScriptTranslation;
+	readonly redos: ReDoSResult;
+}
+
+/**
+ * Markdown report of a finished test
+ * opened is called once the report is shown, e.g. to offer a follow-up.
+ */
+export interface TestReport {
+	readonly content: string;
+	readonly opened?: ((viewColumn: vscode.ViewColumn) => void) | undefined;
+}
+
+export type TestRun = (token: vscode.CancellationToken) => Promise<TestReport>;
+
+interface PatternChoice extends vscode.QuickPickItem {
+	readonly selected: TestPattern | 'all';
+}
+
+/**
+ * Pick the pattern to test and the text to test it against
+ * Patterns come from the active editor, narrowed to the selections when
+ * asked, or are typed in when the file has none; with allowAll, every
+ * pattern can be picked at once. Returns undefined when there is nothing
+ * to test or a prompt is dismissed.
+ */
+export async function pickTestSubject(
+	deps: Readonly<{ notifier: Notifier }>,
+	options: Readonly<{ allowAll: boolean }>,
+): Promise<TestSubject | undefined> {
+	const editor = vscode.window.activeTextEditor;
+	if (!editor) {
+		deps.notifier.showWarning(
+			localize(
+				'runtime.test.no-editor',
+				'No active editor. Please open a file first.',
+			),
+		);
+		return undefined;
+	}
+
+	const config = getConfiguration();
+	const document = editor.document;
+
+	// Perform safety checks
+	const safetyResult = handleSafetyChecks(document, config);
+	if (!safetyResult.proceed) {
+		if (safetyResult.error) {
+			await deps.notifier.showEnhancedError(safetyResult.error);
+		} else {
+			deps.notifier.showError(safetyResult.message);
+		}
+		return undefined;
+	}
+
+	const text = document.getText();
+
+	// Selections narrow the patterns or become the test input
+	const selections = nonEmptySelections(editor);
+	const scope =
+		selections.length > 0
+			? await pickSelectionScope(selections.length, { allowInput: true })
+			: 'document';
+	if (!scope) {
+		return undefined;
+	}
+	const ranges =
+		scope === 'input'
+			? selections.map((selection) => toTextRange(document, selection))
+			: undefined;
+
+	// Extract regex patterns from the file
+	const documentPatterns = extractRegexPatterns(text, document.languageId);
+	const patterns =
+		scope === 'patterns'
+			? patternsWithin(documentPatterns, selections.map(toSourceLocation))
+			: documentPatterns;
+
+	if (patterns.length === 0) {
+		deps.notifier.showInfo(
+			scope === 'patterns'
+				? localize(
+						'runtime.test.no-patterns-in-selection',
+						'No regex patterns found in the selection. Provide a pattern to test.',
+					)
+				: localize(
+						'runtime.test.no-patterns',
+						'No regex patterns found in the file. Select text or provide a pattern to test.',
+					),
+		);
+
+		// Fallback: prompt for pattern if none found
+		const typed = await promptForTestPattern();
+		return typed && { text, config, ranges, patterns, selected: typed };
+	}
+
+	// If patterns found, let user select which one(s) to test
+	const choices = patterns.map(
+		(p): PatternChoice => ({
+			label: `/${p.pattern}/${p.flags}`,
+			description: p.partial
+				? `Line ${p.line} (partially resolved)`
+				: `Line ${p.line}`,
+			selected: { pattern: p.pattern, flags: p.flags, dialect: p.dialect },
+		}),
+	);
+	if (options.allowAll) {
+		choices.push({
+			label: 'Test All Patterns',
+			description: `Test all ${patterns.length} patterns`,
+			selected: 'all',
+		});
+	}
+
+	const picked = await vscode.window.showQuickPick(choices, {
+		placeHolder: ranges
+			? localize(
+					'runtime.test.select-pattern-for-selection',
+					'Select a pattern to test against the selection',
+				)
+			: localize(
+					'runtime.test.select-pattern',
+					'Select a pattern to test against the file',
+				),
+	});
+	return (
+		picked && { text, config, ranges, patterns, selected: picked.selected }
+	);
+}
+
+async function promptForTestPattern(): Promise<TestPattern | undefined> {
+	const patternInput = await vscode.window.showInputBox({
+		prompt: localize(
+			'runtime.test.pattern.prompt',
+			'Enter regex pattern to test',
+		),
+		placeHolder: localize('runtime.test.pattern.placeholder', 'e.g., /\\d+/'),
+		validateInput: (value) => {
+			if (!value || value.trim().length === 0) {
+				return localize(
+					'runtime.test.pattern.invalid',
+					'Pattern cannot be empty',
+				);
+			}
+			return null;
+		},
+	});
+	if (!patternInput) {
+		return undefined;
+	}
+
+	// Parse the input pattern
+	const patternMatch = patternInput.match(/^\/(.+)\/([gimsuvy]*)$/);
+	return {
+		pattern: patternMatch?.[1] ?? patternInput.trim(),
+		flags: patternMatch?.[2] ?? '',
+		dialect: 'javascript',
+	};
+}
+
+/**
+ * Run one pattern and open its report
+ * Patterns from other languages run through an equivalent JavaScript
+ * pattern, and one with a high ReDoS risk only runs once confirmed. prepare
+ * asks whatever the mode needs before the run starts, so no prompt opens
+ * behind the progress notification; the run itself can be cancelled there.
+ */
+export async function runPatternTest(
+	target: TestPattern,
+	config: ReturnType<typeof getConfiguration>,
+	prepare: (test: PreparedTest) => Promise<TestRun | undefined>,
+): Promise<void> {
+	const { pattern, flags, dialect } = target;
+	const dialectCheck = checkDialectSyntax(pattern, flags, dialect);
+	const translation = toJavaScriptPattern(pattern, flags, dialect);
+
+	// Check for ReDoS if enabled (linear-time engines cannot backtrack)
+	let redos: ReDoSResult = { detected: false, severity: 'low', reason: '' };
+	if (config.regexRedosDetectionEnabled && !dialectCheck.linearTime) {
+		redos = detectReDoS(translation.pattern, translation.flags);
+		if (redos.detected && redos.severity === 'high') {
+			const proceed = await vscode.window.showWarningMessage(
+				`ReDoS vulnerability detected: ${redos.reason}. Continue?`,
+				{ modal: true },
+				'Proceed',
+				'Cancel',
+			);
+
+			if (proceed !== 'Proceed') {
+				return;
+			}
+		}
+	}
+
+	const run = await prepare({
+		pattern,
+		flags,
+		dialect,
+		dialectCheck,
+		translation,
+		redos,
+	});
+	if (!run) {
+		return;
+	}
+
+	const report = await vscode.window.withProgress(
+		{
+			location: vscode.ProgressLocation.Notification,
+			title: localize('runtime.test.progress', 'Testing regex pattern...'),
+			cancellable: true,
+		},
+		(_progress, token) => run(token),
+	);
+
+	// Copy to clipboard if enabled
+	if (config.copyToClipboardEnabled) {
+		await vscode.env.clipboard.writeText(report.content);
+	}
+
+	const viewColumn = await openReport(report.content, config);
+	report.opened?.(viewColumn);
+}
+
+/**
+ * Open a Markdown report, beside the editor when openResultsSideBySide is set
+ */
+export async function openReport(
+	content: string,
+	config: ReturnType<typeof getConfiguration>,
+): Promise<vscode.ViewColumn> {
+	const doc = await vscode.workspace.openTextDocument({
+		content,
+		language: 'markdown',
+	});
+
+	const viewColumn = config.openResultsSideBySide
+		? vscode.ViewColumn.Beside
+		: vscode.ViewColumn.Active;
+
+	await vscode.window.showTextDocument(doc, viewColumn);
+	return viewColumn;
+}
+
+/**
+ * Report a test that threw
+ */
+export function showTestError(
+	error: unknown,
+	deps: Readonly<{ telemetry: Telemetry; notifier: Notifier }>,
+): void {
+	const errorMessage = error instanceof Error ? error.message : String(error);
+	deps.notifier.showError(
+		localize('runtime.test.error', 'Testing failed: {0}', errorMessage),
+	);
+	deps.telemetry.event('test-failed', { error: errorMessage });
+}
+
+/**
+ * Status line for a test, noting when its matches are partial
+ */
+export function testStatus(testResult: RegexTestResult): string {
+	if (testResult.success) {
+		return '✅ Success';
+	}
+	if (isTimedOut(testResult)) {
+		return '⏱️ Timed out (partial results)';
+	}
+	if (isCancelled(testResult)) {
+		return '🛑 Cancelled (partial results)';
+	}
+	return '❌ Failed';
+}
+
+/**
+ * Number of matches found, e.g. "1000 of 4210 (capped by the match limit)"
+ * when the limit cut the list short
+ */
+export function matchCount(testResult: RegexTestResult): string {
+	const found = testResult.matches.length;
+	if (!testResult.truncated) {
+		return String(found);
+	}
+	return testResult.totalMatches === undefined
+		? `${found} (capped by the match limit; the rest were not counted in time)`
+		: `${found} of ${testResult.totalMatches} (capped by the match limit)`;
+}
+
+/**
+ * Group number and, for named groups, the name, e.g. "Group 1 (year)"
+ */
+export function groupLabel(group: Pick<RegexGroup, 'index' | 'name'>): string {
+	const label = `Group ${group.index + 1}`;
+	return group.name ? `${label} (${group.name})` : label;
+}
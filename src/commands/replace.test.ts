@@ -0,0 +1,113 @@
+import { beforeEach, describe, expect, it, vi } from 'vitest';
+import * as vscode from 'vscode';
+import { runRegexTest } from '../extraction/regex/regexRunner';
+import { registerReplaceCommand } from './replace';
+
+vi.mock('vscode');
+vi.mock('../extraction/regex/regexRunner', () => ({
+	runRegexTest: vi.fn(),
+	isTimedOut: () => false,
+	isCancelled: () => false,
+}));
+
+const TEXT = 'a-a-a';
+
+describe('registerReplaceCommand', () => {
+	const deps = {
+		telemetry: { event: vi.fn(), dispose: vi.fn() },
+		notifier: {
+			showInfo: vi.fn(),
+			showWarning: vi.fn(),
+			showError: vi.fn(),
+			showEnhancedError: vi.fn(),
+		},
+		statusBar: { updateText: vi.fn() },
+		preview: { show: vi.fn(() => ({ path: '/preview' })) },
+	};
+
+	let replace: () => Promise<void>;
+
+	beforeEach(() => {
+		vi.clearAllMocks();
+
+		(vscode.window as any).activeTextEditor = {
+			document: {
+				uri: { path: '/work/a.txt' },
+				fileName: '/work/a.txt',
+				languageId: 'plaintext',
+				version: 1,
+				getText: () => TEXT,
+				positionAt: (offset: number) => ({ offset }),
+			},
+			selections: [],
+		};
+		vi.mocked(vscode.workspace.getConfiguration).mockReturnValue({
+			get: (_key: string, fallback: unknown) => fallback,
+		} as any);
+		vi.mocked(vscode.window.showInputBox)
+			.mockResolvedValueOnce('/a/g')
+			.mockResolvedValueOnce('b');
+		vi.mocked(vscode.window.withProgress).mockImplementation(
+			(_options: unknown, task: any) =>
+				task({ report: vi.fn() }, { isCancellationRequested: false }),
+		);
+		vi.mocked(vscode.window.showInformationMessage).mockImplementation(
+			((_message: string, apply: string) => Promise.resolve(apply)) as any,
+		);
+		vi.mocked(vscode.workspace.applyEdit).mockResolvedValue(true);
+
+		registerReplaceCommand({ subscriptions: [] } as any, deps as any);
+		replace = vi.mocked(vscode.commands.registerCommand).mock.calls[0]?.[1];
+	});
+
+	it('should apply a capped replace and report it as truncated', async () => {
+		vi.mocked(runRegexTest).mockResolvedValue({
+			success: true,
+			pattern: 'a',
+			flags: 'g',
+			matches: [
+				{ match: 'a', index: 0 },
+				{ match: 'a', index: 2 },
+			],
+			errors: [],
+			truncated: true,
+			totalMatches: 3,
+		} as any);
+
+		await replace();
+
+		expect(vscode.window.showInformationMessage).toHaveBeenCalledWith(
+			'runtime.replace.confirm-limited',
+			'runtime.replace.apply',
+			'runtime.replace.discard',
+		);
+		expect(vscode.workspace.applyEdit).toHaveBeenCalled();
+		expect(deps.notifier.showError).not.toHaveBeenCalled();
+		expect(deps.telemetry.event).toHaveBeenCalledWith('test-replace-applied', {
+			applied: true,
+			replacementCount: 2,
+			truncated: true,
+		});
+	});
+
+	it('should report a replace within the limit as not truncated', async () => {
+		vi.mocked(runRegexTest).mockResolvedValue({
+			success: true,
+			pattern: 'a',
+			flags: 'g',
+			matches: [{ match: 'a', index: 4 }],
+			errors: [],
+			truncated: false,
+			totalMatches: 1,
+		} as any);
+
+		await replace();
+
+		expect(deps.notifier.showError).not.toHaveBeenCalled();
+		expect(deps.telemetry.event).toHaveBeenCalledWith('test-replace-applied', {
+			applied: true,
+			replacementCount: 1,
+			truncated: false,
+		});
+	});
+});
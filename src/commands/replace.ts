@@ -0,0 +1,381 @@
+import * as vscode from 'vscode';
+import * as nls from 'vscode-nls';
+import { getConfiguration } from '../config/config';
+import { toJavaScriptPattern } from '../extraction/regex/dialects';
+import {
+	type ExtractedRegexPattern,
+	extractRegexPatterns,
+	patternsWithin,
+} from '../extraction/regex/extractPatterns';
+import {
+	isCancelled,
+	isTimedOut,
+	runRegexTest,
+} from '../extraction/regex/regexRunner';
+import { captureGroupNames } from '../extraction/regex/regexTest';
+import {
+	applyReplacements,
+	planReplacements,
+} from '../extraction/regex/replace';
+import {
+	createReplacePreview,
+	REPLACE_PREVIEW_SCHEME,
+	type ReplacePreview,
+} from '../providers/replacePreview';
+import type { Telemetry } from '../telemetry/telemetry';
+import type { RegexDialect } from '../types';
+import type { Notifier } from '../ui/notifier';
+import type { StatusBar } from '../ui/statusBar';
+import { handleSafetyChecks } from '../utils/safety';
+import {
+	nonEmptySelections,
+	pickSelectionScope,
+	toSourceLocation,
+	toTextRange,
+} from '../utils/selection';
+
+const localize = nls.config({ messageFormat: nls.MessageFormat.file })();
+
+export interface ReplacePattern {
+	readonly pattern: string;
+	readonly flags: string;
+	readonly dialect: RegexDialect;
+}
+
+interface PatternChoice extends vscode.QuickPickItem {
+	readonly pattern?: ExtractedRegexPattern;
+}
+
+/**
+ * Register the read-only documents shown on the right of replace diffs
+ */
+export function registerReplacePreview(
+	context: vscode.ExtensionContext,
+): ReplacePreview {
+	const preview = createReplacePreview();
+	const provider = vscode.workspace.registerTextDocumentContentProvider(
+		REPLACE_PREVIEW_SCHEME,
+		preview,
+	);
+	context.subscriptions.push(preview, provider);
+	return preview;
+}
+
+/**
+ * Register the test replace command
+ * Replaces the matches of a pattern with a template, shows the result as a
+ * diff against the document and applies it as one undoable edit on request
+ */
+export function registerReplaceCommand(
+	context: vscode.ExtensionContext,
+	deps: Readonly<{
+		telemetry: Telemetry;
+		notifier: Notifier;
+		statusBar: StatusBar;
+		preview: ReplacePreview;
+	}>,
+): void {
+	const disposable = vscode.commands.registerCommand(
+		'regex-le.testReplace',
+		async (): Promise<void> => {
+			deps.telemetry.event('command-test-replace');
+
+			const editor = vscode.window.activeTextEditor;
+			if (!editor) {
+				deps.notifier.showWarning(
+					localize(
+						'runtime.replace.no-editor',
+						'No active editor. Please open a file first.',
+					),
+				);
+				return;
+			}
+
+			const config = getConfiguration();
+			const document = editor.document;
+
+			const safetyResult = handleSafetyChecks(document, config);
+			if (!safetyResult.proceed) {
+				if (safetyResult.error) {
+					await deps.notifier.showEnhancedError(safetyResult.error);
+				} else {
+					deps.notifier.showError(safetyResult.message);
+				}
+				return;
+			}
+
+			const text = document.getText();
+			const version = document.version;
+
+			// Selections narrow the patterns or limit where replacing happens
+			const selections = nonEmptySelections(editor);
+			const scope =
+				selections.length > 0
+					? await pickSelectionScope(selections.length, { allowInput: true })
+					: 'document';
+			if (!scope) {
+				return;
+			}
+			const inputRanges =
+				scope === 'input'
+					? selections.map((selection) => toTextRange(document, selection))
+					: undefined;
+
+			const documentPatterns = extractRegexPatterns(
+				text,
+				document.languageId,
+			);
+			const target = await pickPattern(
+				scope === 'patterns'
+					? patternsWithin(documentPatterns, selections.map(toSourceLocation))
+					: documentPatterns,
+			);
+			if (!target) {
+				return;
+			}
+
+			const template = await vscode.window.showInputBox({
+				prompt: localize(
+					'runtime.replace.template.prompt',
+					'Enter the replacement ($1, $<name>, $& and $$ are substituted)',
+				),
+				placeHolder: localize(
+					'runtime.replace.template.placeholder',
+					'e.g., $2-$1',
+				),
+			});
+			if (template === undefined) {
+				return;
+			}
+
+			try {
+				const translation = toJavaScriptPattern(
+					target.pattern,
+					target.flags,
+					target.dialect,
+				);
+				const result = await vscode.window.withProgress(
+					{
+						location: vscode.ProgressLocation.Notification,
+						title: localize(
+							'runtime.replace.progress',
+							'Finding matches to replace...',
+						),
+						cancellable: true,
+					},
+					(_progress, token) =>
+						runRegexTest(
+							translation.pattern,
+							translation.flags,
+							text,
+							config.regexMaxMatchLimit,
+							config.performanceMaxDuration,
+							inputRanges,
+							token,
+						),
+				);
+
+				// A partial match list would leave later matches unreplaced
+				if (isTimedOut(result) || isCancelled(result)) {
+					deps.notifier.showWarning(
+						localize(
+							'runtime.replace.stopped',
+							'Matching stopped before all matches were found; nothing was replaced',
+						),
+					);
+					return;
+				}
+				if (!result.success) {
+					deps.notifier.showError(
+						localize(
+							'runtime.replace.error',
+							'Replace failed: {0}',
+							result.errors.map((error) => error.message).join('; '),
+						),
+					);
+					return;
+				}
+				if (result.matches.length === 0) {
+					deps.notifier.showInfo(
+						localize('runtime.replace.no-matches', 'No matches to replace.'),
+					);
+					return;
+				}
+
+				const replacements = planReplacements(
+					text,
+					result.matches,
+					template,
+					captureGroupNames(translation.pattern, translation.flags),
+				);
+				const fileName = vscode.workspace.asRelativePath(document.uri);
+				const previewUri = deps.preview.show(
+					document.uri,
+					applyReplacements(text, replacements),
+				);
+				await vscode.commands.executeCommand(
+					'vscode.diff',
+					document.uri,
+					previewUri,
+					localize(
+						'runtime.replace.diff-title',
+						'{0} ↔ Replace Preview',
+						fileName,
+					),
+				);
+
+				const apply = localize('runtime.replace.apply', 'Apply');
+				const choice = await vscode.window.showInformationMessage(
+					result.truncated
+						? localize(
+								'runtime.replace.confirm-limited',
+								'Replace the first {0} of {1} matches in {2}? The match limit was reached.',
+								replacements.length,
+								result.totalMatches ?? `${replacements.length}+`,
+								fileName,
+							)
+						: localize(
+								'runtime.replace.confirm',
+								'Replace {0} matches in {1}?',
+								replacements.length,
+								fileName,
+							),
+					apply,
+					localize('runtime.replace.discard', 'Discard'),
+				);
+				if (choice !== apply) {
+					deps.telemetry.event('test-replace-discarded', {
+						replacementCount: replacements.length,
+					});
+					return;
+				}
+
+				// Offsets were planned for the text as it was
+				if (document.version !== version) {
+					deps.notifier.showWarning(
+						localize(
+							'runtime.replace.changed',
+							'The document changed since the preview was made. Run Test Replace again.',
+						),
+					);
+					return;
+				}
+
+				const edit = new vscode.WorkspaceEdit();
+				for (const replacement of replacements) {
+					edit.replace(
+						document.uri,
+						new vscode.Range(
+							document.positionAt(replacement.start),
+							document.positionAt(replacement.end),
+						),
+						replacement.replacement,
+					);
+				}
+				const applied = await vscode.workspace.applyEdit(edit);
+				if (applied) {
+					deps.statusBar.updateText(
+						localize(
+							'runtime.replace.complete',
+							'Replaced {0} matches',
+							replacements.length,
+						),
+					);
+				}
+
+				deps.telemetry.event('test-replace-applied', {
+					applied,
+					replacementCount: replacements.length,
+					truncated: result.truncated === true,
+				});
+			} catch (error) {
+				const errorMessage =
+					error instanceof Error ? error.message : String(error);
+				deps.notifier.showError(
+					localize(
+						'runtime.replace.error',
+						'Replace failed: {0}',
+						errorMessage,
+					),
+				);
+				deps.telemetry.event('test-replace-failed', { error: errorMessage });
+			}
+		},
+	);
+
+	context.subscriptions.push(disposable);
+}
+
+/**
+ * Choose an extracted pattern or enter one
+ * Goes straight to the input box when there is nothing to choose from.
+ */
+async function pickPattern(
+	patterns: readonly ExtractedRegexPattern[],
+): Promise<ReplacePattern | undefined> {
+	if (patterns.length === 0) {
+		return promptForPattern();
+	}
+
+	const choices: PatternChoice[] = patterns.map(
+		(p): PatternChoice => ({
+			label: `/${p.pattern}/${p.flags}`,
+			description: p.partial
+				? `Line ${p.line} (partially resolved)`
+				: `Line ${p.line}`,
+			pattern: p,
+		}),
+	);
+	choices.push({
+		label: localize('runtime.replace.enter-pattern', 'Enter a Pattern...'),
+	});
+
+	const selected = await vscode.window.showQuickPick(choices, {
+		placeHolder: localize(
+			'runtime.replace.select-pattern',
+			'Select a pattern to replace matches of',
+		),
+	});
+	if (!selected) {
+		return undefined;
+	}
+	return selected.pattern ?? promptForPattern();
+}
+
+/**
+ * Ask for a pattern in /pattern/flags form, read as JavaScript
+ */
+export async function promptForPattern(): Promise<
+	ReplacePattern | undefined
+> {
+	const patternInput = await vscode.window.showInputBox({
+		prompt: localize(
+			'runtime.replace.pattern.prompt',
+			'Enter regex pattern to replace',
+		),
+		placeHolder: localize(
+			'runtime.replace.pattern.placeholder',
+			'e.g., /\\d+/g',
+		),
+		validateInput: (value) => {
+			if (!value || value.trim().length === 0) {
+				return localize(
+					'runtime.replace.pattern.invalid',
+					'Pattern cannot be empty',
+				);
+			}
+			return null;
+		},
+	});
+	if (!patternInput) {
+		return undefined;
+	}
+
+	// Parse the input pattern
+	const patternMatch = patternInput.match(/^\/(.+)\/([dgimsuvy]*)$/);
+	return Object.freeze({
+		pattern: patternMatch?.[1] ?? patternInput.trim(),
+		flags: patternMatch?.[2] ?? '',
+		dialect: 'javascript',
+	});
+}
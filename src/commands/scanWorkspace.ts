@@ -0,0 +1,207 @@
+import * as vscode from 'vscode';
+import * as nls from 'vscode-nls';
+import { getConfiguration } from '../config/config';
+import {
+	countRisks,
+	type FileInventory,
+	formatInventoryReport,
+	inventoryFile,
+	languageIdForPath,
+	type ScanSummary,
+} from '../extraction/regex/inventory';
+import type { InventoryTree } from '../providers/inventoryTree';
+import type { Telemetry } from '../telemetry/telemetry';
+import type { Configuration } from '../types';
+import type { Notifier } from '../ui/notifier';
+import type { StatusBar } from '../ui/statusBar';
+import {
+	findWorkspaceFiles,
+	readWorkspaceText,
+} from '../utils/workspaceFiles';
+
+const localize = nls.config({ messageFormat: nls.MessageFormat.file })();
+
+export interface ScannedFile {
+	readonly uri: vscode.Uri;
+	readonly inventory: FileInventory;
+}
+
+export interface WorkspaceScanResult {
+	readonly files: readonly ScannedFile[];
+	readonly summary: ScanSummary;
+}
+
+/**
+ * Register the workspace scan command
+ * Builds a regex inventory of every matching file in the workspace and
+ * shows it in the report and the inventory view
+ */
+export function registerScanWorkspaceCommand(
+	context: vscode.ExtensionContext,
+	deps: Readonly<{
+		telemetry: Telemetry;
+		notifier: Notifier;
+		statusBar: StatusBar;
+		inventory: InventoryTree;
+	}>,
+): void {
+	const disposable = vscode.commands.registerCommand(
+		'regex-le.scanWorkspace',
+		async (): Promise<void> => {
+			deps.telemetry.event('command-scan-workspace');
+
+			if (!vscode.workspace.workspaceFolders?.length) {
+				deps.notifier.showWarning(
+					localize(
+						'runtime.scan.no-workspace',
+						'No workspace folder is open. Please open a folder first.',
+					),
+				);
+				return;
+			}
+
+			const config = getConfiguration();
+
+			try {
+				const result = await vscode.window.withProgress(
+					{
+						location: vscode.ProgressLocation.Notification,
+						title: localize(
+							'runtime.scan.progress',
+							'Scanning workspace for regex patterns...',
+						),
+						cancellable: true,
+					},
+					(progress, token) => scanWorkspace(config, progress, token),
+				);
+
+				deps.inventory.setFiles(result.files);
+
+				const inventories = result.files.map((file) => file.inventory);
+				const counts = countRisks(inventories);
+				const total = inventories.reduce(
+					(sum, file) => sum + file.entries.length,
+					0,
+				);
+
+				const doc = await vscode.workspace.openTextDocument({
+					content: formatInventoryReport(inventories, result.summary),
+					language: 'markdown',
+				});
+
+				const viewColumn = config.openResultsSideBySide
+					? vscode.ViewColumn.Beside
+					: vscode.ViewColumn.Active;
+
+				await vscode.window.showTextDocument(doc, viewColumn);
+
+				deps.statusBar.updateText(
+					localize(
+						'runtime.scan.complete',
+						'Found {0} patterns in {1} files',
+						total,
+						result.summary.filesScanned,
+					),
+				);
+
+				deps.telemetry.event('scan-workspace-completed', {
+					filesScanned: result.summary.filesScanned,
+					filesSkipped: result.summary.filesSkipped,
+					cancelled: result.summary.cancelled,
+					patternCount: total,
+					highRisk: counts.high,
+					mediumRisk: counts.medium,
+				});
+
+				if (
+					counts.high > 0 &&
+					config.notificationsLevel !== 'silent' &&
+					!result.summary.cancelled
+				) {
+					deps.notifier.showWarning(
+						localize(
+							'runtime.scan.high-risk',
+							'{0} high-risk regex patterns found in the workspace',
+							counts.high,
+						),
+					);
+				} else if (config.notificationsLevel === 'all') {
+					deps.notifier.showInfo(
+						localize(
+							'runtime.scan.complete',
+							'Found {0} patterns in {1} files',
+							total,
+							result.summary.filesScanned,
+						),
+					);
+				}
+			} catch (error) {
+				const errorMessage =
+					error instanceof Error ? error.message : String(error);
+				deps.notifier.showError(
+					localize('runtime.scan.error', 'Scan failed: {0}', errorMessage),
+				);
+				deps.telemetry.event('scan-workspace-failed', {
+					error: errorMessage,
+				});
+			}
+		},
+	);
+
+	context.subscriptions.push(disposable);
+}
+
+/**
+ * Extract and assess the patterns of every file the scan settings match
+ * Stops early when cancelled and reports what was scanned so far.
+ */
+export async function scanWorkspace(
+	config: Configuration,
+	progress: vscode.Progress<{ message?: string; increment?: number }>,
+	token: vscode.CancellationToken,
+): Promise<WorkspaceScanResult> {
+	const uris = await findWorkspaceFiles(config, config.scanInclude, token);
+	const files: ScannedFile[] = [];
+	let filesScanned = 0;
+	let filesSkipped = 0;
+
+	for (const uri of uris) {
+		if (token.isCancellationRequested) {
+			break;
+		}
+
+		const relativePath = vscode.workspace.asRelativePath(uri);
+		progress.report({
+			message: relativePath,
+			increment: 100 / uris.length,
+		});
+
+		const text = await readWorkspaceText(uri, config);
+		if (text === undefined) {
+			filesSkipped++;
+			continue;
+		}
+
+		filesScanned++;
+		files.push(
+			Object.freeze({
+				uri,
+				inventory: inventoryFile(
+					relativePath,
+					text,
+					languageIdForPath(uri.path),
+					{ redosDetectionEnabled: config.regexRedosDetectionEnabled },
+				),
+			}),
+		);
+	}
+
+	return Object.freeze({
+		files: Object.freeze(files),
+		summary: Object.freeze({
+			filesScanned,
+			filesSkipped,
+			cancelled: token.isCancellationRequested,
+		}),
+	});
+}
@@ -0,0 +1,354 @@
+import * as vscode from 'vscode';
+import * as nls from 'vscode-nls';
+import { getConfiguration } from '../config/config';
+import { createLineIndex } from '../extraction/regex/lineIndex';
+import {
+	parseSpecFile,
+	type RegexSpec,
+	runSpecCase,
+	SPEC_FILE_GLOB,
+	type SpecCase,
+} from '../extraction/regex/specFile';
+import type { Telemetry } from '../telemetry/telemetry';
+import type { Notifier } from '../ui/notifier';
+
+const localize = nls.config({ messageFormat: nls.MessageFormat.file })();
+
+const CONTROLLER_ID = 'regex-le.specs';
+
+// Quiet time after the last edit before a spec file is parsed again
+const EDIT_RELOAD_DELAY_MS = 300;
+
+interface CaseTarget {
+	readonly spec: RegexSpec;
+	readonly testCase: SpecCase;
+}
+
+/**
+ * Register *.regex-test.json spec files with the Test Explorer
+ * Files are found when the explorer first asks for tests or when one is
+ * opened, and kept up to date by a file watcher and editor changes; every
+ * case is a test item run in a regex worker within performance.maxDuration,
+ * with a diff for capture groups that differ.
+ */
+export function registerSpecTests(
+	context: vscode.ExtensionContext,
+	deps: Readonly<{
+		telemetry: Telemetry;
+		notifier: Notifier;
+	}>,
+): void {
+	const controller = vscode.tests.createTestController(
+		CONTROLLER_ID,
+		localize('runtime.specs.label', 'Regex Specs'),
+	);
+	const targets = new WeakMap<vscode.TestItem, CaseTarget>();
+
+	const createFileItem = async (
+		uri: vscode.Uri,
+	): Promise<vscode.TestItem | undefined> => {
+		const text = await readSpecText(uri);
+		if (text === undefined) {
+			return undefined;
+		}
+
+		const parsed = parseSpecFile(text);
+		const lines = createLineIndex(text);
+		const rangeAt = (offset: number): vscode.Range | undefined => {
+			if (offset < 0) {
+				return undefined;
+			}
+			const line = lines.lineAt(offset);
+			const position = new vscode.Position(
+				line - 1,
+				offset - lines.lineStart(line),
+			);
+			return new vscode.Range(position, position);
+		};
+
+		const file = controller.createTestItem(
+			uri.toString(),
+			vscode.workspace.asRelativePath(uri),
+			uri,
+		);
+		file.error =
+			parsed.errors.length > 0 ? parsed.errors.join('\n') : undefined;
+		file.children.replace(
+			parsed.specs.map((spec, specIndex) => {
+				const item = controller.createTestItem(
+					`${file.id}#${specIndex}`,
+					spec.name ?? `/${spec.pattern}/${spec.flags}`,
+					uri,
+				);
+				item.range = rangeAt(spec.offset);
+				item.children.replace(
+					spec.cases.map((testCase, caseIndex) => {
+						const child = controller.createTestItem(
+							`${item.id}/${caseIndex}`,
+							caseLabel(testCase),
+							uri,
+						);
+						child.range = rangeAt(testCase.offset);
+						targets.set(child, { spec, testCase });
+						return child;
+					}),
+				);
+				return item;
+			}),
+		);
+		return file;
+	};
+
+	// Loads of the same file can overlap, e.g. a refresh while it is being
+	// edited; only the one started last may show its result
+	const latestLoads = new Map<string, number>();
+	let loadCount = 0;
+	const loadLatest = async (
+		uri: vscode.Uri,
+	): Promise<{ file: vscode.TestItem | undefined; latest: boolean }> => {
+		const key = uri.toString();
+		const load = ++loadCount;
+		latestLoads.set(key, load);
+		const file = await createFileItem(uri);
+		return { file, latest: latestLoads.get(key) === load };
+	};
+
+	const loadFile = async (uri: vscode.Uri): Promise<void> => {
+		const { file, latest } = await loadLatest(uri);
+		if (!latest) {
+			return;
+		}
+		if (file) {
+			controller.items.add(file);
+		} else {
+			controller.items.delete(uri.toString());
+		}
+	};
+
+	// Only files.exclude applies: scan settings limit what gets scanned for
+	// patterns, and leaving out specs would hide their failures. Files are
+	// swapped in at once, so the explorer is never emptied while loading.
+	const discover = async (): Promise<void> => {
+		const uris = await vscode.workspace.findFiles(SPEC_FILE_GLOB);
+		const loaded = await Promise.all(
+			uris.map(async (uri) => {
+				const { file, latest } = await loadLatest(uri);
+				// A newer load of the file adds its own item
+				return latest ? file : controller.items.get(uri.toString());
+			}),
+		);
+		controller.items.replace(
+			loaded.filter((file): file is vscode.TestItem => file !== undefined),
+		);
+	};
+
+	controller.resolveHandler = async (item) => {
+		if (!item) {
+			await discover();
+		}
+	};
+	controller.refreshHandler = () => discover();
+
+	const run = async (
+		request: vscode.TestRunRequest,
+		token: vscode.CancellationToken,
+	): Promise<void> => {
+		const testRun = controller.createTestRun(request);
+		const excluded = new Set(request.exclude ?? []);
+		const cases: vscode.TestItem[] = [];
+		const collect = (item: vscode.TestItem): void => {
+			if (excluded.has(item)) {
+				return;
+			}
+			if (targets.has(item)) {
+				cases.push(item);
+			}
+			item.children.forEach(collect);
+		};
+		if (request.include) {
+			request.include.forEach(collect);
+		} else {
+			controller.items.forEach(collect);
+		}
+
+		for (const item of cases) {
+			testRun.enqueued(item);
+		}
+
+		const timeoutMs = getConfiguration().performanceMaxDuration;
+		let passed = 0;
+		let failed = 0;
+		for (const item of cases) {
+			const target = targets.get(item);
+			if (!target || token.isCancellationRequested) {
+				testRun.skipped(item);
+				continue;
+			}
+
+			testRun.started(item);
+			const start = performance.now();
+			try {
+				const result = await runSpecCase(
+					target.spec,
+					target.testCase,
+					timeoutMs,
+					token,
+				);
+				const duration = performance.now() - start;
+				if (result.errored) {
+					if (token.isCancellationRequested) {
+						testRun.skipped(item);
+					} else {
+						testRun.errored(
+							item,
+							new vscode.TestMessage(result.message ?? ''),
+							duration,
+						);
+					}
+					continue;
+				}
+				if (result.passed) {
+					passed++;
+					testRun.passed(item, duration);
+					continue;
+				}
+
+				failed++;
+				const text = result.message ?? '';
+				const message =
+					result.expected !== undefined && result.actual !== undefined
+						? vscode.TestMessage.diff(text, result.expected, result.actual)
+						: new vscode.TestMessage(text);
+				if (item.uri && item.range) {
+					message.location = new vscode.Location(item.uri, item.range);
+				}
+				testRun.failed(item, message, duration);
+			} catch (error) {
+				const errorMessage =
+					error instanceof Error ? error.message : String(error);
+				testRun.errored(item, new vscode.TestMessage(errorMessage));
+			}
+		}
+
+		testRun.end();
+		deps.telemetry.event('specs-run', {
+			caseCount: cases.length,
+			passed,
+			failed,
+		});
+	};
+
+	const profile = controller.createRunProfile(
+		localize('runtime.specs.run', 'Run Regex Specs'),
+		vscode.TestRunProfileKind.Run,
+		run,
+		true,
+	);
+
+	// Spec files created, saved or deleted outside the editor
+	const watcher = vscode.workspace.createFileSystemWatcher(SPEC_FILE_GLOB);
+	const reload = (uri: vscode.Uri): void => {
+		loadFile(uri).catch((error: unknown) => {
+			const errorMessage =
+				error instanceof Error ? error.message : String(error);
+			deps.notifier.showError(
+				localize(
+					'runtime.specs.load-error',
+					'Could not load regex spec file {0}: {1}',
+					vscode.workspace.asRelativePath(uri),
+					errorMessage,
+				),
+			);
+		});
+	};
+	watcher.onDidCreate(reload);
+	watcher.onDidChange(reload);
+	watcher.onDidDelete((uri) => controller.items.delete(uri.toString()));
+
+	// Open spec files get their gutter buttons before the explorer is shown
+	const isSpecFile = (document: vscode.TextDocument): boolean =>
+		document.uri.scheme === 'file' &&
+		vscode.languages.match({ pattern: SPEC_FILE_GLOB }, document) > 0;
+	for (const document of vscode.workspace.textDocuments) {
+		if (isSpecFile(document)) {
+			reload(document.uri);
+		}
+	}
+	const onOpen = vscode.workspace.onDidOpenTextDocument((document) => {
+		if (isSpecFile(document)) {
+			reload(document.uri);
+		}
+	});
+
+	// Edits reload once typing pauses rather than on every keystroke
+	const pendingReloads = new Map<string, ReturnType<typeof setTimeout>>();
+	const onChange = vscode.workspace.onDidChangeTextDocument((event) => {
+		if (!isSpecFile(event.document)) {
+			return;
+		}
+		const uri = event.document.uri;
+		const key = uri.toString();
+		clearTimeout(pendingReloads.get(key));
+		pendingReloads.set(
+			key,
+			setTimeout(() => {
+				pendingReloads.delete(key);
+				reload(uri);
+			}, EDIT_RELOAD_DELAY_MS),
+		);
+	});
+	const cancelReloads = {
+		dispose: (): void => {
+			for (const timer of pendingReloads.values()) {
+				clearTimeout(timer);
+			}
+			pendingReloads.clear();
+		},
+	};
+
+	context.subscriptions.push(
+		controller,
+		profile,
+		watcher,
+		onOpen,
+		onChange,
+		cancelReloads,
+	);
+}
+
+/**
+ * Text of a spec file, from its document when open so unsaved edits count
+ * Unlike scanned files, specs are read whatever their size.
+ */
+async function readSpecText(uri: vscode.Uri): Promise<string | undefined> {
+	const open = vscode.workspace.textDocuments.find(
+		(document) => document.uri.toString() === uri.toString(),
+	);
+	if (open) {
+		return open.getText();
+	}
+	try {
+		return new TextDecoder().decode(await vscode.workspace.fs.readFile(uri));
+	} catch {
+		return undefined;
+	}
+}
+
+/**
+ * Test item label of a case, e.g. matches "2024-01"
+ */
+function caseLabel(testCase: SpecCase): string {
+	const input = JSON.stringify(testCase.input);
+	switch (testCase.kind) {
+		case 'match':
+			return localize('runtime.specs.case.match', 'matches {0}', input);
+		case 'no-match':
+			return localize(
+				'runtime.specs.case.no-match',
+				'does not match {0}',
+				input,
+			);
+		default:
+			return localize('runtime.specs.case.groups', 'groups of {0}', input);
+	}
+}
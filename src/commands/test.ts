@@ -1,21 +1,49 @@
 import * as vscode from 'vscode';
 import * as nls from 'vscode-nls';
-import { getConfiguration } from '../config/config';
-import { extractRegexPatterns } from '../extraction/regex/extractPatterns';
+import type { getConfiguration } from '../config/config';
+import {
+	type checkDialectSyntax,
+	toJavaScriptPattern,
+} from '../extraction/regex/dialects';
+import {
+	type extractRegexPatterns,
+	groupPatterns,
+} from '../extraction/regex/extractPatterns';
+import { inlineCode } from '../extraction/regex/outputFormats';
 import { calculatePerformanceScore } from '../extraction/regex/performance';
-import { detectReDoS } from '../extraction/regex/redos';
-import { testRegexWithPerformance } from '../extraction/regex/regexTest';
+import {
+	isCancelled,
+	isTimedOut,
+	runRegexTest,
+} from '../extraction/regex/regexRunner';
+import type { TextRange } from '../extraction/regex/regexTest';
 import type { Telemetry } from '../telemetry/telemetry';
+import type { RegexDialect, RegexTestResult } from '../types';
 import type { Notifier } from '../ui/notifier';
 import type { StatusBar } from '../ui/statusBar';
 import type { PerformanceMonitor } from '../utils/performance';
-import { handleSafetyChecks } from '../utils/safety';
+import {
+	groupLabel,
+	matchCount,
+	openReport,
+	type PreparedTest,
+	pickTestSubject,
+	runPatternTest,
+	showTestError,
+	testStatus,
+} from './patternTest';
 
 const localize = nls.config({ messageFormat: nls.MessageFormat.file })();
 
+// Highest limit offered when rerunning a capped test, above the 10000 that
+// regex.maxMatchLimit allows, so capped reports point to the rerun
+const MAX_RERUN_LIMIT = 100_000;
+
 /**
- * Register the regex test command
- * Tests regex patterns found in the active editor against the file content
+ * Register the test command
+ * Tests regex patterns found in the active editor against the file content.
+ * With text selected, the selections either narrow the patterns offered or
+ * become the test input instead of the whole file.
  */
 export function registerTestCommand(
 	context: vscode.ExtensionContext,
@@ -28,109 +56,28 @@ export function registerTestCommand(
 ): void {
 	const disposable = vscode.commands.registerCommand(
 		'regex-le.test',
-		async () => {
+		async (): Promise<void> => {
 			deps.telemetry.event('command-test');
 
-			const editor = vscode.window.activeTextEditor;
-			if (!editor) {
-				deps.notifier.showWarning(
-					localize(
-						'runtime.test.no-editor',
-						'No active editor. Please open a file first.',
-					),
-				);
-				return;
-			}
-
-			const config = getConfiguration();
-			const document = editor.document;
-
-			// Perform safety checks
-			const safetyResult = handleSafetyChecks(document, config);
-			if (!safetyResult.proceed) {
-				if (safetyResult.error) {
-					await deps.notifier.showEnhancedError(safetyResult.error);
-				} else {
-					deps.notifier.showError(safetyResult.message);
-				}
+			const subject = await pickTestSubject(deps, { allowAll: true });
+			if (!subject) {
 				return;
 			}
 
-			const text = document.getText();
-
-			// Extract regex patterns from the file
-			const extractedPatterns = extractRegexPatterns(text);
-
-			if (extractedPatterns.length === 0) {
-				deps.notifier.showInfo(
-					localize(
-						'runtime.test.no-patterns',
-						'No regex patterns found in the file. Select text or provide a pattern to test.',
-					),
-				);
-
-				// Fallback: prompt for pattern if none found
-				const patternInput = await vscode.window.showInputBox({
-					prompt: localize(
-						'runtime.test.pattern.prompt',
-						'Enter regex pattern to test',
-					),
-					placeHolder: localize(
-						'runtime.test.pattern.placeholder',
-						'e.g., /\\d+/',
-					),
-					validateInput: (value) => {
-						if (!value || value.trim().length === 0) {
-							return localize(
-								'runtime.test.pattern.invalid',
-								'Pattern cannot be empty',
-							);
-						}
-						return null;
-					},
-				});
-
-				if (!patternInput) {
+			const { text, config, ranges, patterns, selected } = subject;
+			try {
+				if (selected !== 'all') {
+					await testSinglePattern(
+						selected.pattern,
+						selected.flags,
+						selected.dialect,
+						text,
+						config,
+						deps,
+						ranges,
+					);
 					return;
 				}
-
-				// Parse the input pattern
-				const patternMatch = patternInput.match(/^\/(.+)\/([gimsuvy]*)$/);
-				const pattern = patternMatch?.[1] ?? patternInput.trim();
-				const flags = patternMatch?.[2] ?? '';
-
-				// Test the single pattern
-				await testSinglePattern(pattern, flags, text, config, deps);
-				return;
-			}
-
-			// If patterns found, let user select which one(s) to test
-			const patternChoices = extractedPatterns.map((p) => ({
-				label: `/${p.pattern}/${p.flags}`,
-				description: `Line ${p.line}`,
-				pattern: p.pattern,
-				flags: p.flags,
-			}));
-
-			patternChoices.push({
-				label: 'Test All Patterns',
-				description: `Test all ${extractedPatterns.length} patterns`,
-				pattern: '',
-				flags: '',
-			});
-
-			const selected = await vscode.window.showQuickPick(patternChoices, {
-				placeHolder: localize(
-					'runtime.test.select-pattern',
-					'Select a pattern to test against the file',
-				),
-			});
-
-			if (!selected) {
-				return;
-			}
-
-			try {
 				await vscode.window.withProgress(
 					{
 						location: vscode.ProgressLocation.Notification,
@@ -138,39 +85,21 @@ export function registerTestCommand(
 							'runtime.test.progress',
 							'Testing regex pattern...',
 						),
-						cancellable: false,
-					},
-					async (progress) => {
-						if (selected.pattern === '') {
-							// Test all patterns
-							await testAllPatterns(
-								extractedPatterns,
-								text,
-								config,
-								deps,
-								progress,
-							);
-						} else {
-							// Test single selected pattern
-							progress.report({ increment: 50 });
-							await testSinglePattern(
-								selected.pattern,
-								selected.flags,
-								text,
-								config,
-								deps,
-							);
-							progress.report({ increment: 100 });
-						}
+						cancellable: true,
 					},
+					(progress, token) =>
+						testAllPatterns(
+							patterns,
+							text,
+							config,
+							deps,
+							progress,
+							ranges,
+							token,
+						),
 				);
 			} catch (error) {
-				const errorMessage =
-					error instanceof Error ? error.message : String(error);
-				deps.notifier.showError(
-					localize('runtime.test.error', 'Testing failed: {0}', errorMessage),
-				);
-				deps.telemetry.event('test-failed', { error: errorMessage });
+				showTestError(error, deps);
 			}
 		},
 	);
@@ -178,9 +107,15 @@ export function registerTestCommand(
 	context.subscriptions.push(disposable);
 }
 
-async function testSinglePattern(
+/**
+ * Test one pattern against text and open the result report
+ * With ranges, only those parts of the text are searched. A test stopped by
+ * its time budget or by cancellation reports the matches found so far.
+ */
+export async function testSinglePattern(
 	pattern: string,
 	flags: string,
+	dialect: RegexDialect,
 	text: string,
 	config: ReturnType<typeof getConfiguration>,
 	deps: {
@@ -188,46 +123,73 @@ async function testSinglePattern(
 		notifier: Notifier;
 		statusBar: StatusBar;
 	},
+	ranges?: readonly TextRange[],
 ): Promise<void> {
-	const startTime = performance.now();
-
-	// Check for ReDoS if enabled
-	let redosResult;
-	if (config.regexRedosDetectionEnabled) {
-		redosResult = detectReDoS(pattern, flags);
-		if (redosResult.detected && redosResult.severity === 'high') {
-			const proceed = await vscode.window.showWarningMessage(
-				`ReDoS vulnerability detected: ${redosResult.reason}. Continue?`,
-				{ modal: true },
-				'Proceed',
-				'Cancel',
+	await runPatternTest(
+		{ pattern, flags, dialect },
+		config,
+		async (test) => async (token) => {
+			// Runs in a worker so a runaway pattern cannot freeze the editor
+			const testResult = await runRegexTest(
+				test.translation.pattern,
+				test.translation.flags,
+				text,
+				config.regexMaxMatchLimit,
+				config.performanceMaxDuration,
+				ranges,
+				token,
 			);
 
-			if (proceed !== 'Proceed') {
-				return;
-			}
-		}
-	} else {
-		redosResult = {
-			detected: false,
-			severity: 'low' as const,
-			reason: '',
-		};
-	}
-
-	const testResult = testRegexWithPerformance(
-		pattern,
-		flags,
-		text,
-		config.regexMaxMatchLimit,
-		startTime,
+			return {
+				content: matchesReport(test, testResult, ranges),
+				opened: () => {
+					deps.telemetry.event('test-completed', {
+						success: testResult.success,
+						matchCount: testResult.matches.length,
+						redosDetected: test.redos.detected,
+						timedOut: isTimedOut(testResult),
+						cancelled: isCancelled(testResult),
+						truncated: testResult.truncated === true,
+					});
+
+					// Not awaited, so the progress notification closes with the
+					// report open
+					if (testResult.truncated) {
+						void offerHigherLimit(
+							testResult,
+							config.regexMaxMatchLimit,
+							deps.notifier,
+							(limit) =>
+								testSinglePattern(
+									pattern,
+									flags,
+									dialect,
+									text,
+									{ ...config, regexMaxMatchLimit: limit },
+									deps,
+									ranges,
+								),
+						);
+					}
+				},
+			};
+		},
 	);
+}
+
+function matchesReport(
+	test: PreparedTest,
+	testResult: RegexTestResult,
+	ranges: readonly TextRange[] | undefined,
+): string {
+	const timedOut = isTimedOut(testResult);
+	const cancelled = isCancelled(testResult);
 
 	let performanceScore;
 	if (testResult.performance) {
 		performanceScore = calculatePerformanceScore(
 			testResult.performance,
-			text.length,
+			testResult.performance.inputSize,
 		);
 	}
 
@@ -235,12 +197,26 @@ async function testSinglePattern(
 	const reportLines: string[] = [];
 	reportLines.push('# Regex Test Results');
 	reportLines.push('');
-	reportLines.push(`**Pattern:** \`/${pattern}/${flags}\``);
+	reportLines.push(
+		`**Pattern:** ${inlineCode(`/${test.pattern}/${test.flags}`)}`,
+	);
+	if (ranges) {
+		reportLines.push(`**Input:** ${ranges.length} selection(s)`);
+	}
 	reportLines.push('');
 
-	if (testResult.success) {
-		reportLines.push(`**Status:** ✅ Success`);
-		reportLines.push(`**Matches Found:** ${testResult.matches.length}`);
+	if (test.dialect !== 'javascript') {
+		appendDialectSection(reportLines, test.dialectCheck, test.translation);
+	}
+
+	if (testResult.success || timedOut || cancelled) {
+		reportLines.push(`**Status:** ${testStatus(testResult)}`);
+		reportLines.push(`**Matches Found:** ${matchCount(testResult)}`);
+		if (testResult.truncated) {
+			reportLines.push(
+				`**⚠️ Match Limit:** only the first ${testResult.matches.length} matches are listed; rerun with a higher limit (up to ${MAX_RERUN_LIMIT}) from the notification to see the rest`,
+			);
+		}
 		reportLines.push('');
 
 		if (testResult.matches.length > 0) {
@@ -252,13 +228,18 @@ async function testSinglePattern(
 				const match = testResult.matches[i];
 				if (match) {
 					reportLines.push(
-						`${i + 1}. \`${match.match}\` at position ${match.index}`,
+						`${i + 1}. ${inlineCode(match.match)} at position ${match.index}`,
 					);
 					if (match.line !== undefined) {
 						reportLines.push(
 							`   Line ${match.line}, Column ${match.column || 0}`,
 						);
 					}
+					for (const group of match.groups ?? []) {
+						reportLines.push(
+							`   - ${groupLabel(group)}: ${inlineCode(group.value)} at Line ${group.line}, Column ${group.column || 0} (offsets ${group.start}-${group.end})`,
+						);
+					}
 				}
 			}
 
@@ -268,6 +249,14 @@ async function testSinglePattern(
 				);
 			}
 		}
+
+		if (timedOut || cancelled) {
+			reportLines.push('');
+			reportLines.push(timedOut ? '## ⏱️ Timeout' : '## 🛑 Cancelled');
+			for (const error of testResult.errors) {
+				reportLines.push(`- ${error.message}`);
+			}
+		}
 	} else {
 		reportLines.push(`**Status:** ❌ Failed`);
 		if (testResult.errors.length > 0) {
@@ -279,11 +268,11 @@ async function testSinglePattern(
 		}
 	}
 
-	if (redosResult.detected) {
+	if (test.redos.detected) {
 		reportLines.push('');
 		reportLines.push(`## ⚠️ ReDoS Detection`);
-		reportLines.push(`**Severity:** ${redosResult.severity}`);
-		reportLines.push(`**Reason:** ${redosResult.reason}`);
+		reportLines.push(`**Severity:** ${test.redos.severity}`);
+		reportLines.push(`**Reason:** ${test.redos.reason}`);
 	}
 
 	if (performanceScore) {
@@ -314,30 +303,60 @@ async function testSinglePattern(
 		reportLines.push(`**Matches:** ${testResult.performance.itemCount}`);
 	}
 
-	const report = reportLines.join('\n');
+	return reportLines.join('\n');
+}
 
-	// Copy to clipboard if enabled
-	if (config.copyToClipboardEnabled) {
-		await vscode.env.clipboard.writeText(report);
+/**
+ * Offer to test again with a limit high enough for every match
+ * The new limit is the exact total when it was counted, or ten times the
+ * old one when counting ran out of time.
+ */
+async function offerHigherLimit(
+	testResult: RegexTestResult,
+	limit: number,
+	notifier: Notifier,
+	rerun: (limit: number) => Promise<void>,
+): Promise<void> {
+	const higherLimit = Math.min(
+		testResult.totalMatches ?? limit * 10,
+		MAX_RERUN_LIMIT,
+	);
+	if (higherLimit <= limit) {
+		return;
 	}
 
-	// Open result document
-	const doc = await vscode.workspace.openTextDocument({
-		content: report,
-		language: 'markdown',
-	});
-
-	const viewColumn = config.openResultsSideBySide
-		? vscode.ViewColumn.Beside
-		: vscode.ViewColumn.Active;
-
-	await vscode.window.showTextDocument(doc, viewColumn);
+	const rerunLabel = localize(
+		'runtime.test.truncated.rerun',
+		'Rerun with Limit {0}',
+		higherLimit,
+	);
+	const choice = await vscode.window.showWarningMessage(
+		testResult.totalMatches === undefined
+			? localize(
+					'runtime.test.truncated.uncounted',
+					'Matching stopped at the limit of {0} matches.',
+					testResult.matches.length,
+				)
+			: localize(
+					'runtime.test.truncated',
+					'Only {0} of {1} matches are listed because of the match limit.',
+					testResult.matches.length,
+					testResult.totalMatches,
+				),
+		rerunLabel,
+	);
+	if (choice !== rerunLabel) {
+		return;
+	}
 
-	deps.telemetry.event('test-completed', {
-		success: testResult.success,
-		matchCount: testResult.matches.length,
-		redosDetected: redosResult.detected,
-	});
+	try {
+		await rerun(higherLimit);
+	} catch (error) {
+		const errorMessage = error instanceof Error ? error.message : String(error);
+		notifier.showError(
+			localize('runtime.test.error', 'Testing failed: {0}', errorMessage),
+		);
+	}
 }
 
 async function testAllPatterns(
@@ -350,35 +369,55 @@ async function testAllPatterns(
 		statusBar: StatusBar;
 	},
 	progress: vscode.Progress<{ message?: string; increment?: number }>,
+	ranges?: readonly TextRange[],
+	token?: vscode.CancellationToken,
 ): Promise<void> {
 	const reportLines: string[] = [];
 	reportLines.push('# Regex Test Results - All Patterns');
 	reportLines.push('');
+	if (ranges) {
+		reportLines.push(`**Input:** ${ranges.length} selection(s)`);
+		reportLines.push('');
+	}
 
-	for (let i = 0; i < patterns.length; i++) {
-		const p = patterns[i];
+	// Repeated patterns give the same result, so each is tested once
+	const groups = groupPatterns(patterns);
+	let tested = 0;
+	for (let i = 0; i < groups.length; i++) {
+		const p = groups[i];
 		if (!p) continue;
+		if (token?.isCancellationRequested) {
+			break;
+		}
 
 		progress.report({
-			message: `Testing pattern ${i + 1}/${patterns.length}: /${p.pattern}/${p.flags}`,
-			increment: (100 / patterns.length) * i,
+			message: `Testing pattern ${i + 1}/${groups.length}: /${p.pattern}/${p.flags}`,
+			increment: (100 / groups.length) * i,
 		});
 
-		const startTime = performance.now();
-		const testResult = testRegexWithPerformance(
-			p.pattern,
-			p.flags,
+		const translation = toJavaScriptPattern(p.pattern, p.flags, p.dialect);
+		const testResult = await runRegexTest(
+			translation.pattern,
+			translation.flags,
 			text,
 			config.regexMaxMatchLimit,
-			startTime,
+			config.performanceMaxDuration,
+			ranges,
+			token,
 		);
+		tested++;
 
-		reportLines.push(`## Pattern ${i + 1}: \`/${p.pattern}/${p.flags}\``);
-		reportLines.push(`**Line:** ${p.line}`);
 		reportLines.push(
-			`**Status:** ${testResult.success ? '✅ Success' : '❌ Failed'}`,
+			`## Pattern ${i + 1}: ${inlineCode(`/${p.pattern}/${p.flags}`)}`,
+		);
+		reportLines.push(
+			`**${p.occurrences.length > 1 ? 'Lines' : 'Line'}:** ${p.occurrences.map((o) => o.line).join(', ')}`,
 		);
-		reportLines.push(`**Matches:** ${testResult.matches.length}`);
+		if (p.dialect !== 'javascript') {
+			reportLines.push(`**Dialect:** ${p.dialect}`);
+		}
+		reportLines.push(`**Status:** ${testStatus(testResult)}`);
+		reportLines.push(`**Matches:** ${matchCount(testResult)}`);
 		if (testResult.performance) {
 			reportLines.push(
 				`**Duration:** ${testResult.performance.duration.toFixed(2)}ms`,
@@ -387,21 +426,55 @@ async function testAllPatterns(
 		reportLines.push('');
 	}
 
-	const report = reportLines.join('\n');
-
-	// Open result document
-	const doc = await vscode.workspace.openTextDocument({
-		content: report,
-		language: 'markdown',
-	});
-
-	const viewColumn = config.openResultsSideBySide
-		? vscode.ViewColumn.Beside
-		: vscode.ViewColumn.Active;
+	// Flag an incomplete report before its first pattern
+	const cancelled = token?.isCancellationRequested === true;
+	if (cancelled) {
+		reportLines.splice(
+			2,
+			0,
+			`**🛑 Cancelled:** tested ${tested} of ${groups.length} patterns; the results below are incomplete`,
+			'',
+		);
+	}
 
-	await vscode.window.showTextDocument(doc, viewColumn);
+	await openReport(reportLines.join('\n'), config);
 
 	deps.telemetry.event('test-all-completed', {
 		patternCount: patterns.length,
+		cancelled,
 	});
 }
+
+/**
+ * Describe how a non-JavaScript pattern was checked and translated
+ */
+function appendDialectSection(
+	reportLines: string[],
+	dialectCheck: ReturnType<typeof checkDialectSyntax>,
+	translation: ReturnType<typeof toJavaScriptPattern>,
+): void {
+	reportLines.push(`## Dialect: ${dialectCheck.dialect}`);
+	reportLines.push(
+		`**${dialectCheck.dialect} Syntax:** ${dialectCheck.valid ? '✅ Valid' : '❌ Invalid'}`,
+	);
+	for (const diagnostic of dialectCheck.diagnostics) {
+		const icon = diagnostic.severity === 'error' ? '❌' : '⚠️';
+		reportLines.push(`- ${icon} ${diagnostic.message}`);
+	}
+	if (dialectCheck.suggestedEngine) {
+		reportLines.push(
+			`- 💡 Consider \`${dialectCheck.suggestedEngine}\` for this pattern`,
+		);
+	}
+	if (dialectCheck.linearTime) {
+		reportLines.push('- ✅ Linear-time engine: not vulnerable to ReDoS');
+	}
+	reportLines.push('');
+	reportLines.push(
+		`**Tested as JavaScript:** ${inlineCode(`/${translation.pattern}/${translation.flags}`)}`,
+	);
+	for (const note of translation.notes) {
+		reportLines.push(`- ${note}`);
+	}
+	reportLines.push('');
+}
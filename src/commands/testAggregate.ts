@@ -0,0 +1,296 @@
+import * as vscode from 'vscode';
+import * as nls from 'vscode-nls';
+import type { getConfiguration } from '../config/config';
+import {
+	aggregateMatches,
+	type ValueFrequency,
+} from '../extraction/regex/aggregate';
+import {
+	codeCell,
+	formatFrequencies,
+	inlineCode,
+} from '../extraction/regex/outputFormats';
+import {
+	isCancelled,
+	isTimedOut,
+	runRegexTest,
+} from '../extraction/regex/regexRunner';
+import {
+	captureGroupNames,
+	type TextRange,
+} from '../extraction/regex/regexTest';
+import type { Telemetry } from '../telemetry/telemetry';
+import type { RegexDialect, RegexTestResult } from '../types';
+import type { Notifier } from '../ui/notifier';
+import type { StatusBar } from '../ui/statusBar';
+import {
+	groupLabel,
+	type PreparedTest,
+	pickTestSubject,
+	runPatternTest,
+	showTestError,
+	testStatus,
+} from './patternTest';
+
+const localize = nls.config({ messageFormat: nls.MessageFormat.file })();
+
+interface AggregateTarget {
+	readonly label: string;
+	readonly group?: number; // Counted instead of the whole match
+}
+
+interface AggregateChoice extends vscode.QuickPickItem {
+	readonly target: AggregateTarget;
+}
+
+/**
+ * Register the match frequency command
+ * Like Test Regex, but counts how often each distinct match of the picked
+ * pattern occurs instead of listing every match.
+ */
+export function registerTestAggregateCommand(
+	context: vscode.ExtensionContext,
+	deps: Readonly<{
+		telemetry: Telemetry;
+		notifier: Notifier;
+		statusBar: StatusBar;
+	}>,
+): void {
+	const disposable = vscode.commands.registerCommand(
+		'regex-le.testAggregate',
+		async (): Promise<void> => {
+			deps.telemetry.event('command-test-aggregate');
+
+			// Frequencies are for one pattern at a time
+			const subject = await pickTestSubject(deps, { allowAll: false });
+			if (!subject || subject.selected === 'all') {
+				return;
+			}
+
+			const { pattern, flags, dialect } = subject.selected;
+			try {
+				await aggregateSinglePattern(
+					pattern,
+					flags,
+					dialect,
+					subject.text,
+					subject.config,
+					deps,
+					subject.ranges,
+				);
+			} catch (error) {
+				showTestError(error, deps);
+			}
+		},
+	);
+
+	context.subscriptions.push(disposable);
+}
+
+/**
+ * Count how often each distinct match of a pattern occurs and open the
+ * frequency report
+ * A pattern with capture groups can count one group instead of the whole
+ * match, and every distinct value can be exported as CSV afterwards.
+ */
+export async function aggregateSinglePattern(
+	pattern: string,
+	flags: string,
+	dialect: RegexDialect,
+	text: string,
+	config: ReturnType<typeof getConfiguration>,
+	deps: {
+		telemetry: Telemetry;
+		notifier: Notifier;
+		statusBar: StatusBar;
+	},
+	ranges?: readonly TextRange[],
+): Promise<void> {
+	await runPatternTest({ pattern, flags, dialect }, config, async (test) => {
+		// Every occurrence counts, so matching is always global
+		const { translation } = test;
+		const jsFlags = translation.flags.includes('g')
+			? translation.flags
+			: `${translation.flags}g`;
+		const target = await pickAggregateTarget(
+			captureGroupNames(translation.pattern, jsFlags),
+		);
+		if (!target) {
+			return undefined;
+		}
+
+		return async (token) => {
+			const testResult = await runRegexTest(
+				translation.pattern,
+				jsFlags,
+				text,
+				config.regexMaxMatchLimit,
+				config.performanceMaxDuration,
+				ranges,
+				token,
+			);
+			const stopped = isTimedOut(testResult) || isCancelled(testResult);
+			const aggregate = aggregateMatches(testResult.matches, target.group);
+
+			return {
+				content: frequencyReport(test, target, testResult, aggregate, ranges),
+				opened: (viewColumn) => {
+					deps.telemetry.event('test-aggregate-completed', {
+						success: testResult.success,
+						matchCount: testResult.matches.length,
+						distinctCount: aggregate.frequencies.length,
+						grouped: target.group !== undefined,
+						stopped,
+					});
+
+					// Not awaited, so the progress notification closes with the
+					// report open
+					if (aggregate.frequencies.length > 0) {
+						void offerFrequencyExport(aggregate.frequencies, viewColumn);
+					}
+				},
+			};
+		};
+	});
+}
+
+function frequencyReport(
+	test: PreparedTest,
+	target: AggregateTarget,
+	testResult: RegexTestResult,
+	aggregate: ReturnType<typeof aggregateMatches>,
+	ranges: readonly TextRange[] | undefined,
+): string {
+	const stopped = isTimedOut(testResult) || isCancelled(testResult);
+
+	const reportLines: string[] = [];
+	reportLines.push('# Regex Match Frequency');
+	reportLines.push('');
+	reportLines.push(
+		`**Pattern:** ${inlineCode(`/${test.pattern}/${test.flags}`)}`,
+	);
+	reportLines.push(`**Counted:** ${target.label}`);
+	if (ranges) {
+		reportLines.push(`**Input:** ${ranges.length} selection(s)`);
+	}
+	reportLines.push('');
+
+	if (testResult.success || stopped) {
+		reportLines.push(`**Status:** ${testStatus(testResult)}`);
+		reportLines.push(`**Values Counted:** ${aggregate.total}`);
+		reportLines.push(`**Distinct Values:** ${aggregate.frequencies.length}`);
+		if (aggregate.skipped > 0) {
+			reportLines.push(
+				`**Skipped:** ${aggregate.skipped} match(es) where the group did not participate`,
+			);
+		}
+		if (testResult.truncated) {
+			const of =
+				testResult.totalMatches === undefined
+					? ''
+					: ` of ${testResult.totalMatches}`;
+			reportLines.push(
+				`**⚠️ Match Limit:** only the first ${testResult.matches.length}${of} matches were counted`,
+			);
+		}
+		reportLines.push('');
+
+		if (aggregate.frequencies.length > 0) {
+			reportLines.push('## Frequencies');
+			reportLines.push('');
+			reportLines.push('| Value | Count | Share | First Line | Last Line |');
+			reportLines.push('| --- | ---: | ---: | ---: | ---: |');
+
+			const maxValuesToShow = Math.min(aggregate.frequencies.length, 100);
+			for (const frequency of aggregate.frequencies.slice(0, maxValuesToShow)) {
+				const value =
+					frequency.value === '' ? '*(empty)*' : codeCell(frequency.value);
+				reportLines.push(
+					`| ${value} | ${frequency.count} | ${frequency.percentage.toFixed(1)}% | ${frequency.firstLine} | ${frequency.lastLine} |`,
+				);
+			}
+
+			if (aggregate.frequencies.length > maxValuesToShow) {
+				reportLines.push(
+					`\n... and ${aggregate.frequencies.length - maxValuesToShow} more values (export to CSV for the full list)`,
+				);
+			}
+		}
+
+		if (stopped) {
+			reportLines.push('');
+			reportLines.push(
+				isTimedOut(testResult) ? '## ⏱️ Timeout' : '## 🛑 Cancelled',
+			);
+			for (const error of testResult.errors) {
+				reportLines.push(`- ${error.message}`);
+			}
+		}
+	} else {
+		reportLines.push(`**Status:** ❌ Failed`);
+		if (testResult.errors.length > 0) {
+			reportLines.push('');
+			reportLines.push('## Errors');
+			for (const error of testResult.errors) {
+				reportLines.push(`- ${error.message}`);
+			}
+		}
+	}
+
+	return reportLines.join('\n');
+}
+
+/**
+ * Choose between counting whole matches and one capture group
+ * Patterns without groups count whole matches without asking.
+ */
+async function pickAggregateTarget(
+	groupNames: readonly (string | undefined)[],
+): Promise<AggregateTarget | undefined> {
+	const wholeMatch: AggregateTarget = {
+		label: localize('runtime.test.aggregate.whole-match', 'Whole match'),
+	};
+	if (groupNames.length === 0) {
+		return wholeMatch;
+	}
+
+	const choices: AggregateChoice[] = [
+		{ label: wholeMatch.label, target: wholeMatch },
+		...groupNames.map((name, index): AggregateChoice => {
+			const label = groupLabel({ index, name });
+			return { label, target: { label, group: index } };
+		}),
+	];
+	const selected = await vscode.window.showQuickPick(choices, {
+		placeHolder: localize(
+			'runtime.test.aggregate.select-target',
+			'Count whole matches or the values of one capture group',
+		),
+	});
+	return selected?.target;
+}
+
+async function offerFrequencyExport(
+	frequencies: readonly ValueFrequency[],
+	viewColumn: vscode.ViewColumn,
+): Promise<void> {
+	const exportCsv = localize('runtime.test.aggregate.export', 'Export CSV');
+	const choice = await vscode.window.showInformationMessage(
+		localize(
+			'runtime.test.aggregate.complete',
+			'Counted {0} distinct values.',
+			frequencies.length,
+		),
+		exportCsv,
+	);
+	if (choice !== exportCsv) {
+		return;
+	}
+
+	const formatted = formatFrequencies(frequencies);
+	const doc = await vscode.workspace.openTextDocument({
+		content: formatted.content,
+		language: formatted.language,
+	});
+	await vscode.window.showTextDocument(doc, viewColumn);
+}
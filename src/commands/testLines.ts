@@ -0,0 +1,295 @@
+import * as vscode from 'vscode';
+import * as nls from 'vscode-nls';
+import type { getConfiguration } from '../config/config';
+import {
+	formatGrepOutput,
+	type GrepOptions,
+	type GrepResult,
+	grepLines,
+	lineRanges,
+	linesWithin,
+} from '../extraction/regex/grep';
+import { inlineCode } from '../extraction/regex/outputFormats';
+import {
+	isCancelled,
+	isTimedOut,
+	runRegexTest,
+} from '../extraction/regex/regexRunner';
+import type { TextRange } from '../extraction/regex/regexTest';
+import type { Telemetry } from '../telemetry/telemetry';
+import type { RegexDialect, RegexTestResult } from '../types';
+import type { Notifier } from '../ui/notifier';
+import type { StatusBar } from '../ui/statusBar';
+import {
+	type PreparedTest,
+	pickTestSubject,
+	runPatternTest,
+	showTestError,
+	testStatus,
+} from './patternTest';
+
+const localize = nls.config({ messageFormat: nls.MessageFormat.file })();
+
+interface GrepModeChoice extends vscode.QuickPickItem {
+	readonly invert: boolean;
+}
+
+/**
+ * Register the test lines command
+ * Like Test Regex, but matches the picked pattern against each line on its
+ * own and lists the lines that match, like grep.
+ */
+export function registerTestLinesCommand(
+	context: vscode.ExtensionContext,
+	deps: Readonly<{
+		telemetry: Telemetry;
+		notifier: Notifier;
+		statusBar: StatusBar;
+	}>,
+): void {
+	const disposable = vscode.commands.registerCommand(
+		'regex-le.testLines',
+		async (): Promise<void> => {
+			deps.telemetry.event('command-test-lines');
+
+			// Line results are for one pattern at a time
+			const subject = await pickTestSubject(deps, { allowAll: false });
+			if (!subject || subject.selected === 'all') {
+				return;
+			}
+
+			const { pattern, flags, dialect } = subject.selected;
+			try {
+				await grepSinglePattern(
+					pattern,
+					flags,
+					dialect,
+					subject.text,
+					subject.config,
+					deps,
+					subject.ranges,
+				);
+			} catch (error) {
+				showTestError(error, deps);
+			}
+		},
+	);
+
+	context.subscriptions.push(disposable);
+}
+
+/**
+ * Match a pattern against each line on its own and open a grep-style report
+ * of the matching (or, inverted, the non-matching) lines with context
+ * Lines run as ranges through the worker, so the time budget still applies;
+ * with ranges, only the lines they overlap are searched.
+ */
+export async function grepSinglePattern(
+	pattern: string,
+	flags: string,
+	dialect: RegexDialect,
+	text: string,
+	config: ReturnType<typeof getConfiguration>,
+	deps: {
+		telemetry: Telemetry;
+		notifier: Notifier;
+		statusBar: StatusBar;
+	},
+	ranges?: readonly TextRange[],
+): Promise<void> {
+	await runPatternTest({ pattern, flags, dialect }, config, async (test) => {
+		const options = await pickGrepOptions(config.regexMaxMatchLimit);
+		if (!options) {
+			return undefined;
+		}
+
+		return async (token) => {
+			// One match is enough to select a line
+			const lineFlags = test.translation.flags.replace('g', '');
+			const lines = lineRanges(text);
+			const searchedNumbers = ranges
+				? linesWithin(lines, ranges)
+				: lines.map((_line, index) => index + 1);
+			const searchedRanges = searchedNumbers.flatMap((line) => {
+				const range = lines[line - 1];
+				return range ? [range] : [];
+			});
+
+			const testResult = await runRegexTest(
+				test.translation.pattern,
+				lineFlags,
+				text,
+				Math.max(searchedRanges.length, 1),
+				config.performanceMaxDuration,
+				searchedRanges,
+				token,
+			);
+			const stopped = isTimedOut(testResult) || isCancelled(testResult);
+			const matched = new Set(
+				testResult.matches.map((match) => match.line ?? 1),
+			);
+
+			// A stopped test only got as far as its last match
+			const lastMatched = testResult.matches.reduce(
+				(last, match) => Math.max(last, match.line ?? 1),
+				0,
+			);
+			const searched = new Set(
+				stopped
+					? searchedNumbers.filter((line) => line <= lastMatched)
+					: searchedNumbers,
+			);
+			const result = grepLines(
+				lines.map((line) => text.slice(line.start, line.end)),
+				matched,
+				options,
+				searched,
+			);
+
+			return {
+				content: linesReport(
+					test,
+					options,
+					testResult,
+					result,
+					searched.size,
+					ranges,
+				),
+				opened: () => {
+					deps.telemetry.event('test-lines-completed', {
+						success: testResult.success,
+						linesSearched: searched.size,
+						linesSelected: result.selectedCount,
+						invert: options.invert,
+						context: options.context,
+						stopped,
+					});
+				},
+			};
+		};
+	});
+}
+
+function linesReport(
+	test: PreparedTest,
+	options: GrepOptions,
+	testResult: RegexTestResult,
+	result: GrepResult,
+	searchedCount: number,
+	ranges: readonly TextRange[] | undefined,
+): string {
+	const stopped = isTimedOut(testResult) || isCancelled(testResult);
+
+	const reportLines: string[] = [];
+	reportLines.push('# Regex Line Results');
+	reportLines.push('');
+	reportLines.push(
+		`**Pattern:** ${inlineCode(`/${test.pattern}/${test.flags}`)}`,
+	);
+	reportLines.push(
+		`**Mode:** ${options.invert ? 'Non-matching lines (inverted)' : 'Matching lines'}`,
+	);
+	reportLines.push(`**Context:** ${options.context} line(s)`);
+	if (ranges) {
+		reportLines.push(`**Input:** ${ranges.length} selection(s)`);
+	}
+	reportLines.push('');
+
+	if (testResult.success || stopped) {
+		reportLines.push(`**Status:** ${testStatus(testResult)}`);
+		reportLines.push(`**Lines Searched:** ${searchedCount}`);
+		reportLines.push(`**Lines Selected:** ${result.selectedCount}`);
+		if (result.truncated) {
+			reportLines.push(
+				`**⚠️ Line Limit:** only the first ${options.maxLines} selected lines are shown`,
+			);
+		}
+		reportLines.push('');
+
+		if (result.blocks.length > 0) {
+			const output = formatGrepOutput(result);
+			const longestRun = Math.max(
+				2,
+				...(output.match(/`+/g) ?? []).map((run) => run.length),
+			);
+			const fence = '`'.repeat(longestRun + 1);
+			reportLines.push('## Lines');
+			reportLines.push('');
+			reportLines.push(fence, output, fence);
+		}
+
+		if (stopped) {
+			reportLines.push('');
+			reportLines.push(
+				isTimedOut(testResult) ? '## ⏱️ Timeout' : '## 🛑 Cancelled',
+			);
+			for (const error of testResult.errors) {
+				reportLines.push(`- ${error.message}`);
+			}
+		}
+	} else {
+		reportLines.push(`**Status:** ❌ Failed`);
+		if (testResult.errors.length > 0) {
+			reportLines.push('');
+			reportLines.push('## Errors');
+			for (const error of testResult.errors) {
+				reportLines.push(`- ${error.message}`);
+			}
+		}
+	}
+
+	return reportLines.join('\n');
+}
+
+/**
+ * Ask for matching or non-matching lines and how much context to show
+ */
+async function pickGrepOptions(
+	maxLines: number,
+): Promise<GrepOptions | undefined> {
+	const choices: GrepModeChoice[] = [
+		{
+			label: localize('runtime.test.lines.matching', 'Matching Lines'),
+			description: 'grep',
+			invert: false,
+		},
+		{
+			label: localize('runtime.test.lines.inverted', 'Non-Matching Lines'),
+			description: 'grep -v',
+			invert: true,
+		},
+	];
+	const mode = await vscode.window.showQuickPick(choices, {
+		placeHolder: localize(
+			'runtime.test.lines.select-mode',
+			'Show the lines that match or the lines that do not',
+		),
+	});
+	if (!mode) {
+		return undefined;
+	}
+
+	const contextInput = await vscode.window.showInputBox({
+		prompt: localize(
+			'runtime.test.lines.context.prompt',
+			'Lines of context to show before and after each line',
+		),
+		value: '0',
+		validateInput: (value) =>
+			/^\d+$/.test(value.trim())
+				? null
+				: localize(
+						'runtime.test.lines.context.invalid',
+						'Enter a whole number of lines',
+					),
+	});
+	if (contextInput === undefined) {
+		return undefined;
+	}
+
+	return Object.freeze({
+		invert: mode.invert,
+		context: Number(contextInput.trim()),
+		maxLines,
+	});
+}
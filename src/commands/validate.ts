@@ -1,18 +1,34 @@
 import * as vscode from 'vscode';
 import * as nls from 'vscode-nls';
 import { getConfiguration } from '../config/config';
-import { extractRegexPatterns } from '../extraction/regex/extractPatterns';
+import {
+	checkDialectSyntax,
+	toJavaScriptPattern,
+} from '../extraction/regex/dialects';
+import {
+	extractRegexPatterns,
+	formatLocation,
+	groupPatterns,
+	patternsWithin,
+} from '../extraction/regex/extractPatterns';
+import { inlineCode } from '../extraction/regex/outputFormats';
 import { estimatePatternComplexity } from '../extraction/regex/performance';
 import { detectReDoS } from '../extraction/regex/redos';
 import type { Telemetry } from '../telemetry/telemetry';
 import type { Notifier } from '../ui/notifier';
 import type { StatusBar } from '../ui/statusBar';
+import {
+	nonEmptySelections,
+	pickSelectionScope,
+	toSourceLocation,
+} from '../utils/selection';
 
 const localize = nls.config({ messageFormat: nls.MessageFormat.file })();
 
 /**
  * Register the regex validate command
- * Validates all regex patterns found in the active editor
+ * Validates all regex patterns found in the active editor, or only those
+ * inside the selections
  */
 export function registerValidateCommand(
 	context: vscode.ExtensionContext,
@@ -41,15 +57,36 @@ export function registerValidateCommand(
 			const document = editor.document;
 			const text = document.getText();
 
+			const selections = nonEmptySelections(editor);
+			const scope =
+				selections.length > 0
+					? await pickSelectionScope(selections.length, { allowInput: false })
+					: 'document';
+			if (!scope) {
+				return;
+			}
+
 			// Extract regex patterns from the file
-			const extractedPatterns = extractRegexPatterns(text);
+			const documentPatterns = extractRegexPatterns(
+				text,
+				document.languageId,
+			);
+			const extractedPatterns =
+				scope === 'patterns'
+					? patternsWithin(documentPatterns, selections.map(toSourceLocation))
+					: documentPatterns;
 
 			if (extractedPatterns.length === 0) {
 				deps.notifier.showInfo(
-					localize(
-						'runtime.validate.no-patterns',
-						'No regex patterns found in the file. Provide a pattern to validate.',
-					),
+					scope === 'patterns'
+						? localize(
+								'runtime.validate.no-patterns-in-selection',
+								'No regex patterns found in the selection. Provide a pattern to validate.',
+							)
+						: localize(
+								'runtime.validate.no-patterns',
+								'No regex patterns found in the file. Provide a pattern to validate.',
+							),
 				);
 
 				// Fallback: prompt for pattern if none found
@@ -96,10 +133,15 @@ export function registerValidateCommand(
 							'runtime.validate.progress',
 							'Validating regex patterns...',
 						),
-						cancellable: false,
+						cancellable: true,
 					},
-					async (progress) => {
-						await validateAllPatterns(extractedPatterns, deps, progress);
+					async (progress, token) => {
+						await validateAllPatterns(
+							extractedPatterns,
+							deps,
+							progress,
+							token,
+						);
 					},
 				);
 			} catch (error) {
@@ -132,14 +174,9 @@ async function validateSinglePattern(
 	const config = getConfiguration();
 
 	// Validate syntax
-	let isValid = false;
-	let syntaxError: string | undefined;
-	try {
-		new RegExp(pattern, flags);
-		isValid = true;
-	} catch (error) {
-		syntaxError = error instanceof Error ? error.message : String(error);
-	}
+	const syntaxCheck = checkDialectSyntax(pattern, flags, 'javascript');
+	const isValid = syntaxCheck.valid;
+	const syntaxError = syntaxCheck.error;
 
 	// Check for ReDoS
 	let redosResult;
@@ -163,7 +200,7 @@ async function validateSinglePattern(
 	const reportLines: string[] = [];
 	reportLines.push('# Regex Validation Results');
 	reportLines.push('');
-	reportLines.push(`**Pattern:** \`/${pattern}/${flags}\``);
+	reportLines.push(`**Pattern:** ${inlineCode(`/${pattern}/${flags}`)}`);
 	reportLines.push('');
 
 	if (isValid) {
@@ -245,7 +282,11 @@ async function validateSinglePattern(
 	});
 }
 
-async function validateAllPatterns(
+/**
+ * Validate extracted occurrences against their dialects and open a report
+ * Stops between patterns when cancelled and marks the report incomplete.
+ */
+export async function validateAllPatterns(
 	patterns: ReturnType<typeof extractRegexPatterns>,
 	deps: {
 		telemetry: Telemetry;
@@ -253,45 +294,62 @@ async function validateAllPatterns(
 		statusBar: StatusBar;
 	},
 	progress: vscode.Progress<{ message?: string; increment?: number }>,
+	token?: vscode.CancellationToken,
 ): Promise<void> {
 	const config = getConfiguration();
 
 	const reportLines: string[] = [];
 	reportLines.push('# Regex Validation Results - All Patterns');
 	reportLines.push('');
-	reportLines.push(`Found ${patterns.length} pattern(s) to validate\n`);
+	const groups = groupPatterns(patterns);
+	reportLines.push(
+		`Found ${groups.length} unique pattern(s) across ${patterns.length} occurrence(s) to validate\n`,
+	);
 
 	let validCount = 0;
 	let invalidCount = 0;
 	let redosCount = 0;
+	let backtrackingCount = 0;
+	let validated = 0;
 
-	for (let i = 0; i < patterns.length; i++) {
-		const p = patterns[i];
+	for (let i = 0; i < groups.length; i++) {
+		const p = groups[i];
 		if (!p) continue;
 
+		// Yield so a cancel request can arrive between patterns
+		await new Promise((resolve) => setImmediate(resolve));
+		if (token?.isCancellationRequested) {
+			break;
+		}
+		validated++;
+		// Counts are per occurrence so repeated patterns are not undercounted
+		const occurrenceCount = p.occurrences.length;
+
 		progress.report({
-			message: `Validating pattern ${i + 1}/${patterns.length}`,
-			increment: (100 / patterns.length) * i,
+			message: `Validating pattern ${i + 1}/${groups.length}`,
+			increment: (100 / groups.length) * i,
 		});
 
-		// Validate syntax
-		let isValid = false;
-		let syntaxError: string | undefined;
-		try {
-			new RegExp(p.pattern, p.flags);
-			isValid = true;
-			validCount++;
-		} catch (error) {
-			syntaxError = error instanceof Error ? error.message : String(error);
-			invalidCount++;
+		// Validate syntax against the engine the pattern was written for
+		const syntaxCheck = checkDialectSyntax(p.pattern, p.flags, p.dialect);
+		const isValid = syntaxCheck.valid;
+		if (isValid) {
+			validCount += occurrenceCount;
+		} else {
+			invalidCount += occurrenceCount;
+		}
+		if (syntaxCheck.suggestedEngine) {
+			backtrackingCount += occurrenceCount;
 		}
 
-		// Check for ReDoS
+		// Check for ReDoS (linear-time engines cannot backtrack)
 		let redosResult;
-		if (config.regexRedosDetectionEnabled) {
-			redosResult = detectReDoS(p.pattern, p.flags);
+		if (config.regexRedosDetectionEnabled && !syntaxCheck.linearTime) {
+			// Backtracking dialects are checked through their JS equivalent
+			const translation = toJavaScriptPattern(p.pattern, p.flags, p.dialect);
+			redosResult = detectReDoS(translation.pattern, translation.flags);
 			if (redosResult.detected) {
-				redosCount++;
+				redosCount += occurrenceCount;
 			}
 		} else {
 			redosResult = {
@@ -305,30 +363,72 @@ async function validateAllPatterns(
 		const complexity = estimatePatternComplexity(p.pattern);
 
 		// Build report for this pattern
-		reportLines.push(`## Pattern ${i + 1}: \`/${p.pattern}/${p.flags}\``);
-		reportLines.push(`**Line:** ${p.line}`);
+		reportLines.push(
+			`## Pattern ${i + 1}: ${inlineCode(`/${p.pattern}/${p.flags}`)}`,
+		);
+		if (p.dialect !== 'javascript') {
+			reportLines.push(`**Dialect:** ${p.dialect}`);
+		}
 		reportLines.push(`**Status:** ${isValid ? '✅ Valid' : '❌ Invalid'}`);
-		if (syntaxError) {
-			reportLines.push(`**Error:** ${syntaxError}`);
+		if (syntaxCheck.error) {
+			reportLines.push(`**Error:** ${syntaxCheck.error}`);
+		}
+		for (const diagnostic of syntaxCheck.diagnostics) {
+			if (diagnostic.message !== syntaxCheck.error) {
+				const icon = diagnostic.severity === 'error' ? '❌' : '⚠️';
+				reportLines.push(`- ${icon} ${diagnostic.message}`);
+			}
+		}
+		if (syntaxCheck.suggestedEngine) {
+			reportLines.push(
+				`**💡 Suggestion:** Use \`${syntaxCheck.suggestedEngine}\` for look-around, backreferences and other backtracking features`,
+			);
 		}
 		if (redosResult.detected) {
 			reportLines.push(
 				`**⚠️ ReDoS:** ${redosResult.severity} - ${redosResult.reason}`,
 			);
+		} else if (syntaxCheck.linearTime) {
+			reportLines.push('**ReDoS:** Not applicable (linear-time engine)');
 		}
 		reportLines.push(`**Complexity:** ${complexity.score}/100`);
+		reportLines.push(`**Occurrences:** ${occurrenceCount}`);
+		for (const occurrence of p.occurrences) {
+			reportLines.push(
+				occurrence.partial
+					? `- Line ${formatLocation(occurrence)} ⚠️ Partially resolved: non-constant parts were left out`
+					: `- Line ${formatLocation(occurrence)}`,
+			);
+		}
 		reportLines.push('');
 	}
 
+	// Flag an incomplete report before its first pattern
+	const cancelled = token?.isCancellationRequested === true;
+	if (cancelled) {
+		reportLines.splice(
+			2,
+			0,
+			`**🛑 Cancelled:** validated ${validated} of ${groups.length} patterns; the results below are incomplete`,
+			'',
+		);
+	}
+
 	// Summary
 	reportLines.push('---');
 	reportLines.push('## Summary');
 	reportLines.push(`**Total Patterns:** ${patterns.length}`);
+	reportLines.push(`**Unique Patterns:** ${groups.length}`);
 	reportLines.push(`**✅ Valid:** ${validCount}`);
 	reportLines.push(`**❌ Invalid:** ${invalidCount}`);
 	if (config.regexRedosDetectionEnabled) {
 		reportLines.push(`**⚠️ ReDoS Vulnerable:** ${redosCount}`);
 	}
+	if (backtrackingCount > 0) {
+		reportLines.push(
+			`**💡 Need a backtracking engine:** ${backtrackingCount}`,
+		);
+	}
 
 	const report = reportLines.join('\n');
 
@@ -346,9 +446,11 @@ async function validateAllPatterns(
 
 	deps.telemetry.event('validate-all-completed', {
 		totalPatterns: patterns.length,
+		uniquePatterns: groups.length,
 		validCount,
 		invalidCount,
 		redosCount,
+		cancelled,
 	});
 
 	if (config.notificationsLevel === 'all') {
@@ -0,0 +1,475 @@
+import * as vscode from 'vscode';
+import * as nls from 'vscode-nls';
+import { getConfiguration } from '../config/config';
+import {
+	isCancelled,
+	isTimedOut,
+	runRegexTest,
+} from '../extraction/regex/regexRunner';
+import { captureGroupNames } from '../extraction/regex/regexTest';
+import {
+	applyReplacements,
+	planReplacements,
+} from '../extraction/regex/replace';
+import type { ReplacePreview } from '../providers/replacePreview';
+import {
+	createReplaceTree,
+	type ReplaceFile,
+	type ReplaceNode,
+} from '../providers/replaceTree';
+import type { Telemetry } from '../telemetry/telemetry';
+import type { Configuration } from '../types';
+import type { Notifier } from '../ui/notifier';
+import type { StatusBar } from '../ui/statusBar';
+import {
+	findWorkspaceFiles,
+	readWorkspaceText,
+} from '../utils/workspaceFiles';
+import { promptForPattern } from './replace';
+
+const localize = nls.config({ messageFormat: nls.MessageFormat.file })();
+
+const VIEW_ID = 'regex-le.replace';
+const HAS_RESULTS_CONTEXT = 'regex-le.hasReplaceResults';
+
+export interface WorkspaceReplaceSearch {
+	readonly files: readonly ReplaceFile[];
+	readonly filesSearched: number;
+	readonly filesSkipped: number; // Unreadable, too large or binary
+	readonly filesTimedOut: number;
+	readonly filesLimited: number; // Hit the match limit
+	readonly cancelled: boolean;
+}
+
+/**
+ * Register the workspace search and replace command and its preview view
+ * Matches are found with JavaScript semantics in every workspace file,
+ * listed by file with a checkbox each, and the checked ones are applied as
+ * one workspace edit.
+ */
+export function registerWorkspaceReplaceCommand(
+	context: vscode.ExtensionContext,
+	deps: Readonly<{
+		telemetry: Telemetry;
+		notifier: Notifier;
+		statusBar: StatusBar;
+		preview: ReplacePreview;
+	}>,
+): void {
+	const tree = createReplaceTree();
+	const view = vscode.window.createTreeView(VIEW_ID, {
+		treeDataProvider: tree,
+		manageCheckboxStateManually: true,
+	});
+
+	const showFiles = async (files: readonly ReplaceFile[]): Promise<void> => {
+		tree.setFiles(files);
+		await vscode.commands.executeCommand(
+			'setContext',
+			HAS_RESULTS_CONTEXT,
+			files.length > 0,
+		);
+	};
+
+	// Keeps an open diff in step with the checkboxes
+	const updatePreview = (file: ReplaceFile): vscode.Uri => {
+		const checked = tree
+			.checkedFiles()
+			.find((candidate) => candidate.uri.toString() === file.uri.toString());
+		return deps.preview.show(
+			file.uri,
+			applyReplacements(file.text, checked?.replacements ?? []),
+		);
+	};
+
+	const checkboxes = view.onDidChangeCheckboxState((event) => {
+		for (const [node, state] of event.items) {
+			tree.setChecked(node, state === vscode.TreeItemCheckboxState.Checked);
+			updatePreview(node.file);
+		}
+	});
+
+	const search = vscode.commands.registerCommand(
+		'regex-le.workspaceReplace',
+		async (): Promise<void> => {
+			deps.telemetry.event('command-workspace-replace');
+			if (!vscode.workspace.workspaceFolders?.length) {
+				deps.notifier.showWarning(
+					localize(
+						'runtime.scan.no-workspace',
+						'No workspace folder is open. Please open a folder first.',
+					),
+				);
+				return;
+			}
+
+			const target = await promptForPattern();
+			if (!target) {
+				return;
+			}
+			const template = await vscode.window.showInputBox({
+				prompt: localize(
+					'runtime.replace.template.prompt',
+					'Enter the replacement ($1, $<name>, $& and $$ are substituted)',
+				),
+				placeHolder: localize(
+					'runtime.replace.template.placeholder',
+					'e.g., $2-$1',
+				),
+			});
+			if (template === undefined) {
+				return;
+			}
+
+			const config = getConfiguration();
+			const include = await vscode.window.showInputBox({
+				prompt: localize(
+					'runtime.workspace-replace.include.prompt',
+					'Files to search (glob pattern)',
+				),
+				value: config.scanInclude,
+			});
+			if (include === undefined) {
+				return;
+			}
+
+			// Every match is replaced, not just the first
+			const flags = target.flags.includes('g')
+				? target.flags
+				: `${target.flags}g`;
+
+			try {
+				// Report syntax errors once rather than for every file
+				new RegExp(target.pattern, flags);
+
+				const result = await vscode.window.withProgress(
+					{
+						location: vscode.ProgressLocation.Notification,
+						title: localize(
+							'runtime.workspace-replace.progress',
+							'Searching workspace...',
+						),
+						cancellable: true,
+					},
+					(progress, token) =>
+						searchWorkspace(
+							target.pattern,
+							flags,
+							template,
+							include.trim() || config.scanInclude,
+							config,
+							progress,
+							token,
+						),
+				);
+
+				await showFiles(result.files);
+				const matchCount = result.files.reduce(
+					(count, file) => count + file.replacements.length,
+					0,
+				);
+				if (result.files.length > 0) {
+					await vscode.commands.executeCommand(`${VIEW_ID}.focus`);
+				}
+				deps.notifier.showInfo(searchSummary(result, matchCount));
+
+				deps.telemetry.event('workspace-replace-searched', {
+					filesSearched: result.filesSearched,
+					fileCount: result.files.length,
+					matchCount,
+					filesTimedOut: result.filesTimedOut,
+					cancelled: result.cancelled,
+				});
+			} catch (error) {
+				const errorMessage =
+					error instanceof Error ? error.message : String(error);
+				deps.notifier.showError(
+					localize(
+						'runtime.replace.error',
+						'Replace failed: {0}',
+						errorMessage,
+					),
+				);
+				deps.telemetry.event('workspace-replace-failed', {
+					error: errorMessage,
+				});
+			}
+		},
+	);
+
+	const preview = vscode.commands.registerCommand(
+		'regex-le.workspaceReplace.preview',
+		async (node?: ReplaceNode): Promise<void> => {
+			if (!node) {
+				return;
+			}
+			await vscode.commands.executeCommand(
+				'vscode.diff',
+				node.file.uri,
+				updatePreview(node.file),
+				localize(
+					'runtime.replace.diff-title',
+					'{0} ↔ Replace Preview',
+					node.file.file,
+				),
+			);
+		},
+	);
+
+	const apply = vscode.commands.registerCommand(
+		'regex-le.workspaceReplace.apply',
+		async (): Promise<void> => {
+			const files = tree.checkedFiles();
+			const replacementCount = files.reduce(
+				(count, file) => count + file.replacements.length,
+				0,
+			);
+			if (replacementCount === 0) {
+				deps.notifier.showInfo(
+					localize(
+						'runtime.workspace-replace.nothing-selected',
+						'No replacements are selected.',
+					),
+				);
+				return;
+			}
+
+			const confirm = localize('runtime.replace.apply', 'Apply');
+			const choice = await vscode.window.showWarningMessage(
+				localize(
+					'runtime.workspace-replace.confirm',
+					'Replace {0} matches in {1} files?',
+					replacementCount,
+					files.length,
+				),
+				{ modal: true },
+				confirm,
+			);
+			if (choice !== confirm) {
+				return;
+			}
+
+			try {
+				const targets = await Promise.all(
+					files.map(async (file) => ({
+						file,
+						document: await vscode.workspace.openTextDocument(file.uri),
+					})),
+				);
+
+				// Offsets were planned for the text as it was; apply all or nothing
+				const changed = targets.filter(
+					({ file, document }) => document.getText() !== file.text,
+				);
+				if (changed.length > 0) {
+					deps.notifier.showWarning(
+						localize(
+							'runtime.workspace-replace.changed',
+							'{0} file(s) changed since the search, so nothing was replaced. Run Search and Replace in Workspace again.',
+							changed.length,
+						),
+					);
+					return;
+				}
+
+				const edit = new vscode.WorkspaceEdit();
+				for (const { file, document } of targets) {
+					for (const replacement of file.replacements) {
+						edit.replace(
+							file.uri,
+							new vscode.Range(
+								document.positionAt(replacement.start),
+								document.positionAt(replacement.end),
+							),
+							replacement.replacement,
+						);
+					}
+				}
+				const applied = await vscode.workspace.applyEdit(edit);
+				if (applied) {
+					await showFiles([]);
+					deps.statusBar.updateText(
+						localize(
+							'runtime.workspace-replace.complete',
+							'Replaced {0} matches in {1} files',
+							replacementCount,
+							files.length,
+						),
+					);
+				}
+
+				deps.telemetry.event('workspace-replace-applied', {
+					applied,
+					fileCount: files.length,
+					replacementCount,
+				});
+			} catch (error) {
+				const errorMessage =
+					error instanceof Error ? error.message : String(error);
+				deps.notifier.showError(
+					localize(
+						'runtime.replace.error',
+						'Replace failed: {0}',
+						errorMessage,
+					),
+				);
+				deps.telemetry.event('workspace-replace-failed', {
+					error: errorMessage,
+				});
+			}
+		},
+	);
+
+	const clear = vscode.commands.registerCommand(
+		'regex-le.workspaceReplace.clear',
+		async (): Promise<void> => {
+			await showFiles([]);
+		},
+	);
+
+	context.subscriptions.push(
+		tree,
+		view,
+		checkboxes,
+		search,
+		preview,
+		apply,
+		clear,
+	);
+}
+
+/**
+ * Find and plan the replacements of a pattern in every matching workspace
+ * file
+ * Each file runs in its own worker under the time budget; files that run
+ * out of time are counted and left out, and cancelling keeps the files
+ * finished so far.
+ */
+export async function searchWorkspace(
+	pattern: string,
+	flags: string,
+	template: string,
+	include: string,
+	config: Configuration,
+	progress: vscode.Progress<{ message?: string; increment?: number }>,
+	token: vscode.CancellationToken,
+): Promise<WorkspaceReplaceSearch> {
+	const uris = await findWorkspaceFiles(config, include, token);
+	const groupNames = captureGroupNames(pattern, flags);
+	const files: ReplaceFile[] = [];
+	let filesSearched = 0;
+	let filesSkipped = 0;
+	let filesTimedOut = 0;
+	let filesLimited = 0;
+	let cancelled = false;
+
+	for (const uri of uris) {
+		if (token.isCancellationRequested) {
+			cancelled = true;
+			break;
+		}
+		const file = vscode.workspace.asRelativePath(uri);
+		progress.report({ message: file, increment: 100 / uris.length });
+
+		// A NUL character is a good sign of a binary file
+		const text = await readWorkspaceText(uri, config);
+		if (text === undefined || text.includes('\0')) {
+			filesSkipped++;
+			continue;
+		}
+
+		const result = await runRegexTest(
+			pattern,
+			flags,
+			text,
+			config.regexMaxMatchLimit,
+			config.performanceMaxDuration,
+			undefined,
+			token,
+		);
+		if (isCancelled(result)) {
+			cancelled = true;
+			break;
+		}
+		filesSearched++;
+		if (isTimedOut(result)) {
+			filesTimedOut++;
+			continue;
+		}
+		if (!result.success) {
+			throw new Error(result.errors.map((error) => error.message).join('; '));
+		}
+		if (result.matches.length === 0) {
+			continue;
+		}
+		if (result.truncated) {
+			filesLimited++;
+		}
+
+		files.push(
+			Object.freeze({
+				uri,
+				file,
+				text,
+				replacements: planReplacements(
+					text,
+					result.matches,
+					template,
+					groupNames,
+				),
+				limited: result.truncated,
+			}),
+		);
+	}
+
+	return Object.freeze({
+		files: Object.freeze(files),
+		filesSearched,
+		filesSkipped,
+		filesTimedOut,
+		filesLimited,
+		cancelled,
+	});
+}
+
+function searchSummary(
+	result: WorkspaceReplaceSearch,
+	matchCount: number,
+): string {
+	const parts = [
+		localize(
+			'runtime.workspace-replace.summary',
+			'Found {0} matches in {1} of {2} files searched.',
+			matchCount,
+			result.files.length,
+			result.filesSearched,
+		),
+	];
+	if (result.filesTimedOut > 0) {
+		parts.push(
+			localize(
+				'runtime.workspace-replace.summary.timed-out',
+				'{0} file(s) ran out of time and were left out.',
+				result.filesTimedOut,
+			),
+		);
+	}
+	if (result.filesLimited > 0) {
+		parts.push(
+			localize(
+				'runtime.workspace-replace.summary.limited',
+				'{0} file(s) reached the match limit; only their first matches are listed, unchecked.',
+				result.filesLimited,
+			),
+		);
+	}
+	if (result.cancelled) {
+		parts.push(
+			localize(
+				'runtime.workspace-replace.summary.cancelled',
+				'The search was cancelled before every file was searched.',
+			),
+		);
+	}
+	return parts.join(' ');
+}
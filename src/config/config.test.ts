@@ -23,6 +23,11 @@ describe('config', () => {
 				copyToClipboardEnabled: false,
 				notificationsLevel: 'silent',
 				openResultsSideBySide: true,
+				'extract.outputFormat': 'text',
+				'scan.include': '**/*.{js,ts}',
+				'scan.exclude': ['**/node_modules/**'],
+				'scan.respectGitignore': true,
+				'scan.maxFiles': 5000,
 				'safety.enabled': true,
 				'safety.fileSizeWarnBytes': 1000000,
 				'safety.largeOutputLinesThreshold': 50000,
@@ -118,6 +123,11 @@ describe('config', () => {
 			expect(config).toHaveProperty('copyToClipboardEnabled');
 			expect(config).toHaveProperty('notificationsLevel');
 			expect(config).toHaveProperty('openResultsSideBySide');
+			expect(config).toHaveProperty('extractOutputFormat');
+			expect(config).toHaveProperty('scanInclude');
+			expect(config).toHaveProperty('scanExclude');
+			expect(config).toHaveProperty('scanRespectGitignore');
+			expect(config).toHaveProperty('scanMaxFiles');
 			expect(config).toHaveProperty('safetyEnabled');
 			expect(config).toHaveProperty('safetyFileSizeWarnBytes');
 			expect(config).toHaveProperty('safetyLargeOutputLinesThreshold');
@@ -145,6 +155,49 @@ describe('config', () => {
 			expect(config.regexMaxMatchLimit).toBe(500);
 		});
 
+		it('should validate extract output format', () => {
+			for (const format of ['json', 'csv', 'tsv', 'markdown', 'ask']) {
+				mockConfig.get.mockImplementation((key: string) => {
+					if (key === 'extract.outputFormat') return format;
+					return undefined;
+				});
+
+				expect(getConfiguration().extractOutputFormat).toBe(format);
+			}
+
+			mockConfig.get.mockImplementation((key: string) => {
+				if (key === 'extract.outputFormat') return 'xml';
+				return undefined;
+			});
+			expect(getConfiguration().extractOutputFormat).toBe('text');
+		});
+
+		it('should sanitize workspace scan settings', () => {
+			mockConfig.get.mockImplementation((key: string) => {
+				if (key === 'scan.include') return '';
+				if (key === 'scan.exclude') return ['**/vendor/**', 42];
+				if (key === 'scan.maxFiles') return 0;
+				return undefined;
+			});
+
+			const config = getConfiguration();
+			expect(config.scanInclude).toContain('**/*.');
+			expect(config.scanExclude).toEqual(['**/vendor/**']);
+			expect(config.scanMaxFiles).toBe(1);
+		});
+
+		it('should use defaults for numeric settings that are not numbers', () => {
+			mockConfig.get.mockImplementation((key: string) => {
+				if (key === 'scan.maxFiles') return 'many';
+				if (key === 'regex.maxMatchLimit') return 'lots';
+				return undefined;
+			});
+
+			const config = getConfiguration();
+			expect(config.scanMaxFiles).toBe(5000);
+			expect(config.regexMaxMatchLimit).toBe(1000);
+		});
+
 		it('should handle all notification levels', () => {
 			for (const level of ['all', 'important', 'silent'] as const) {
 				mockConfig.get.mockImplementation((key: string) => {
@@ -1,6 +1,21 @@
 import * as vscode from 'vscode';
+import { isExtractOutputFormat } from '../extraction/regex/outputFormats';
 import type { Configuration } from '../types';
 
+/**
+ * Source files the workspace scan reads by default
+ */
+const DEFAULT_SCAN_INCLUDE =
+	'**/*.{js,jsx,mjs,cjs,ts,tsx,mts,cts,rs,py,go,java,kt,kts,cs}';
+
+const DEFAULT_SCAN_EXCLUDE: readonly string[] = Object.freeze([
+	'**/node_modules/**',
+	'**/dist/**',
+	'**/out/**',
+	'**/build/**',
+	'**/*.min.js',
+]);
+
 /**
  * Get extension configuration with validation and defaults
  */
@@ -16,31 +31,55 @@ export function getConfiguration(): Configuration {
 		? notifRaw
 		: 'silent';
 
+	// Validate extract output format
+	const formatRaw: unknown = config.get('extract.outputFormat', 'text');
+	const extractOutputFormat =
+		formatRaw === 'ask' || isExtractOutputFormat(formatRaw)
+			? formatRaw
+			: 'text';
+
 	return Object.freeze({
 		copyToClipboardEnabled: Boolean(
 			config.get('copyToClipboardEnabled', false),
 		),
 		notificationsLevel,
 		openResultsSideBySide: Boolean(config.get('openResultsSideBySide', true)),
+		extractOutputFormat,
+		scanInclude:
+			String(config.get('scan.include', DEFAULT_SCAN_INCLUDE)) ||
+			DEFAULT_SCAN_INCLUDE,
+		scanExclude: Object.freeze(
+			toStringArray(config.get('scan.exclude', DEFAULT_SCAN_EXCLUDE)),
+		),
+		scanRespectGitignore: Boolean(config.get('scan.respectGitignore', true)),
+		scanMaxFiles: numberSetting(config, 'scan.maxFiles', 5000, 1, 100000),
 		safetyEnabled: Boolean(config.get('safety.enabled', true)),
-		safetyFileSizeWarnBytes: Math.max(
+		safetyFileSizeWarnBytes: numberSetting(
+			config,
+			'safety.fileSizeWarnBytes',
+			1000000,
 			1000,
-			Number(config.get('safety.fileSizeWarnBytes', 1000000)),
 		),
-		safetyLargeOutputLinesThreshold: Math.max(
+		safetyLargeOutputLinesThreshold: numberSetting(
+			config,
+			'safety.largeOutputLinesThreshold',
+			50000,
 			100,
-			Number(config.get('safety.largeOutputLinesThreshold', 50000)),
 		),
 		statusBarEnabled: Boolean(config.get('statusBar.enabled', true)),
 		telemetryEnabled: Boolean(config.get('telemetryEnabled', false)),
 		performanceEnabled: Boolean(config.get('performance.enabled', true)),
-		performanceMaxDuration: Math.max(
+		performanceMaxDuration: numberSetting(
+			config,
+			'performance.maxDuration',
+			5000,
 			1000,
-			Number(config.get('performance.maxDuration', 5000)),
 		),
-		performanceMaxMemoryUsage: Math.max(
+		performanceMaxMemoryUsage: numberSetting(
+			config,
+			'performance.maxMemoryUsage',
+			104857600,
 			1048576,
-			Number(config.get('performance.maxMemoryUsage', 104857600)),
 		),
 		regexRealtimePreviewEnabled: Boolean(
 			config.get('regex.realtimePreviewEnabled', true),
@@ -48,13 +87,39 @@ export function getConfiguration(): Configuration {
 		regexRedosDetectionEnabled: Boolean(
 			config.get('regex.redosDetectionEnabled', true),
 		),
-		regexMaxMatchLimit: Math.max(
+		regexMaxMatchLimit: numberSetting(
+			config,
+			'regex.maxMatchLimit',
+			1000,
 			10,
-			Math.min(10000, Number(config.get('regex.maxMatchLimit', 1000))),
+			10000,
 		),
 	});
 }
 
+/**
+ * A numeric setting clamped to [min, max], or its default when the value
+ * is not a finite number
+ */
+function numberSetting(
+	config: vscode.WorkspaceConfiguration,
+	key: string,
+	defaultValue: number,
+	min: number,
+	max: number = Number.POSITIVE_INFINITY,
+): number {
+	const value = Number(config.get(key, defaultValue));
+	return Number.isFinite(value)
+		? Math.max(min, Math.min(max, value))
+		: defaultValue;
+}
+
+function toStringArray(value: unknown): string[] {
+	return Array.isArray(value)
+		? value.filter((item): item is string => typeof item === 'string')
+		: [];
+}
+
 export type NotificationLevel = 'all' | 'important' | 'silent';
 
 function isValidNotificationLevel(v: unknown): v is NotificationLevel {
@@ -16,6 +16,21 @@ export const SETTINGS_SCHEMA = Object.freeze({
 	openResultsSideBySide: { type: 'boolean' as const },
 	telemetryEnabled: { type: 'boolean' as const },
 
+	// Extract settings
+	'extract.outputFormat': {
+		type: 'string' as const,
+		enum: ['text', 'json', 'csv', 'tsv', 'markdown', 'ask'] as const,
+	},
+
+	// Workspace scan settings
+	'scan.include': { type: 'string' as const },
+	'scan.respectGitignore': { type: 'boolean' as const },
+	'scan.maxFiles': {
+		type: 'number' as const,
+		min: 1,
+		max: 100_000,
+	},
+
 	// Safety settings
 	'safety.enabled': { type: 'boolean' as const },
 	'safety.fileSizeWarnBytes': {
@@ -0,0 +1,58 @@
+import { describe, expect, it } from 'vitest';
+import { aggregateMatches } from './aggregate';
+import { testRegexPattern } from './regexTest';
+
+const LOG = [
+	'10:00:00 [INFO] Application started',
+	'10:00:05 [DEBUG] Processing request',
+	'10:00:10 [INFO] User authenticated',
+	'10:01:20 [WARN] Slow query detected',
+	'10:02:00 [INFO] Request completed',
+	'10:02:30 [WARN] Retrying',
+].join('\n');
+
+describe('aggregateMatches', () => {
+	it('should count whole matches, most frequent first', () => {
+		const result = testRegexPattern('\\[\\w+\\]', 'g', LOG);
+		const aggregate = aggregateMatches(result.matches);
+
+		expect(aggregate.total).toBe(6);
+		expect(aggregate.skipped).toBe(0);
+		expect(aggregate.frequencies.map((f) => [f.value, f.count])).toEqual([
+			['[INFO]', 3],
+			['[WARN]', 2],
+			['[DEBUG]', 1],
+		]);
+		expect(aggregate.frequencies[0]?.percentage).toBe(50);
+	});
+
+	it('should count a capture group with the lines it was first and last seen', () => {
+		const result = testRegexPattern('\\[(?<level>\\w+)\\]', 'g', LOG);
+		const aggregate = aggregateMatches(result.matches, 0);
+
+		expect(aggregate.frequencies[1]).toEqual({
+			value: 'WARN',
+			count: 2,
+			percentage: (2 / 6) * 100,
+			firstLine: 4,
+			lastLine: 6,
+		});
+	});
+
+	it('should skip matches where the group did not participate', () => {
+		const result = testRegexPattern('\\[(?:(WARN)|\\w+)\\]', 'g', LOG);
+		const aggregate = aggregateMatches(result.matches, 0);
+
+		expect(aggregate.total).toBe(2);
+		expect(aggregate.skipped).toBe(4);
+		expect(aggregate.frequencies).toHaveLength(1);
+		expect(aggregate.frequencies[0]?.percentage).toBe(100);
+	});
+
+	it('should keep first-seen order for equal counts', () => {
+		const result = testRegexPattern('\\w', 'g', 'cabc');
+		const aggregate = aggregateMatches(result.matches);
+
+		expect(aggregate.frequencies.map((f) => f.value)).toEqual(['c', 'a', 'b']);
+	});
+});
@@ -0,0 +1,81 @@
+/**
+ * Match frequencies
+ * Counts how often each distinct match (or capture group value) occurs, for
+ * questions like "which log levels appear, and how often".
+ */
+
+import type { RegexMatch } from '../../types';
+
+export interface ValueFrequency {
+	readonly value: string;
+	readonly count: number;
+	readonly percentage: number; // Of all counted values, 0-100
+	readonly firstLine: number;
+	readonly lastLine: number;
+}
+
+export interface MatchAggregate {
+	readonly frequencies: readonly ValueFrequency[];
+	readonly total: number; // Values counted
+	readonly skipped: number; // Matches where the group did not participate
+}
+
+interface Sighting {
+	count: number;
+	firstLine: number;
+	lastLine: number;
+}
+
+/**
+ * Count the distinct values of the matches, most frequent first
+ * With groupIndex (0-based, as in RegexGroup.index) the value of that group
+ * is counted instead of the whole match. Values with the same count keep
+ * the order in which they were first seen.
+ */
+export function aggregateMatches(
+	matches: readonly RegexMatch[],
+	groupIndex?: number,
+): MatchAggregate {
+	const counts = new Map<string, Sighting>();
+	let total = 0;
+	let skipped = 0;
+
+	for (const match of matches) {
+		const value =
+			groupIndex === undefined
+				? match.match
+				: match.groups?.find((group) => group.index === groupIndex)?.value;
+		if (value === undefined) {
+			skipped++;
+			continue;
+		}
+
+		total++;
+		const line = match.line ?? 1;
+		const seen = counts.get(value);
+		if (seen) {
+			seen.count++;
+			seen.lastLine = line;
+		} else {
+			counts.set(value, { count: 1, firstLine: line, lastLine: line });
+		}
+	}
+
+	const frequencies = [...counts].map(
+		([value, seen]): ValueFrequency =>
+			Object.freeze({
+				value,
+				count: seen.count,
+				percentage: (seen.count / total) * 100,
+				firstLine: seen.firstLine,
+				lastLine: seen.lastLine,
+			}),
+	);
+	frequencies.sort((a, b) => b.count - a.count);
+
+	return Object.freeze({
+		frequencies: Object.freeze(frequencies),
+		total,
+		skipped,
+	});
+}
@@ -0,0 +1,116 @@
+/**
+ * Extract regex construction sites from C# source
+ * Understands Regex constructors, the static Regex helpers and the
+ * [GeneratedRegex] attribute, with regular, verbatim (@"...") and raw
+ * ("""...""") string literals
+ */
+
+import type { ExtractedRegexPattern } from './extractPatterns';
+import { createLineIndex } from './lineIndex';
+import {
+	findClosingBracket,
+	locationAt,
+	mapFlagNames,
+	maskNonCode,
+	readCallArguments,
+	readLiteralArgument,
+	type SourceSyntax,
+} from './sourceScanner';
+import { readCSharpString } from './stringLiterals';
+
+const CSHARP_SYNTAX: SourceSyntax = Object.freeze({
+	lineComments: Object.freeze(['//']),
+	blockComment: Object.freeze(['/*', '*/'] as const),
+	readString: readCSharpString,
+});
+
+/**
+ * RegexOptions members and their inline flag equivalents
+ */
+const REGEX_OPTIONS: Readonly<Record<string, string>> = Object.freeze({
+	IgnoreCase: 'i',
+	Multiline: 'm',
+	Singleline: 's',
+	IgnorePatternWhitespace: 'x',
+	ExplicitCapture: 'n',
+});
+
+/**
+ * Positions of the pattern and options arguments of each entry point
+ */
+const SIGNATURES: Readonly<
+	Record<string, { readonly pattern: number; readonly options: number }>
+> = Object.freeze({
+	constructor: { pattern: 0, options: 1 },
+	GeneratedRegex: { pattern: 0, options: 1 },
+	IsMatch: { pattern: 1, options: 2 },
+	Match: { pattern: 1, options: 2 },
+	Matches: { pattern: 1, options: 2 },
+	Count: { pattern: 1, options: 2 },
+	EnumerateMatches: { pattern: 1, options: 2 },
+	Split: { pattern: 1, options: 2 },
+	Replace: { pattern: 1, options: 3 },
+});
+
+/**
+ * Named arguments, e.g. options: RegexOptions.IgnoreCase
+ */
+const NAMED_ARGUMENT = /^\s*(\w+)\s*:(?!:)/;
+
+/**
+ * Extract all regex patterns from C# source text
+ */
+export function extractCSharpPatterns(
+	text: string,
+): readonly ExtractedRegexPattern[] {
+	const masked = maskNonCode(text, CSHARP_SYNTAX);
+	const lines = createLineIndex(text);
+	const patterns: ExtractedRegexPattern[] = [];
+
+	// new Regex(...), Regex.IsMatch(input, ...), [GeneratedRegex(...)]
+	const callSite =
+		/\bnew\s+(?:System\.Text\.RegularExpressions\.)?Regex\s*\(|(?<![\w.])(?:System\.Text\.RegularExpressions\.)?Regex\s*\.\s*(IsMatch|Matches|Match|Count|EnumerateMatches|Split|Replace)\s*\(|\b(GeneratedRegex)(?:Attribute)?\s*\(/g;
+	for (const site of masked.matchAll(callSite)) {
+		const siteOffset = site.index ?? 0;
+		const openParen = siteOffset + site[0].length - 1;
+		const closeParen = findClosingBracket(masked, openParen);
+		const signature = SIGNATURES[site[1] ?? site[2] ?? 'constructor'];
+		if (closeParen === -1 || !signature) {
+			continue;
+		}
+
+		const args = readCallArguments(
+			masked,
+			openParen,
+			closeParen,
+			NAMED_ARGUMENT,
+		);
+		const patternArgument =
+			args.named.get('pattern') ?? args.positional[signature.pattern];
+		const optionsArgument =
+			args.named.get('options') ?? args.positional[signature.options];
+		const literal =
+			patternArgument &&
+			readLiteralArgument(text, patternArgument, CSHARP_SYNTAX);
+		if (!literal) {
+			continue;
+		}
+
+		patterns.push(
+			Object.freeze({
+				pattern: literal.value,
+				flags: optionsArgument
+					? mapFlagNames(
+							text.slice(optionsArgument.start, optionsArgument.end),
+							REGEX_OPTIONS,
+						)
+					: '',
+				...locationAt(lines, siteOffset, closeParen + 1),
+				match: text.slice(siteOffset, closeParen + 1),
+				dialect: 'dotnet' as const,
+			}),
+		);
+	}
+
+	return Object.freeze(patterns);
+}
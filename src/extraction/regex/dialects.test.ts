@@ -0,0 +1,270 @@
+import { describe, expect, it } from 'vitest';
+import { checkDialectSyntax, toJavaScriptPattern } from './dialects';
+
+describe('checkDialectSyntax', () => {
+	describe('javascript', () => {
+		it('should accept valid JavaScript patterns', () => {
+			const result = checkDialectSyntax('(?<=a)b\\1', '', 'javascript');
+
+			expect(result.valid).toBe(true);
+			expect(result.linearTime).toBe(false);
+		});
+
+		it('should report JavaScript syntax errors', () => {
+			const result = checkDialectSyntax('(abc', '', 'javascript');
+
+			expect(result.valid).toBe(false);
+			expect(result.error).toBeDefined();
+		});
+	});
+
+	describe('rust', () => {
+		it('should accept patterns using Rust-only syntax', () => {
+			const result = checkDialectSyntax(
+				'(?i)(?P<year>\\d{4})-[[:digit:]]{2}\\z',
+				'',
+				'rust',
+			);
+
+			expect(result.valid).toBe(true);
+			expect(result.linearTime).toBe(true);
+		});
+
+		it('should reject look-around and suggest fancy-regex', () => {
+			const result = checkDialectSyntax('foo(?=bar)', '', 'rust');
+
+			expect(result.valid).toBe(false);
+			expect(result.error).toContain('Look-ahead');
+			expect(result.suggestedEngine).toBe('fancy-regex');
+		});
+
+		it('should reject backreferences', () => {
+			const result = checkDialectSyntax('(\\w)\\1', '', 'rust');
+
+			expect(result.valid).toBe(false);
+			expect(result.suggestedEngine).toBe('fancy-regex');
+		});
+
+		it('should reject unknown escapes and inline flags', () => {
+			expect(checkDialectSyntax('\\Z', '', 'rust').valid).toBe(false);
+			expect(checkDialectSyntax('(?g)a', '', 'rust').valid).toBe(false);
+		});
+
+		it('should report structural errors', () => {
+			const result = checkDialectSyntax('(a|b', '', 'rust');
+
+			expect(result.valid).toBe(false);
+			expect(result.suggestedEngine).toBeUndefined();
+		});
+
+		it('should note Unicode-aware Perl classes without warning', () => {
+			const result = checkDialectSyntax('\\w+', '', 'rust');
+			const translation = toJavaScriptPattern('\\w+', '', 'rust');
+
+			expect(result.valid).toBe(true);
+			expect(result.diagnostics).toHaveLength(0);
+			expect(translation.exact).toBe(true);
+			expect(translation.notes[0]).toContain('Unicode-aware in');
+		});
+	});
+
+	describe('python', () => {
+		it('should accept backtracking features', () => {
+			const result = checkDialectSyntax(
+				'(?P<q>[\'"])(?<=\\s)(?>\\w+)(?P=q)',
+				'',
+				'python',
+			);
+
+			expect(result.valid).toBe(true);
+			expect(result.linearTime).toBe(false);
+			expect(result.suggestedEngine).toBeUndefined();
+		});
+
+		it('should reject global flags after the start of the pattern', () => {
+			const result = checkDialectSyntax('abc(?i)', '', 'python');
+
+			expect(result.valid).toBe(false);
+			expect(result.error).toContain('start of the pattern');
+		});
+
+		it('should reject Unicode property classes', () => {
+			expect(checkDialectSyntax('\\p{L}+', '', 'python').valid).toBe(false);
+		});
+
+		it('should reject (?<name>) groups, which re does not know', () => {
+			const result = checkDialectSyntax('(?<n>x)', '', 'python');
+
+			expect(result.valid).toBe(false);
+			expect(result.error).toContain('(?<name>) groups are not supported');
+		});
+
+		it('should reject group names only the Rust crate allows', () => {
+			expect(checkDialectSyntax('(?P<a.b>x)', '', 'python').error).toBe(
+				'Invalid capture group name "a.b"',
+			);
+			expect(checkDialectSyntax('(?P<a.b>x)', '', 'rust').valid).toBe(true);
+		});
+	});
+
+	describe('go', () => {
+		it('should accept RE2 syntax with inline flags', () => {
+			const result = checkDialectSyntax(
+				'(?i)(?P<key>[[:alpha:]]+)=\\Q$1.00\\E',
+				'',
+				'go',
+			);
+
+			expect(result.valid).toBe(true);
+			expect(result.linearTime).toBe(true);
+			expect(result.diagnostics).toHaveLength(0);
+		});
+
+		it('should reject backreferences and look-around', () => {
+			const backreference = checkDialectSyntax('(a)\\1', '', 'go');
+			const lookahead = checkDialectSyntax('a(?!b)', '', 'go');
+
+			expect(backreference.valid).toBe(false);
+			expect(lookahead.valid).toBe(false);
+			expect(lookahead.suggestedEngine).toBe('github.com/dlclark/regexp2');
+		});
+
+		it('should reject syntax Go does not share with Rust', () => {
+			expect(checkDialectSyntax('(?x)a', '', 'go').valid).toBe(false);
+			expect(checkDialectSyntax('\\u0041', '', 'go').valid).toBe(false);
+		});
+	});
+
+	describe('java', () => {
+		it('should accept backtracking features', () => {
+			const result = checkDialectSyntax(
+				'(?<word>\\w+)\\s+\\k<word>(?=\\.)\\p{IsGreek}',
+				'',
+				'java',
+			);
+
+			expect(result.valid).toBe(true);
+			expect(result.linearTime).toBe(false);
+		});
+
+		it('should warn about possessive quantifiers and atomic groups', () => {
+			const result = checkDialectSyntax('(?>a++)b*+', '', 'java');
+
+			expect(result.valid).toBe(true);
+			expect(result.diagnostics.map((d) => d.severity)).toEqual([
+				'warning',
+				'warning',
+			]);
+		});
+
+		it('should reject syntax java.util.regex does not support', () => {
+			expect(checkDialectSyntax('(?P<name>a)', '', 'java').valid).toBe(false);
+			expect(checkDialectSyntax('(?(1)a|b)', '', 'java').valid).toBe(false);
+		});
+	});
+
+	describe('dotnet', () => {
+		it('should accept conditionals and class subtraction', () => {
+			const result = checkDialectSyntax(
+				'(a)?(?(1)b|c)[a-z-[aeiou]](?#comment)',
+				'',
+				'dotnet',
+			);
+
+			expect(result.valid).toBe(true);
+			expect(result.diagnostics.length).toBeGreaterThan(0);
+		});
+
+		it('should reject possessive quantifiers', () => {
+			expect(checkDialectSyntax('a++', '', 'dotnet').valid).toBe(false);
+		});
+	});
+});
+
+describe('toJavaScriptPattern', () => {
+	it('should leave JavaScript patterns untouched', () => {
+		const translation = toJavaScriptPattern('a+', 'gi', 'javascript');
+
+		expect(translation.pattern).toBe('a+');
+		expect(translation.flags).toBe('gi');
+	});
+
+	it('should lift leading inline flags into JavaScript flags', () => {
+		const translation = toJavaScriptPattern('(?im)^abc$', '', 'rust');
+
+		expect(translation.pattern).toBe('^abc$');
+		expect(translation.flags).toBe('gim');
+		expect(translation.exact).toBe(true);
+	});
+
+	it('should convert named groups and anchors', () => {
+		const translation = toJavaScriptPattern('\\A(?P<word>\\w+)\\z', '', 'rust');
+
+		expect(translation.pattern).toBe('(?<![\\s\\S])(?<word>\\w+)(?![\\s\\S])');
+		expect(new RegExp(translation.pattern, translation.flags).test('hi')).toBe(
+			true,
+		);
+	});
+
+	it('should strip whitespace and comments in verbose mode', () => {
+		const translation = toJavaScriptPattern(
+			'(?x)\n  \\d+  # digits\n  [ a-z ]',
+			'',
+			'rust',
+		);
+
+		expect(translation.pattern).toBe('\\d+[a-z]');
+	});
+
+	it('should swap greediness when U is set', () => {
+		const translation = toJavaScriptPattern('a+b*?', 'U', 'rust');
+
+		expect(translation.pattern).toBe('a+?b*');
+	});
+
+	it('should expand POSIX classes and Unicode escapes', () => {
+		const translation = toJavaScriptPattern(
+			'[[:upper:]_]\\x{1F600}\\pL\\p{Greek}',
+			'',
+			'rust',
+		);
+
+		expect(translation.pattern).toBe(
+			'[A-Z_]\\u{1F600}\\p{L}\\p{Script=Greek}',
+		);
+		expect(translation.flags).toBe('gu');
+		expect(
+			() => new RegExp(translation.pattern, translation.flags),
+		).not.toThrow();
+	});
+
+	it('should note approximations for scoped flags', () => {
+		const translation = toJavaScriptPattern('a(?i:b)c', '', 'rust');
+
+		expect(translation.pattern).toBe('a(?:b)c');
+		expect(translation.exact).toBe(false);
+		expect(translation.notes.length).toBeGreaterThan(0);
+	});
+	it('should translate Python-specific syntax', () => {
+		const translation = toJavaScriptPattern(
+			'(?P<n>a{,2})(?#note)\\012\\Z',
+			'i',
+			'python',
+		);
+
+		expect(translation.pattern).toBe('(?<n>a{0,2})\\x0a(?![\\s\\S])');
+		expect(translation.flags).toBe('gi');
+	});
+
+	it("should let $ match before a final newline like Python's re", () => {
+		const translation = toJavaScriptPattern('end$', '', 'python');
+		const regex = new RegExp(translation.pattern, translation.flags);
+
+		expect(regex.test('the end\n')).toBe(true);
+	});
+	it('should expand Go quoted literals and octal escapes', () => {
+		const translation = toJavaScriptPattern('\\Q(a.b)\\E\\101', '', 'go');
+
+		expect(translation.pattern).toBe('\\(a\\.b\\)\\x41');
+	});
+});
@@ -0,0 +1,1016 @@
+/**
+ * Dialect-aware regex validation
+ * Patterns extracted from non-JavaScript sources are checked against the
+ * rules of their own engine, and translated into an equivalent JavaScript
+ * pattern so they can still be tested with RegExp
+ */
+
+import type { RegexDialect } from '../../types';
+
+export interface DialectDiagnostic {
+	readonly severity: 'error' | 'warning';
+	readonly message: string;
+	readonly index?: number | undefined; // Offset in the pattern, when known
+}
+
+export interface DialectCheckResult {
+	readonly dialect: RegexDialect;
+	readonly valid: boolean;
+	readonly error?: string | undefined; // First error, for one-line summaries
+	readonly diagnostics: readonly DialectDiagnostic[];
+	readonly suggestedEngine?: string | undefined; // Backtracking engine that would accept the pattern
+	readonly linearTime: boolean; // Engine guarantees linear-time matching (no ReDoS)
+}
+
+export interface JavaScriptTranslation {
+	readonly pattern: string;
+	readonly flags: string;
+	readonly exact: boolean; // False when some semantics could only be approximated
+	readonly notes: readonly string[];
+}
+
+/**
+ * Syntax rules of a regex engine, as far as they differ from JavaScript
+ * Features an engine lacks are errors; features JavaScript lacks are
+ * approximated in the translation and reported as notes
+ */
+interface DialectRules {
+	readonly engine: string;
+	readonly inlineFlags: string;
+	readonly ungreedyFlag?: string | undefined; // Swaps greedy and lazy quantifiers
+	readonly unicodeClassFlag?: string | undefined; // Makes \d, \w, \s Unicode-aware
+	readonly asciiFlag?: string | undefined; // Makes \d, \w, \s ASCII-only
+	readonly linearTime: boolean; // Engine guarantees linear-time matching
+	readonly backtrackingEngine?: string | undefined; // Accepts what a linear-time engine rejects
+	readonly lookaround: boolean;
+	readonly backreferences: boolean;
+	readonly atomicGroups: boolean; // (?>...)
+	readonly possessiveQuantifiers: boolean; // a++, a*+, ...
+	readonly pythonNamedGroups: boolean; // (?P<name>...) and (?P=name)
+	readonly angleNamedGroups: boolean; // (?<name>...)
+	readonly groupName: RegExp; // Names a named group may have
+	readonly conditionals: boolean; // (?(1)yes|no)
+	readonly commentGroups: boolean; // (?#...)
+	readonly unicodeProperties: boolean; // \p{...}
+	readonly namedCharacters: boolean; // \N{EM DASH}
+	readonly octalEscape?: RegExp | undefined; // Digits after the backslash
+	readonly hexEscapes: string; // Letters introducing \x41, \u0041, ...
+	readonly quoting: boolean; // \Q...\E
+	readonly anchors: Readonly<Record<string, string>>; // \A, \z, ... (not in classes)
+	readonly escapes: Readonly<Record<string, string>>; // Other escapes, e.g. \h
+	readonly posixProperties: boolean; // \p{Alpha}, \p{Punct}, ...
+	readonly classBackspace: boolean; // [\b] is a backspace rather than an error
+	readonly wordEdges: boolean; // \<, \>, \b{start}, ...
+	readonly posixClasses: boolean; // [[:alpha:]]
+	readonly nestedClasses: boolean; // [a[bc]]
+	readonly classSetOperators: string; // && -- ~~ inside classes
+	readonly classSubtraction: boolean; // [a-z-[aeiou]]
+	readonly unicodePerlClasses: boolean; // \d, \w, \s match Unicode by default
+	readonly verboseClasses: boolean; // x mode also ignores whitespace in classes
+	readonly leadingGlobalFlags: boolean; // (?flags) is only allowed at the start
+	readonly dollarBeforeFinalNewline: boolean; // $ also matches before a trailing \n
+	readonly omittedMinimum: boolean; // {,n} means {0,n}
+}
+
+const START_OF_TEXT = '(?<![\\s\\S])';
+const END_OF_TEXT = '(?![\\s\\S])';
+const END_BEFORE_NEWLINE = '(?=\\n?(?![\\s\\S]))';
+const HORIZONTAL_SPACE =
+	'\\t \\xA0\\u1680\\u180E\\u2000-\\u200A\\u202F\\u205F\\u3000';
+const VERTICAL_SPACE = '\\n\\x0B\\f\\r\\x85\\u2028\\u2029';
+
+const RUST_RULES: DialectRules = Object.freeze({
+	engine: 'the Rust regex crate',
+	inlineFlags: 'imsUuxR',
+	ungreedyFlag: 'U',
+	unicodeClassFlag: 'u',
+	linearTime: true,
+	backtrackingEngine: 'fancy-regex',
+	lookaround: false,
+	backreferences: false,
+	atomicGroups: false,
+	possessiveQuantifiers: false,
+	pythonNamedGroups: true,
+	angleNamedGroups: true,
+	groupName: /^[A-Za-z_][\w.[\]]*$/,
+	conditionals: false,
+	commentGroups: false,
+	unicodeProperties: true,
+	namedCharacters: false,
+	hexEscapes: 'xuU',
+	quoting: false,
+	anchors: Object.freeze({ A: START_OF_TEXT, z: END_OF_TEXT }),
+	escapes: Object.freeze({}),
+	posixProperties: false,
+	classBackspace: false,
+	wordEdges: true,
+	posixClasses: true,
+	nestedClasses: true,
+	classSetOperators: '&-~',
+	classSubtraction: false,
+	unicodePerlClasses: true,
+	verboseClasses: true,
+	leadingGlobalFlags: false,
+	dollarBeforeFinalNewline: false,
+	omittedMinimum: false,
+});
+
+const PYTHON_RULES: DialectRules = Object.freeze({
+	engine: "Python's re module",
+	inlineFlags: 'aiLmsux',
+	asciiFlag: 'a',
+	linearTime: false,
+	lookaround: true,
+	backreferences: true,
+	atomicGroups: true,
+	possessiveQuantifiers: true,
+	pythonNamedGroups: true,
+	angleNamedGroups: false,
+	groupName: /^[A-Za-z_]\w*$/,
+	conditionals: true,
+	commentGroups: true,
+	unicodeProperties: false,
+	namedCharacters: true,
+	octalEscape: /^(?:0[0-7]{0,2}|[0-3][0-7]{2})/,
+	hexEscapes: 'xuU',
+	quoting: false,
+	anchors: Object.freeze({ A: START_OF_TEXT, Z: END_OF_TEXT }),
+	escapes: Object.freeze({}),
+	posixProperties: false,
+	classBackspace: true,
+	wordEdges: false,
+	posixClasses: false,
+	nestedClasses: false,
+	classSetOperators: '',
+	classSubtraction: false,
+	unicodePerlClasses: true,
+	verboseClasses: false,
+	leadingGlobalFlags: true,
+	dollarBeforeFinalNewline: true,
+	omittedMinimum: true,
+});
+
+const GO_RULES: DialectRules = Object.freeze({
+	engine: "Go's regexp package",
+	inlineFlags: 'imsU',
+	ungreedyFlag: 'U',
+	linearTime: true,
+	backtrackingEngine: 'github.com/dlclark/regexp2',
+	lookaround: false,
+	backreferences: false,
+	atomicGroups: false,
+	possessiveQuantifiers: false,
+	pythonNamedGroups: true,
+	angleNamedGroups: true,
+	groupName: /^\w+$/,
+	conditionals: false,
+	commentGroups: false,
+	unicodeProperties: true,
+	namedCharacters: false,
+	octalEscape: /^(?:0[0-7]{0,2}|[1-7][0-7]{1,2})/,
+	hexEscapes: 'x',
+	quoting: true,
+	anchors: Object.freeze({ A: START_OF_TEXT, z: END_OF_TEXT }),
+	escapes: Object.freeze({}),
+	posixProperties: false,
+	classBackspace: false,
+	wordEdges: false,
+	posixClasses: true,
+	nestedClasses: false,
+	classSetOperators: '',
+	classSubtraction: false,
+	unicodePerlClasses: false,
+	verboseClasses: false,
+	leadingGlobalFlags: false,
+	dollarBeforeFinalNewline: false,
+	omittedMinimum: false,
+});
+
+const JAVA_RULES: DialectRules = Object.freeze({
+	engine: "Java's java.util.regex",
+	inlineFlags: 'idmsuxU',
+	unicodeClassFlag: 'U',
+	linearTime: false,
+	lookaround: true,
+	backreferences: true,
+	atomicGroups: true,
+	possessiveQuantifiers: true,
+	pythonNamedGroups: false,
+	angleNamedGroups: true,
+	groupName: /^[A-Za-z][A-Za-z0-9]*$/,
+	conditionals: false,
+	commentGroups: false,
+	unicodeProperties: true,
+	namedCharacters: true,
+	octalEscape: /^0(?:[0-3][0-7]{2}|[0-7]{1,2})/,
+	hexEscapes: 'xu',
+	quoting: true,
+	anchors: Object.freeze({
+		A: START_OF_TEXT,
+		z: END_OF_TEXT,
+		Z: END_BEFORE_NEWLINE,
+		R: `(?:\\r\\n|[${VERTICAL_SPACE}])`,
+	}),
+	escapes: Object.freeze({
+		e: '\\x1b',
+		h: `[${HORIZONTAL_SPACE}]`,
+		H: `[^${HORIZONTAL_SPACE}]`,
+		v: `[${VERTICAL_SPACE}]`,
+		V: `[^${VERTICAL_SPACE}]`,
+	}),
+	posixProperties: true,
+	classBackspace: false,
+	wordEdges: false,
+	posixClasses: false,
+	nestedClasses: true,
+	classSetOperators: '&',
+	classSubtraction: false,
+	unicodePerlClasses: false,
+	verboseClasses: true,
+	leadingGlobalFlags: false,
+	dollarBeforeFinalNewline: true,
+	omittedMinimum: false,
+});
+
+const DOTNET_RULES: DialectRules = Object.freeze({
+	engine: '.NET System.Text.RegularExpressions',
+	inlineFlags: 'imnsx',
+	linearTime: false,
+	lookaround: true,
+	backreferences: true,
+	atomicGroups: true,
+	possessiveQuantifiers: false,
+	pythonNamedGroups: false,
+	angleNamedGroups: true,
+	groupName: /^[A-Za-z_]\w*$/,
+	conditionals: true,
+	commentGroups: true,
+	unicodeProperties: true,
+	namedCharacters: false,
+	octalEscape: /^(?:0[0-7]{0,2}|[1-3][0-7]{2})/,
+	hexEscapes: 'xu',
+	quoting: false,
+	anchors: Object.freeze({
+		A: START_OF_TEXT,
+		z: END_OF_TEXT,
+		Z: END_BEFORE_NEWLINE,
+	}),
+	escapes: Object.freeze({ e: '\\x1b' }),
+	posixProperties: false,
+	classBackspace: true,
+	wordEdges: false,
+	posixClasses: false,
+	nestedClasses: false,
+	classSetOperators: '',
+	classSubtraction: true,
+	unicodePerlClasses: true,
+	verboseClasses: false,
+	leadingGlobalFlags: false,
+	dollarBeforeFinalNewline: true,
+	omittedMinimum: false,
+});
+
+const DIALECT_RULES: Readonly<Partial<Record<RegexDialect, DialectRules>>> =
+	Object.freeze({
+		rust: RUST_RULES,
+		python: PYTHON_RULES,
+		go: GO_RULES,
+		java: JAVA_RULES,
+		dotnet: DOTNET_RULES,
+	});
+
+/**
+ * ASCII ranges for POSIX bracket classes such as [[:alpha:]]
+ */
+const POSIX_CLASSES: Readonly<Record<string, string>> = Object.freeze({
+	alnum: '0-9A-Za-z',
+	alpha: 'A-Za-z',
+	ascii: '\\x00-\\x7F',
+	blank: '\\t ',
+	cntrl: '\\x00-\\x1F\\x7F',
+	digit: '0-9',
+	graph: '!-~',
+	lower: 'a-z',
+	print: ' -~',
+	punct: '!-\\/:-@\\[-`{-~',
+	space: '\\t\\n\\v\\f\\r ',
+	upper: 'A-Z',
+	word: '0-9A-Za-z_',
+	xdigit: '0-9A-Fa-f',
+});
+
+/**
+ * Characters that may be escaped with a backslash in a unicode-mode RegExp
+ */
+const JS_SYNTAX_CHARS = '^$\\.*+?()[]{}|/';
+
+/**
+ * Check a pattern against the syntax rules of its dialect
+ */
+export function checkDialectSyntax(
+	pattern: string,
+	flags: string,
+	dialect: RegexDialect,
+): DialectCheckResult {
+	const rules = DIALECT_RULES[dialect];
+	if (!rules) {
+		const error = compileError(pattern, flags);
+		return Object.freeze({
+			dialect,
+			valid: error === undefined,
+			error,
+			diagnostics: Object.freeze(
+				error
+					? [Object.freeze({ severity: 'error' as const, message: error })]
+					: [],
+			),
+			linearTime: false,
+		});
+	}
+
+	const analysis = analyzePattern(pattern, flags, rules);
+	const diagnostics = [...analysis.diagnostics];
+
+	// Anything the dialect walker accepted must still be well-formed
+	if (!diagnostics.some((d) => d.severity === 'error')) {
+		const error = compileError(analysis.pattern, analysis.flags);
+		if (error) {
+			diagnostics.push(
+				Object.freeze({
+					severity: analysis.exact ? ('error' as const) : ('warning' as const),
+					message: analysis.exact
+						? error
+						: `Syntax could only be checked approximately: ${error}`,
+				}),
+			);
+		}
+	}
+
+	const firstError = diagnostics.find((d) => d.severity === 'error');
+	return Object.freeze({
+		dialect,
+		valid: firstError === undefined,
+		error: firstError?.message,
+		diagnostics: Object.freeze(diagnostics),
+		suggestedEngine: analysis.needsBacktracking
+			? rules.backtrackingEngine
+			: undefined,
+		linearTime: rules.linearTime,
+	});
+}
+
+/**
+ * Translate a dialect pattern into a JavaScript pattern with equivalent
+ * matching behaviour, for running it through RegExp
+ */
+export function toJavaScriptPattern(
+	pattern: string,
+	flags: string,
+	dialect: RegexDialect,
+): JavaScriptTranslation {
+	const rules = DIALECT_RULES[dialect];
+	if (!rules) {
+		return Object.freeze({
+			pattern,
+			flags,
+			exact: true,
+			notes: Object.freeze([]),
+		});
+	}
+
+	const analysis = analyzePattern(pattern, flags, rules);
+	return Object.freeze({
+		pattern: analysis.pattern,
+		flags: analysis.flags,
+		exact: analysis.exact,
+		notes: analysis.notes,
+	});
+}
+
+interface DialectAnalysis {
+	readonly pattern: string;
+	readonly flags: string;
+	readonly exact: boolean;
+	readonly notes: readonly string[];
+	readonly diagnostics: readonly DialectDiagnostic[];
+	readonly needsBacktracking: boolean;
+}
+
+/**
+ * Walk a dialect pattern once, collecting diagnostics and building
+ * the JavaScript translation at the same time
+ */
+function analyzePattern(
+	pattern: string,
+	flags: string,
+	rules: DialectRules,
+): DialectAnalysis {
+	const diagnostics: DialectDiagnostic[] = [];
+	const notes = new Set<string>();
+	// Every match is reported when testing, so test globally
+	const jsFlags = new Set<string>(['g']);
+	let verbose = false;
+	let swapGreed = false;
+	let unicodeClasses = rules.unicodePerlClasses;
+	let needsUnicode = false;
+	let usesPerlClasses = false;
+	let needsBacktracking = false;
+	let groupDepth = 0;
+	let out = '';
+	let i = 0;
+
+	const error = (message: string, index: number): void => {
+		diagnostics.push(Object.freeze({ severity: 'error', message, index }));
+	};
+	const unsupported = (feature: string, index: number): void => {
+		const hint = rules.backtrackingEngine
+			? ` (requires ${rules.backtrackingEngine})`
+			: '';
+		error(`${feature} not supported by ${rules.engine}${hint}`, index);
+		needsBacktracking = rules.backtrackingEngine !== undefined;
+	};
+	const approximated = (feature: string): void => {
+		const message = `${feature} not supported by JavaScript`;
+		if (!diagnostics.some((d) => d.message === message)) {
+			diagnostics.push(Object.freeze({ severity: 'warning', message }));
+		}
+		notes.add(`${feature} approximated when testing`);
+	};
+	const applyFlag = (letter: string, negate: boolean): void => {
+		if (letter === 'x') {
+			verbose = !negate;
+		} else if (letter === rules.ungreedyFlag) {
+			swapGreed = !negate;
+		} else if (letter === rules.unicodeClassFlag) {
+			unicodeClasses = !negate;
+		} else if (letter === rules.asciiFlag) {
+			unicodeClasses = negate;
+		} else if (letter === 'n') {
+			approximated('Explicit capture (n) is');
+		} else if ('ims'.includes(letter)) {
+			if (negate) {
+				notes.add(`Disabling inline flag "${letter}" is ignored when testing`);
+			} else {
+				jsFlags.add(letter);
+			}
+		}
+	};
+
+	for (const flag of flags) {
+		if (rules.inlineFlags.includes(flag)) {
+			applyFlag(flag, false);
+		}
+	}
+
+	// Class escapes such as \h expand to a class; inside another class only
+	// their body is kept and negated ones cannot be expressed
+	const toClassBody = (js: string): string => {
+		if (!js.startsWith('[')) {
+			return js;
+		}
+		if (js.startsWith('[^')) {
+			notes.add(
+				'Negated class escapes inside classes are ignored when testing',
+			);
+			return '';
+		}
+		return js.slice(1, -1);
+	};
+
+	const readEscape = (start: number, inClass: boolean) => {
+		const next = pattern[start + 1];
+		const end = start + 2;
+		if (next === undefined) {
+			error('Incomplete escape sequence at end of pattern', start);
+			return { js: '\\\\', end: start + 1 };
+		}
+
+		const octal = rules.octalEscape?.exec(pattern.slice(start + 1));
+		if (octal) {
+			const code = Number.parseInt(octal[0], 8);
+			return {
+				js: toHexEscape(String.fromCharCode(code)),
+				end: start + 1 + octal[0].length,
+			};
+		}
+		if (/[1-9]/.test(next)) {
+			if (!rules.backreferences) {
+				unsupported('Backreferences are', start);
+			}
+			const digits = /^\d{1,2}/.exec(pattern.slice(start + 1))?.[0] ?? next;
+			return { js: `\\${digits}`, end: start + 1 + digits.length };
+		}
+		if (next === 'k' && pattern[end] === '<') {
+			if (!rules.backreferences) {
+				unsupported('Backreferences are', start);
+			}
+			return { js: '\\k', end };
+		}
+		if (next === '0') {
+			error('Octal escapes are not supported', start);
+			return { js: '\\0', end };
+		}
+		const anchor = rules.anchors[next];
+		if (
+			inClass &&
+			(anchor !== undefined ||
+				next === 'B' ||
+				(next === 'b' && !rules.classBackspace) ||
+				(rules.wordEdges && (next === '<' || next === '>')))
+		) {
+			error(`Escape \\${next} is not allowed in a character class`, start);
+			return { js: '', end };
+		}
+		if (anchor !== undefined) {
+			return { js: anchor, end };
+		}
+		const special = rules.escapes[next];
+		if (special !== undefined) {
+			return { js: inClass ? toClassBody(special) : special, end };
+		}
+		if (rules.wordEdges && (next === '<' || next === '>')) {
+			return { js: next === '<' ? '\\b(?=\\w)' : '\\b(?<=\\w)', end };
+		}
+		if (next === 'b' && rules.wordEdges && pattern[end] === '{') {
+			const edge = /^\{(start|end|start-half|end-half)\}/.exec(
+				pattern.slice(end),
+			);
+			if (!edge) {
+				error('Unrecognized word boundary assertion', start);
+				return { js: '\\b', end };
+			}
+			const edges: Record<string, string> = {
+				start: '\\b(?=\\w)',
+				end: '\\b(?<=\\w)',
+				'start-half': '(?<!\\w)',
+				'end-half': '(?!\\w)',
+			};
+			return { js: edges[edge[1] || ''] || '\\b', end: end + edge[0].length };
+		}
+		if ('dDsSwWbB'.includes(next)) {
+			// Inside a class \b is a backspace, not a word boundary
+			if (!inClass || next !== 'b') {
+				usesPerlClasses = true;
+			}
+			return { js: `\\${next}`, end };
+		}
+		if (next === 'p' || next === 'P') {
+			const property = /^(?:\{([^}]*)\}|([A-Za-z]))/.exec(pattern.slice(end));
+			if (!rules.unicodeProperties) {
+				error(
+					`Unicode classes \\${next}{...} are not supported by ${rules.engine}`,
+					start,
+				);
+				return { js: '', end: end + (property?.[0].length ?? 0) };
+			}
+			if (!property) {
+				error(`Incomplete Unicode class \\${next}`, start);
+				return { js: '', end };
+			}
+			const posix = rules.posixProperties
+				? POSIX_CLASSES[(property[1] ?? '').toLowerCase()]
+				: undefined;
+			if (posix) {
+				const negated = next === 'P' ? '^' : '';
+				const cls = `[${negated}${posix}]`;
+				return {
+					js: inClass ? toClassBody(cls) : cls,
+					end: end + property[0].length,
+				};
+			}
+			needsUnicode = true;
+			const name = property[1] ?? property[2] ?? '';
+			return {
+				js: `\\${next}{${toJavaScriptProperty(name, notes)}}`,
+				end: end + property[0].length,
+			};
+		}
+		if (rules.hexEscapes.includes(next)) {
+			const sizes: Record<string, number> = { x: 2, u: 4, U: 8 };
+			const hex = new RegExp(
+				`^(?:\\{([0-9A-Fa-f]{1,8})\\}|([0-9A-Fa-f]{${sizes[next]}}))`,
+			).exec(pattern.slice(end));
+			if (!hex) {
+				error(`Invalid hexadecimal escape \\${next}`, start);
+				return { js: '', end };
+			}
+			needsUnicode = true;
+			const code = hex[1] ?? hex[2] ?? '0';
+			return { js: `\\u{${code}}`, end: end + hex[0].length };
+		}
+		if (next === 'a') {
+			return { js: '\\x07', end };
+		}
+		if (next === 'Q' && rules.quoting) {
+			const close = pattern.indexOf('\\E', end);
+			const quoted = pattern.slice(end, close === -1 ? undefined : close);
+			return {
+				js: [...quoted].map((char) => escapeLiteral(char)).join(''),
+				end: close === -1 ? pattern.length : close + 2,
+			};
+		}
+		if (next === 'N' && rules.namedCharacters && pattern[end] === '{') {
+			const close = pattern.indexOf('}', end);
+			notes.add('Named characters \\N{...} match any character when testing');
+			return {
+				js: '[\\s\\S]',
+				end: close === -1 ? pattern.length : close + 1,
+			};
+		}
+		if ('fnrtv'.includes(next)) {
+			return { js: `\\${next}`, end };
+		}
+		if (/[A-Za-z]/.test(next) || next.charCodeAt(0) > 0x7f) {
+			error(`Unrecognized escape sequence \\${next}`, start);
+			return { js: '', end };
+		}
+
+		// Escaped ASCII punctuation or whitespace is a literal
+		return {
+			js: inClass && next === '-' ? '\\-' : escapeLiteral(next),
+			end,
+		};
+	};
+
+	const readClass = (
+		start: number,
+	): { body: string; negated: boolean; end: number } => {
+		let j = start + 1;
+		let negated = false;
+		if (pattern[j] === '^') {
+			negated = true;
+			j++;
+		}
+
+		let body = '';
+		let first = true;
+		let dropRest = false;
+		const append = (part: string): void => {
+			if (!dropRest) {
+				body += part;
+			}
+		};
+
+		while (j < pattern.length) {
+			const c = pattern[j] || '';
+			if (c === ']' && !first) {
+				return { body, negated, end: j + 1 };
+			}
+			first = false;
+
+			if (verbose && rules.verboseClasses && /\s/.test(c)) {
+				j++;
+				continue;
+			}
+			if (c === '[') {
+				const posix = rules.posixClasses
+					? /^\[:(\^?)([a-z]+):\]/.exec(pattern.slice(j))
+					: null;
+				if (posix) {
+					const ranges = POSIX_CLASSES[posix[2] || ''];
+					if (!ranges) {
+						error(`Unknown POSIX class [:${posix[2]}:]`, j);
+					} else if (posix[1]) {
+						notes.add('Negated POSIX classes are approximated when testing');
+					} else {
+						append(ranges);
+					}
+					j += posix[0].length;
+					continue;
+				}
+				if (rules.nestedClasses) {
+					const nested = readClass(j);
+					if (nested.negated) {
+						notes.add('Negated nested classes are approximated when testing');
+					} else {
+						append(nested.body);
+					}
+					j = nested.end;
+					continue;
+				}
+			}
+			if (rules.classSubtraction && c === '-' && pattern[j + 1] === '[') {
+				notes.add(
+					'Class subtraction is approximated by the outer class when testing',
+				);
+				j = readClass(j + 1).end;
+				continue;
+			}
+			if (
+				c !== '' &&
+				rules.classSetOperators.includes(c) &&
+				pattern[j + 1] === c
+			) {
+				notes.add(
+					'Class set operations (&&, --, ~~) are approximated by their left operand when testing',
+				);
+				dropRest = true;
+				j += 2;
+				continue;
+			}
+			if (c === '\\') {
+				const escape = readEscape(j, true);
+				append(escape.js);
+				j = escape.end;
+				continue;
+			}
+
+			append(c === ']' || c === '[' ? `\\${c}` : c);
+			j++;
+		}
+
+		error('Unclosed character class', start);
+		return { body, negated, end: pattern.length };
+	};
+
+	while (i < pattern.length) {
+		const c = pattern[i] || '';
+
+		if (verbose && /\s/.test(c)) {
+			i++;
+			continue;
+		}
+		if (verbose && c === '#') {
+			const newline = pattern.indexOf('\n', i);
+			i = newline === -1 ? pattern.length : newline + 1;
+			continue;
+		}
+
+		if (c === '\\') {
+			const escape = readEscape(i, false);
+			out += escape.js;
+			i = escape.end;
+			continue;
+		}
+
+		if (c === '[') {
+			const cls = readClass(i);
+			out += `[${cls.negated ? '^' : ''}${cls.body}]`;
+			i = cls.end;
+			continue;
+		}
+
+		if (c === '(') {
+			const rest = pattern.slice(i);
+			const lookaround = /^\(\?(<?[=!])/.exec(rest);
+			if (lookaround) {
+				const kind = lookaround[1]?.startsWith('<')
+					? 'Look-behind'
+					: 'Look-ahead';
+				if (!rules.lookaround) {
+					unsupported(`${kind} assertions are`, i);
+				}
+				out += lookaround[0];
+				groupDepth++;
+				i += lookaround[0].length;
+				continue;
+			}
+			if (rest.startsWith('(?>')) {
+				if (rules.atomicGroups) {
+					approximated('Atomic groups are');
+				} else {
+					unsupported('Atomic groups are', i);
+				}
+				out += '(?:';
+				groupDepth++;
+				i += 3;
+				continue;
+			}
+			const named = /^\(\?(P?<)([^>]*)>/.exec(rest);
+			if (named) {
+				const name = named[2] || '';
+				if (named[1] === 'P<' && !rules.pythonNamedGroups) {
+					unsupported('(?P<name>) groups are', i);
+				} else if (named[1] === '<' && !rules.angleNamedGroups) {
+					unsupported('(?<name>) groups are', i);
+				}
+				if (!rules.groupName.test(name)) {
+					error(`Invalid capture group name "${name}"`, i);
+				}
+				out += `(?<${toJavaScriptGroupName(name)}>`;
+				groupDepth++;
+				i += named[0].length;
+				continue;
+			}
+			const namedReference = /^\(\?P([=>])(\w+)\)/.exec(rest);
+			if (namedReference) {
+				if (!rules.pythonNamedGroups) {
+					unsupported('(?P<name>) groups are', i);
+				} else if (namedReference[1] === '=') {
+					if (!rules.backreferences) {
+						unsupported('Backreferences are', i);
+					}
+					out += `\\k<${toJavaScriptGroupName(namedReference[2] || '')}>`;
+				} else {
+					error(
+						`Recursive group calls are not supported by ${rules.engine}`,
+						i,
+					);
+				}
+				i += namedReference[0].length;
+				continue;
+			}
+			if (rules.commentGroups && rest.startsWith('(?#')) {
+				const close = pattern.indexOf(')', i);
+				i = close === -1 ? pattern.length : close + 1;
+				continue;
+			}
+			const flagGroup = /^\(\?([A-Za-z-]*)([:)])/.exec(rest);
+			if (flagGroup) {
+				const letters = flagGroup[1] || '';
+				const scoped = flagGroup[2] === ':';
+				let negate = false;
+				for (const letter of letters) {
+					if (letter === '-') {
+						negate = true;
+						continue;
+					}
+					if (!rules.inlineFlags.includes(letter)) {
+						error(`Unrecognized inline flag "${letter}"`, i);
+						continue;
+					}
+					applyFlag(letter, negate);
+				}
+				if (!scoped && out !== '' && rules.leadingGlobalFlags) {
+					error('Global inline flags must be at the start of the pattern', i);
+				}
+				// Only leading (?flags) map exactly onto JavaScript flags
+				if (scoped || out !== '') {
+					if (letters.length > 0) {
+						notes.add(
+							'Scoped inline flags are applied to the whole pattern when testing',
+						);
+					}
+				}
+				if (scoped) {
+					out += '(?:';
+					groupDepth++;
+				}
+				i += flagGroup[0].length;
+				continue;
+			}
+			const conditional = /^\(\?\((\w+)\)/.exec(rest);
+			if (conditional) {
+				if (rules.conditionals) {
+					approximated('Conditionals are');
+				} else {
+					unsupported('Conditionals are', i);
+				}
+				out += '(?:';
+				groupDepth++;
+				i += conditional[0].length;
+				continue;
+			}
+			if (rest.startsWith('(?(')) {
+				unsupported('Conditionals are', i);
+				out += '(?:';
+				groupDepth++;
+				i += 3;
+				continue;
+			}
+			if (rest.startsWith('(?')) {
+				error('Unrecognized group syntax', i);
+				out += '(?:';
+				groupDepth++;
+				i += 2;
+				continue;
+			}
+			out += '(';
+			groupDepth++;
+			i++;
+			continue;
+		}
+
+		if (c === ')') {
+			groupDepth--;
+			if (groupDepth < 0) {
+				error('Unopened group', i);
+				groupDepth = 0;
+			}
+			out += ')';
+			i++;
+			continue;
+		}
+
+		const quantifier = (
+			rules.omittedMinimum
+				? /^(?:[*+?]|\{\d+(?:,\d*)?\}|\{,\d+\})/
+				: /^(?:[*+?]|\{\d+(?:,\d*)?\})/
+		).exec(pattern.slice(i));
+		if (quantifier) {
+			let end = i + quantifier[0].length;
+			let lazy = false;
+			if (pattern[end] === '?') {
+				lazy = true;
+				end++;
+			} else if (pattern[end] === '+') {
+				if (rules.possessiveQuantifiers) {
+					approximated('Possessive quantifiers are');
+				} else {
+					unsupported('Possessive quantifiers are', i);
+				}
+				end++;
+			}
+			out +=
+				quantifier[0].replace('{,', '{0,') + (lazy !== swapGreed ? '?' : '');
+			i = end;
+			continue;
+		}
+
+		if (c === '$' && rules.dollarBeforeFinalNewline && !jsFlags.has('m')) {
+			out += END_BEFORE_NEWLINE;
+			i++;
+			continue;
+		}
+
+		out += c === '{' || c === '}' || c === ']' ? `\\${c}` : c;
+		i++;
+	}
+
+	if (groupDepth > 0) {
+		error('Unclosed group', pattern.length);
+	}
+
+	// Only non-ASCII input matches differently, so this is a note that does
+	// not make the translation inexact or the pattern risky
+	const classNotes =
+		usesPerlClasses && unicodeClasses
+			? [
+					`\\d, \\w, \\s and \\b are Unicode-aware in ${rules.engine} but match ASCII only when testing`,
+				]
+			: [];
+
+	if (needsUnicode) {
+		jsFlags.add('u');
+	}
+
+	return Object.freeze({
+		pattern: out,
+		flags: [...jsFlags].join(''),
+		exact: notes.size === 0,
+		notes: Object.freeze([...notes, ...classNotes]),
+		diagnostics: Object.freeze(diagnostics),
+		needsBacktracking,
+	});
+}
+
+/**
+ * Map a Unicode property name to one JavaScript accepts
+ * Other engines accept bare script names (\p{Greek}), ':' separators and
+ * Is/In prefixes (\p{IsLatin}, \p{InGreek})
+ */
+function toJavaScriptProperty(name: string, notes: Set<string>): string {
+	const normalized = name.replace(':', '=').replace(/\s/g, '');
+	const unprefixed = normalized.replace(/^(?:Is|In)(?=[A-Z])/, '');
+	for (const candidate of [
+		normalized,
+		`Script=${normalized}`,
+		unprefixed,
+		`Script=${unprefixed}`,
+	]) {
+		try {
+			new RegExp(`\\p{${candidate}}`, 'u');
+			return candidate;
+		} catch {
+			// Try the next spelling
+		}
+	}
+	notes.add(`Unicode class \\p{${name}} has no JavaScript equivalent`);
+	return normalized;
+}
+
+/**
+ * Rename a capture group so JavaScript accepts it, e.g. Rust's "a.b" as
+ * "a_b" and Go's "1st" as "_1st"
+ */
+function toJavaScriptGroupName(name: string): string {
+	return name.replace(/[.[\]]/g, '_').replace(/^(?=\d)/, '_');
+}
+
+/**
+ * Escape a literal character so it is valid in a unicode-mode RegExp
+ */
+function toHexEscape(char: string): string {
+	return `\\x${char.charCodeAt(0).toString(16).padStart(2, '0')}`;
+}
+
+/**
+ * Write a literal character, escaping it only where RegExp requires
+ */
+function escapeLiteral(char: string): string {
+	if (JS_SYNTAX_CHARS.includes(char)) {
+		return `\\${char}`;
+	}
+	return /[\w\s]/.test(char) || char.charCodeAt(0) > 0x7f
+		? char
+		: toHexEscape(char);
+}
+
+function compileError(pattern: string, flags: string): string | undefined {
+	try {
+		new RegExp(pattern, flags);
+		return undefined;
+	} catch (error) {
+		return error instanceof Error ? error.message : String(error);
+	}
+}
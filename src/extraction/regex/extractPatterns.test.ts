@@ -0,0 +1,488 @@
+import { describe, expect, it } from 'vitest';
+import {
+	extractRegexPatterns,
+	formatLocation,
+	groupPatterns,
+	patternsWithin,
+} from './extractPatterns';
+
+describe('extractRegexPatterns', () => {
+	describe('JavaScript', () => {
+		it('should extract regex literals with flags', () => {
+			const patterns = extractRegexPatterns('const re = /\\d+/gi;');
+
+			expect(patterns).toHaveLength(1);
+			expect(patterns[0]?.pattern).toBe('\\d+');
+			expect(patterns[0]?.flags).toBe('gi');
+			expect(patterns[0]?.dialect).toBe('javascript');
+		});
+
+		it('should extract RegExp constructor calls', () => {
+			const patterns = extractRegexPatterns(
+				"const re = new RegExp('abc', 'g');",
+			);
+
+			expect(patterns).toHaveLength(1);
+			expect(patterns[0]?.pattern).toBe('abc');
+			expect(patterns[0]?.flags).toBe('g');
+		});
+
+		it('should unescape constructor strings like the engine sees them', () => {
+			const text = [
+				"new RegExp('\\\\d+\\\\.\\x41\\d');",
+				'new RegExp("it\'s \\"quoted\\"", \'i\');',
+				'new RegExp(`a\\\\sb`, `m`);',
+				'new RegExp(String.raw`\\d+\\.\\w`, "u");',
+			].join('\n');
+			const patterns = extractRegexPatterns(text);
+
+			expect(patterns.map((p) => [p.pattern, p.flags])).toEqual([
+				['\\d+\\.Ad', ''],
+				['it\'s "quoted"', 'i'],
+				['a\\sb', 'm'],
+				['\\d+\\.\\w', 'u'],
+			]);
+		});
+
+		it('should skip constructor arguments that are not constant', () => {
+			const text = [
+				'new RegExp(`${prefix}\\\\d+`);',
+				"new RegExp(source, 'g');",
+				"new RegExp('x'.repeat(3));",
+			].join('\n');
+
+			expect(extractRegexPatterns(text)).toHaveLength(0);
+		});
+
+		it('should join multi-line constructor calls and concatenation', () => {
+			const text = [
+				'const re = new RegExp(',
+				"  'foo' +",
+				"    ('\\\\d{' + 2 + '}') +",
+				'    `bar`,',
+				"  'g'",
+				');',
+			].join('\n');
+			const patterns = extractRegexPatterns(text);
+
+			expect(patterns).toHaveLength(1);
+			expect(patterns[0]?.pattern).toBe('foo\\d{2}bar');
+			expect(patterns[0]?.flags).toBe('g');
+			expect(patterns[0]?.partial).toBeUndefined();
+			expect([patterns[0]?.line, patterns[0]?.column]).toEqual([1, 12]);
+			expect([patterns[0]?.endLine, patterns[0]?.endColumn]).toEqual([6, 2]);
+		});
+
+		it('should count columns in UTF-16 code units, as the editor does', () => {
+			const patterns = extractRegexPatterns("const s = '😀'; const r = /a/;");
+
+			// The emoji is two code units, so /a/ starts at column 27, not 26
+			expect(patterns[0]?.column).toBe(27);
+			expect(patterns[0]?.endColumn).toBe(30);
+		});
+
+		it('should keep patterns with non-constant parts as partial', () => {
+			const text = [
+				"new RegExp('^' + prefix + '\\\\d+$', 'i');",
+				"new RegExp('a', flags);",
+			].join('\n');
+			const patterns = extractRegexPatterns(text);
+
+			expect(patterns.map((p) => [p.pattern, p.flags, p.partial])).toEqual([
+				['^\\d+$', 'i', true],
+				['a', '', true],
+			]);
+		});
+
+		it('should ignore slashes in strings, comments and division', () => {
+			const text = [
+				'const url = "/api/users/"; // see /docs/',
+				'const ratio = a / b / c;',
+				'/* /not/g */ const half = (total) / 2 / n;',
+				'const size = {width: 1}.width / 2 / scale;',
+				'const next = count++ / 2 / 1;',
+				"const s = 'new RegExp(\"x\")';",
+			].join('\n');
+
+			expect(extractRegexPatterns(text)).toHaveLength(0);
+		});
+
+		it('should keep slashes inside character classes', () => {
+			const patterns = extractRegexPatterns('const re = /[/\\]]+\\//g;');
+
+			expect(patterns[0]?.pattern).toBe('[/\\]]+\\/');
+			expect(patterns[0]?.flags).toBe('g');
+		});
+
+		it('should detect literals wherever an expression may start', () => {
+			const text = [
+				'if (ok) /a/.test(s);',
+				'return /b/;',
+				'const t = `x/${/c/.source}/y`;',
+				'list.map((s) => /d/.exec(s));',
+				'const el = <div>e</div>;',
+			].join('\n');
+			const patterns = extractRegexPatterns(text, 'typescriptreact');
+
+			expect(patterns.map((p) => p.pattern)).toEqual(['a', 'b', 'c', 'd']);
+			expect(patterns[2]?.line).toBe(3);
+			expect(patterns[2]?.column).toBe(16);
+		});
+	});
+
+	describe('Rust', () => {
+		it('should extract Regex::new with raw strings', () => {
+			const patterns = extractRegexPatterns(
+				'let re = Regex::new(r"^\\d{4}-\\d{2}$").unwrap();',
+				'rust',
+			);
+
+			expect(patterns).toHaveLength(1);
+			expect(patterns[0]?.pattern).toBe('^\\d{4}-\\d{2}$');
+			expect(patterns[0]?.flags).toBe('');
+			expect(patterns[0]?.dialect).toBe('rust');
+			expect(patterns[0]?.line).toBe(1);
+			expect(patterns[0]?.column).toBe(10);
+		});
+
+		it('should extract hashed raw strings containing quotes', () => {
+			const patterns = extractRegexPatterns(
+				'let re = Regex::new(r#"key="(\\w+)""#)?;',
+				'rust',
+			);
+
+			expect(patterns[0]?.pattern).toBe('key="(\\w+)"');
+		});
+
+		it('should unescape normal string literals', () => {
+			const patterns = extractRegexPatterns(
+				'let re = Regex::new("\\\\d+\\\\.\\x41");',
+				'rust',
+			);
+
+			expect(patterns[0]?.pattern).toBe('\\d+\\.A');
+		});
+
+		it('should map RegexBuilder options to flags across lines', () => {
+			const text = [
+				'let re = RegexBuilder::new(r"^abc$")',
+				'    .case_insensitive(true)',
+				'    .multi_line(true)',
+				'    .dot_matches_new_line(false)',
+				'    .build()?;',
+			].join('\n');
+			const patterns = extractRegexPatterns(text, 'rust');
+
+			expect(patterns).toHaveLength(1);
+			expect(patterns[0]?.pattern).toBe('^abc$');
+			expect(patterns[0]?.flags).toBe('im');
+			expect(patterns[0]?.endLine).toBe(5);
+		});
+
+		it('should extract every member of a RegexSet', () => {
+			const patterns = extractRegexPatterns(
+				'let set = RegexSet::new(&[r"\\w+", r"\\d+"]).unwrap();',
+				'rust',
+			);
+
+			expect(patterns.map((p) => p.pattern)).toEqual(['\\w+', '\\d+']);
+		});
+
+		it('should find patterns inside Lazy and lazy_static blocks', () => {
+			const text = [
+				'static A: Lazy<Regex> = Lazy::new(|| Regex::new(r"a+").unwrap());',
+				'lazy_static! {',
+				'    static ref B: Regex = Regex::new(r"b+").unwrap();',
+				'}',
+			].join('\n');
+			const patterns = extractRegexPatterns(text, 'rust');
+
+			expect(patterns.map((p) => p.pattern)).toEqual(['a+', 'b+']);
+			expect(patterns[1]?.line).toBe(3);
+		});
+
+		it('should read lazy-regex macro flags', () => {
+			const patterns = extractRegexPatterns(
+				'let re = regex!("^[a-z]+$"i);',
+				'rust',
+			);
+
+			expect(patterns[0]?.pattern).toBe('^[a-z]+$');
+			expect(patterns[0]?.flags).toBe('i');
+		});
+
+		it('should ignore constructors in comments and strings', () => {
+			const text = [
+				'// Regex::new(r"commented")',
+				'let s = "Regex::new(r\\"quoted\\")";',
+				'let path = "/api/users/";',
+			].join('\n');
+
+			expect(extractRegexPatterns(text, 'rust')).toHaveLength(0);
+		});
+	});
+
+	describe('Python', () => {
+		it('should extract re.compile with raw strings and flags', () => {
+			const patterns = extractRegexPatterns(
+				"EMAIL = re.compile(r'^[\\w.]+@\\w+$', re.I | re.MULTILINE)",
+				'python',
+			);
+
+			expect(patterns).toHaveLength(1);
+			expect(patterns[0]?.pattern).toBe('^[\\w.]+@\\w+$');
+			expect(patterns[0]?.flags).toBe('im');
+			expect(patterns[0]?.dialect).toBe('python');
+			expect(patterns[0]?.column).toBe(9);
+		});
+
+		it('should read the flags argument of each function', () => {
+			const text = [
+				"re.sub(r'a+', 'b', text, 0, re.S)",
+				"re.split(r'b+', text, 1, re.X)",
+				"regex.findall(r'c+', text, flags=re.DOTALL)",
+				"re.search(pattern=r'd+', string=text, flags=re.A)",
+			].join('\n');
+			const patterns = extractRegexPatterns(text, 'python');
+
+			expect(patterns.map((p) => [p.pattern, p.flags])).toEqual([
+				['a+', 's'],
+				['b+', 'x'],
+				['c+', 's'],
+				['d+', 'a'],
+			]);
+		});
+
+		it('should join implicitly concatenated and triple-quoted literals', () => {
+			const text = [
+				'DATE = re.compile(',
+				"    r'(?P<year>\\d{4})-'  # year",
+				'    r"(?P<month>\\d{2})"',
+				"    '''\\n?'''",
+				')',
+			].join('\n');
+			const patterns = extractRegexPatterns(text, 'python');
+
+			expect(patterns[0]?.pattern).toBe(
+				'(?P<year>\\d{4})-(?P<month>\\d{2})\n?',
+			);
+		});
+
+		it('should decode escapes in non-raw strings', () => {
+			const patterns = extractRegexPatterns(
+				're.match("\\\\d+\\x41\\w", s)',
+				'python',
+			);
+
+			expect(patterns[0]?.pattern).toBe('\\d+A\\w');
+		});
+
+		it('should skip f-strings and non-literal patterns', () => {
+			const text = [
+				"re.compile(f'{prefix}\\d+')",
+				're.compile(PATTERN)',
+				"# re.compile(r'commented')",
+				"obj.re.compile(r'attribute')",
+			].join('\n');
+
+			expect(extractRegexPatterns(text, 'python')).toHaveLength(0);
+		});
+	});
+
+	describe('Go', () => {
+		it('should extract MustCompile with backtick literals', () => {
+			const patterns = extractRegexPatterns(
+				'var re = regexp.MustCompile(`^\\d+\\s*"(\\w+)"$`)',
+				'go',
+			);
+
+			expect(patterns).toHaveLength(1);
+			expect(patterns[0]?.pattern).toBe('^\\d+\\s*"(\\w+)"$');
+			expect(patterns[0]?.flags).toBe('');
+			expect(patterns[0]?.dialect).toBe('go');
+		});
+
+		it('should decode interpreted string literals', () => {
+			const patterns = extractRegexPatterns(
+				'ok, err := regexp.MatchString("\\\\d+\\x41\\101", input)',
+				'go',
+			);
+
+			expect(patterns[0]?.pattern).toBe('\\d+AA');
+		});
+
+		it('should ignore calls in comments and non-literal patterns', () => {
+			const text = [
+				'// regexp.MustCompile(`commented`)',
+				'/* regexp.Compile("block") */',
+				're := regexp.MustCompile(pattern)',
+				'r := \'"\'; s := "regexp.Compile(`quoted`)"',
+			].join('\n');
+
+			expect(extractRegexPatterns(text, 'go')).toHaveLength(0);
+		});
+	});
+
+	describe('Java', () => {
+		it('should unescape Pattern.compile strings and map flag constants', () => {
+			const patterns = extractRegexPatterns(
+				'Pattern p = Pattern.compile("\\\\d+\\\\.\\\\w", Pattern.CASE_INSENSITIVE | Pattern.MULTILINE);',
+				'java',
+			);
+
+			expect(patterns).toHaveLength(1);
+			expect(patterns[0]?.pattern).toBe('\\d+\\.\\w');
+			expect(patterns[0]?.flags).toBe('im');
+			expect(patterns[0]?.dialect).toBe('java');
+		});
+
+		it('should strip the incidental indentation of text blocks', () => {
+			const text = [
+				'Pattern p = Pattern.compile("""',
+				'        ^(\\\\w+)',
+				'          \\\\s*$',
+				'        """, Pattern.COMMENTS);',
+			].join('\n');
+			const patterns = extractRegexPatterns(text, 'java');
+
+			expect(patterns[0]?.pattern).toBe('^(\\w+)\n  \\s*$\n');
+			expect(patterns[0]?.flags).toBe('x');
+		});
+
+		it('should not read the input of Pattern.matches as flags', () => {
+			const text = [
+				'boolean ok = Pattern.matches("a\\"b", Pattern.DOTALL);',
+				'char c = \'"\'; // Pattern.compile("x")',
+			].join('\n');
+			const patterns = extractRegexPatterns(text, 'java');
+
+			expect(patterns.map((p) => [p.pattern, p.flags])).toEqual([
+				['a"b', ''],
+			]);
+		});
+	});
+
+	describe('Kotlin', () => {
+		it('should extract Regex constructors and toRegex receivers', () => {
+			const text = [
+				'val a = "\\\\d+".toRegex()',
+				'val b = """\\d+\\s""".toRegex(setOf(RegexOption.IGNORE_CASE))',
+				'val c = Regex("a\\$b", RegexOption.DOT_MATCHES_ALL)',
+				'val d = Regex(pattern = "x", option = RegexOption.MULTILINE)',
+			].join('\n');
+			const patterns = extractRegexPatterns(text, 'kotlin');
+
+			expect(patterns.map((p) => [p.pattern, p.flags])).toEqual([
+				['\\d+', ''],
+				['\\d+\\s', 'i'],
+				['a$b', 's'],
+				['x', 'm'],
+			]);
+			expect(patterns[1]?.dialect).toBe('java');
+		});
+
+		it('should skip string templates', () => {
+			const text = [
+				'val a = "$prefix\\\\d+".toRegex()',
+				'val b = Regex("${name}+")',
+			].join('\n');
+
+			expect(extractRegexPatterns(text, 'kotlin')).toHaveLength(0);
+		});
+	});
+
+	describe('C#', () => {
+		it('should extract constructors with verbatim strings and options', () => {
+			const patterns = extractRegexPatterns(
+				'var re = new Regex(@"^\\d+""x""$", RegexOptions.IgnoreCase | RegexOptions.Multiline);',
+				'csharp',
+			);
+
+			expect(patterns).toHaveLength(1);
+			expect(patterns[0]?.pattern).toBe('^\\d+"x"$');
+			expect(patterns[0]?.flags).toBe('im');
+			expect(patterns[0]?.dialect).toBe('dotnet');
+		});
+
+		it('should read the pattern argument of static helpers', () => {
+			const text = [
+				'Regex.IsMatch(input, "\\\\w+");',
+				'Regex.Replace(s, @"\\s+", " ", RegexOptions.Singleline);',
+				'[GeneratedRegex("abc", RegexOptions.ExplicitCapture)]',
+				'var q = new Regex(pattern: """',
+				'    a"b',
+				'    """, options: RegexOptions.IgnorePatternWhitespace);',
+			].join('\n');
+			const patterns = extractRegexPatterns(text, 'csharp');
+
+			expect(patterns.map((p) => [p.pattern, p.flags])).toEqual([
+				['\\w+', ''],
+				['\\s+', 's'],
+				['abc', 'n'],
+				['a"b', 'x'],
+			]);
+		});
+
+		it('should skip interpolated strings', () => {
+			const text = 'var re = new Regex($"{x}+");';
+
+			expect(extractRegexPatterns(text, 'csharp')).toHaveLength(0);
+		});
+	});
+});
+
+describe('groupPatterns', () => {
+	it('should keep every occurrence and group them by pattern', () => {
+		const text = [
+			'const a = /\\d+/g;',
+			'const b = /\\w+/;',
+			'const c = /\\d+/g;',
+			'const d = /\\d+/;',
+		].join('\n');
+		const patterns = extractRegexPatterns(text);
+		const groups = groupPatterns(patterns);
+
+		expect(patterns).toHaveLength(4);
+		expect(groups.map((g) => [g.pattern, g.flags])).toEqual([
+			['\\d+', 'g'],
+			['\\w+', ''],
+			['\\d+', ''],
+		]);
+		expect(groups[0]?.occurrences.map((o) => o.line)).toEqual([1, 3]);
+	});
+
+	it('should format single and multi-line locations', () => {
+		const text = "new RegExp(\n  'a' +\n  'b'\n); /c/;";
+		const patterns = extractRegexPatterns(text);
+
+		expect(patterns.map(formatLocation)).toEqual(['1:1-4:2', '4:4']);
+	});
+});
+
+describe('patternsWithin', () => {
+	const text = [
+		'const a = /a/;',
+		'const b = /b/;',
+		'const c = /c/;',
+	].join('\n');
+	const patterns = extractRegexPatterns(text);
+
+	it('should keep patterns overlapping any selection', () => {
+		const selected = patternsWithin(patterns, [
+			{ line: 1, column: 12, endLine: 1, endColumn: 13 },
+			{ line: 3, column: 1, endLine: 3, endColumn: 15 },
+		]);
+
+		expect(selected.map((p) => p.pattern)).toEqual(['a', 'c']);
+	});
+
+	it('should ignore selections that only touch a pattern', () => {
+		// /b/ spans columns 11-14 of line 2, end exclusive
+		const selected = patternsWithin(patterns, [
+			{ line: 2, column: 1, endLine: 2, endColumn: 11 },
+			{ line: 2, column: 14, endLine: 3, endColumn: 1 },
+		]);
+
+		expect(selected).toEqual([]);
+	});
+});
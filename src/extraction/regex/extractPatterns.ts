@@ -1,107 +1,147 @@
 /**
  * Extract regex patterns from code/text
  * Finds patterns in various formats: /pattern/flags, new RegExp(), etc.
+ * Languages with their own regex APIs (Rust, Python, Go, Java, Kotlin, C#)
+ * use dedicated extractors
  */
 
+import type { RegexDialect } from '../../types';
+import { extractCSharpPatterns } from './csharpPatterns';
+import { extractGoPatterns } from './goPatterns';
+import { extractJavaPatterns, extractKotlinPatterns } from './javaPatterns';
+import { extractJavaScriptPatterns } from './javascriptPatterns';
+import { extractPythonPatterns } from './pythonPatterns';
+import { extractRustPatterns } from './rustPatterns';
+import type { SourceLocation } from './sourceScanner';
+
 export interface ExtractedRegexPattern {
 	readonly pattern: string;
 	readonly flags: string;
 	readonly line: number;
 	readonly column: number;
+	readonly endLine: number; // Where the match ends, for multi-line calls
+	readonly endColumn: number; // Exclusive
 	readonly match: string; // The full match string (e.g., "/pattern/gi" or "new RegExp(...)")
+	readonly dialect: RegexDialect;
+	readonly partial?: boolean | undefined; // Non-constant parts were left out of the pattern
+}
+
+/**
+ * Extractors for languages whose regex APIs differ from JavaScript
+ * Keyed by VS Code language identifier
+ */
+const LANGUAGE_EXTRACTORS: Readonly<
+	Record<string, (text: string) => readonly ExtractedRegexPattern[]>
+> = Object.freeze({
+	rust: extractRustPatterns,
+	python: extractPythonPatterns,
+	go: extractGoPatterns,
+	java: extractJavaPatterns,
+	kotlin: extractKotlinPatterns,
+	csharp: extractCSharpPatterns,
+});
+
+/**
+ * Every occurrence of the same pattern, in source order
+ */
+export interface PatternGroup {
+	readonly pattern: string;
+	readonly flags: string;
+	readonly dialect: RegexDialect;
+	readonly occurrences: readonly ExtractedRegexPattern[];
 }
 
 /**
  * Extract all regex patterns from text content
+ * languageId selects the host language; JavaScript syntax is assumed otherwise.
+ * Repeated patterns are reported once per occurrence, in source order.
  */
 export function extractRegexPatterns(
 	text: string,
+	languageId?: string,
 ): readonly ExtractedRegexPattern[] {
-	const patterns: ExtractedRegexPattern[] = [];
-	const lines = text.split(/\r?\n/);
+	const extractor =
+		(languageId && LANGUAGE_EXTRACTORS[languageId]) ||
+		extractJavaScriptPatterns;
 
-	// Track what we've already found to avoid duplicates
-	const foundPatterns = new Set<string>();
+	return Object.freeze([...extractor(text)]);
+}
 
-	for (let lineIndex = 0; lineIndex < lines.length; lineIndex++) {
-		const line = lines[lineIndex] || '';
+/**
+ * Group occurrences by pattern, flags and dialect
+ * Groups are ordered by their first occurrence.
+ */
+export function groupPatterns(
+	patterns: readonly ExtractedRegexPattern[],
+): readonly PatternGroup[] {
+	const groups = new Map<string, ExtractedRegexPattern[]>();
+	for (const pattern of patterns) {
+		const key = `${pattern.dialect}::${pattern.pattern}::${pattern.flags}`;
+		const occurrences = groups.get(key);
+		if (occurrences) {
+			occurrences.push(pattern);
+		} else {
+			groups.set(key, [pattern]);
+		}
+	}
 
-		// Pattern 1: Literal regex like /pattern/flags or /pattern/
-		// Match forward slashes with escape handling
-		const literalRegexPattern = /\/(?:[^/\r\n\\]|\\.)+\/[gimsuvy]*/g;
-		let match: RegExpExecArray | null;
+	const result: PatternGroup[] = [];
+	for (const occurrences of groups.values()) {
+		const first = occurrences[0];
+		if (!first) continue;
+		result.push(
+			Object.freeze({
+				pattern: first.pattern,
+				flags: first.flags,
+				dialect: first.dialect,
+				occurrences: Object.freeze(occurrences),
+			}),
+		);
+	}
+	return Object.freeze(result);
+}
 
-		// Reset regex for each line
-		literalRegexPattern.lastIndex = 0;
-		while ((match = literalRegexPattern.exec(line)) !== null) {
-			const fullMatch = match[0] || '';
-			const patternMatch = fullMatch.match(/^\/(.+)\/([gimsuvy]*)$/);
-			if (patternMatch) {
-				const pattern = patternMatch[1] || '';
-				const flags = patternMatch[2] || '';
-				const key = `${pattern}::${flags}`;
-				if (!foundPatterns.has(key)) {
-					foundPatterns.add(key);
-					const matchIndex = match.index || 0;
-					patterns.push(
-						Object.freeze({
-							pattern,
-							flags,
-							line: lineIndex + 1,
-							column: matchIndex + 1,
-							match: fullMatch,
-						}),
-					);
-				}
-			}
-		}
+/**
+ * Format where an occurrence is, e.g. "12:5" or "12:5-14:2"
+ */
+export function formatLocation(pattern: ExtractedRegexPattern): string {
+	const start = `${pattern.line}:${pattern.column}`;
+	return pattern.endLine > pattern.line
+		? `${start}-${pattern.endLine}:${pattern.endColumn}`
+		: start;
+}
 
-		// Pattern 2: new RegExp('pattern', 'flags') or new RegExp("pattern", "flags")
-		const regExpConstructorPattern =
-			/new\s+RegExp\s*\(\s*['"]([^'"]+)['"]\s*(?:,\s*['"]([gimsuvy]*)['"])?\)/gi;
-		regExpConstructorPattern.lastIndex = 0;
-		while ((match = regExpConstructorPattern.exec(line)) !== null) {
-			const pattern = match[1] || '';
-			const flags = match[2] || '';
-			const key = `${pattern}::${flags}`;
-			if (!foundPatterns.has(key) && pattern.length > 0) {
-				foundPatterns.add(key);
-				const matchIndex = match.index || 0;
-				patterns.push(
-					Object.freeze({
-						pattern,
-						flags,
-						line: lineIndex + 1,
-						column: matchIndex + 1,
-						match: match[0] || '',
-					}),
-				);
-			}
-		}
+/**
+ * Keep the occurrences that overlap any of the ranges, e.g. selections
+ * Extraction still runs on the whole text so that the surrounding code
+ * decides what is a pattern; this only narrows the result.
+ */
+export function patternsWithin(
+	patterns: readonly ExtractedRegexPattern[],
+	ranges: readonly SourceLocation[],
+): readonly ExtractedRegexPattern[] {
+	return Object.freeze(
+		patterns.filter((pattern) =>
+			ranges.some((range) => overlaps(pattern, range)),
+		),
+	);
+}
 
-		// Pattern 3: RegExp('pattern', 'flags') - without 'new'
-		const regExpCallPattern =
-			/(?:^|[^a-zA-Z])RegExp\s*\(\s*['"]([^'"]+)['"]\s*(?:,\s*['"]([gimsuvy]*)['"])?\)/gi;
-		regExpCallPattern.lastIndex = 0;
-		while ((match = regExpCallPattern.exec(line)) !== null) {
-			const pattern = match[1] || '';
-			const flags = match[2] || '';
-			const key = `${pattern}::${flags}`;
-			if (!foundPatterns.has(key) && pattern.length > 0) {
-				foundPatterns.add(key);
-				const matchIndex = match.index || 0;
-				patterns.push(
-					Object.freeze({
-						pattern,
-						flags,
-						line: lineIndex + 1,
-						column: matchIndex + 1,
-						match: match[0] || '',
-					}),
-				);
-			}
-		}
-	}
+/**
+ * Whether two ranges share at least one character (ends are exclusive)
+ */
+function overlaps(a: SourceLocation, b: SourceLocation): boolean {
+	return (
+		isBefore(a.line, a.column, b.endLine, b.endColumn) &&
+		isBefore(b.line, b.column, a.endLine, a.endColumn)
+	);
+}
 
-	return Object.freeze(patterns);
+function isBefore(
+	line: number,
+	column: number,
+	otherLine: number,
+	otherColumn: number,
+): boolean {
+	return line < otherLine || (line === otherLine && column < otherColumn);
 }
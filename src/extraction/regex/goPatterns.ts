@@ -0,0 +1,69 @@
+/**
+ * Extract regex call sites from Go source
+ * Understands the regexp package entry points with raw (`...`) and
+ * interpreted ("...") string literals; Go has no flags argument, so
+ * options only ever appear as inline (?i) groups inside the pattern
+ */
+
+import type { ExtractedRegexPattern } from './extractPatterns';
+import { createLineIndex } from './lineIndex';
+import {
+	findClosingBracket,
+	locationAt,
+	maskNonCode,
+	readLiteralArgument,
+	type SourceSyntax,
+	splitArguments,
+} from './sourceScanner';
+import { readGoString } from './stringLiterals';
+
+const GO_SYNTAX: SourceSyntax = Object.freeze({
+	lineComments: Object.freeze(['//']),
+	blockComment: Object.freeze(['/*', '*/'] as const),
+	readString: readGoString,
+});
+
+/**
+ * Extract all regex patterns from Go source text
+ */
+export function extractGoPatterns(
+	text: string,
+): readonly ExtractedRegexPattern[] {
+	const masked = maskNonCode(text, GO_SYNTAX);
+	const lines = createLineIndex(text);
+	const patterns: ExtractedRegexPattern[] = [];
+
+	// regexp.MustCompile(...), regexp.MatchString(...), ...
+	const callSite =
+		/\bregexp\s*\.\s*(?:MustCompile|Compile|MatchString|MatchReader|Match)\s*\(/g;
+	for (const site of masked.matchAll(callSite)) {
+		const siteOffset = site.index ?? 0;
+		const openParen = siteOffset + site[0].length - 1;
+		const closeParen = findClosingBracket(masked, openParen);
+		if (closeParen === -1) {
+			continue;
+		}
+
+		// The pattern is always the first argument
+		const argument = splitArguments(masked, openParen, closeParen)[0];
+		if (!argument) {
+			continue;
+		}
+		const literal = readLiteralArgument(text, argument, GO_SYNTAX);
+		if (!literal) {
+			continue;
+		}
+
+		patterns.push(
+			Object.freeze({
+				pattern: literal.value,
+				flags: '',
+				...locationAt(lines, siteOffset, closeParen + 1),
+				match: text.slice(siteOffset, closeParen + 1),
+				dialect: 'go' as const,
+			}),
+		);
+	}
+
+	return Object.freeze(patterns);
+}
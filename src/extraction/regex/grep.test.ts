@@ -0,0 +1,85 @@
+import { describe, expect, it } from 'vitest';
+import { formatGrepOutput, grepLines, lineRanges, linesWithin } from './grep';
+
+const LINES = ['a1', 'b2', 'a3', 'b4', 'b5', 'b6', 'b7', 'a8'];
+
+function grep(
+	matched: readonly number[],
+	invert: boolean,
+	context: number,
+	maxLines = 100,
+): string {
+	return formatGrepOutput(
+		grepLines(LINES, new Set(matched), { invert, context, maxLines }),
+	);
+}
+
+describe('lineRanges', () => {
+	it('should leave out line breaks, including CRLF', () => {
+		const text = 'ab\r\n\ncd\n';
+
+		expect(lineRanges(text)).toEqual([
+			{ start: 0, end: 2 },
+			{ start: 4, end: 4 },
+			{ start: 5, end: 7 },
+		]);
+		expect(lineRanges('')).toEqual([]);
+	});
+});
+
+describe('linesWithin', () => {
+	it('should not take in the line a range ends at the start of', () => {
+		const lines = lineRanges('ab\n\ncd\nef');
+
+		expect(linesWithin(lines, [{ start: 1, end: 5 }])).toEqual([1, 2, 3]);
+		expect(linesWithin(lines, [{ start: 0, end: 4 }])).toEqual([1, 2]);
+	});
+});
+
+describe('grepLines', () => {
+	it('should list matching lines like grep -n', () => {
+		expect(grep([1, 3, 8], false, 0)).toBe('1:a1\n--\n3:a3\n--\n8:a8');
+	});
+
+	it('should merge context that touches and separate the rest', () => {
+		expect(grep([1, 3, 8], false, 1)).toBe(
+			['1:a1', '2-b2', '3:a3', '4-b4', '--', '7-b7', '8:a8'].join('\n'),
+		);
+	});
+
+	it('should select the lines that did not match when inverted', () => {
+		const result = grepLines(LINES, new Set([1, 3, 8]), {
+			invert: true,
+			context: 0,
+			maxLines: 100,
+		});
+
+		expect(result.selectedCount).toBe(5);
+		expect(formatGrepOutput(result)).toBe(
+			['2:b2', '--', '4:b4', '5:b5', '6:b6', '7:b7'].join('\n'),
+		);
+	});
+
+	it('should stop at maxLines but count every selected line', () => {
+		const result = grepLines(LINES, new Set([1, 3, 8]), {
+			invert: false,
+			context: 0,
+			maxLines: 2,
+		});
+
+		expect(result.truncated).toBe(true);
+		expect(result.selectedCount).toBe(3);
+		expect(formatGrepOutput(result)).toBe('1:a1\n--\n3:a3');
+	});
+
+	it('should only consider searched lines', () => {
+		const result = grepLines(
+			LINES,
+			new Set([3]),
+			{ invert: true, context: 0, maxLines: 100 },
+			new Set([2, 3, 4]),
+		);
+
+		expect(formatGrepOutput(result)).toBe('2:b2\n--\n4:b4');
+	});
+});
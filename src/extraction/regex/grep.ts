@@ -0,0 +1,142 @@
+/**
+ * Grep-style line results
+ * Line mode matches a pattern against each line on its own (as ranges of
+ * the whole text, so the test still runs in the worker). These helpers split
+ * text into lines and turn the lines that matched into grep-like blocks
+ * with context, optionally inverted like grep -v.
+ */
+
+import type { TextRange } from './regexTest';
+
+export interface GrepOptions {
+	readonly invert: boolean;
+	readonly context: number; // Lines shown before and after each selected line
+	readonly maxLines: number; // Selected lines to report
+}
+
+export interface GrepLine {
+	readonly line: number; // 1-based
+	readonly text: string;
+	readonly selected: boolean; // Matched, or did not match when inverted
+}
+
+export interface GrepResult {
+	readonly blocks: readonly (readonly GrepLine[])[];
+	readonly selectedCount: number; // Including lines past maxLines
+	readonly truncated: boolean;
+}
+
+/**
+ * The range of every line, without its line break
+ * A line break at the very end does not start another line, as in grep.
+ */
+export function lineRanges(text: string): readonly TextRange[] {
+	const ranges: TextRange[] = [];
+	let start = 0;
+	while (start < text.length) {
+		const newline = text.indexOf('\n', start);
+		const end = newline === -1 ? text.length : newline;
+		ranges.push(
+			Object.freeze({
+				start,
+				end: end > start && text[end - 1] === '\r' ? end - 1 : end,
+			}),
+		);
+		if (newline === -1) {
+			break;
+		}
+		start = newline + 1;
+	}
+	return Object.freeze(ranges);
+}
+
+/**
+ * Numbers (1-based) of the lines that overlap any of the ranges
+ * A range ending at the start of a line, as when whole lines are selected,
+ * does not take in that line.
+ */
+export function linesWithin(
+	lines: readonly TextRange[],
+	ranges: readonly TextRange[],
+): readonly number[] {
+	const numbers: number[] = [];
+	lines.forEach((line, index) => {
+		const overlaps = ranges.some((range) =>
+			line.start === line.end
+				? range.start <= line.start && line.start < range.end
+				: line.start < range.end && range.start < line.end,
+		);
+		if (overlaps) {
+			numbers.push(index + 1);
+		}
+	});
+	return Object.freeze(numbers);
+}
+
+/**
+ * Select lines like grep and gather them into blocks with their context
+ * Blocks whose context touches or overlaps are merged; grep separates the
+ * rest with "--". searched limits the lines considered (all when omitted).
+ */
+export function grepLines(
+	lines: readonly string[],
+	matched: ReadonlySet<number>,
+	options: GrepOptions,
+	searched?: ReadonlySet<number>,
+): GrepResult {
+	const selected: number[] = [];
+	for (let line = 1; line <= lines.length; line++) {
+		if (searched && !searched.has(line)) {
+			continue;
+		}
+		if (matched.has(line) !== options.invert) {
+			selected.push(line);
+		}
+	}
+
+	const isSelected = new Set(selected);
+	const blocks: GrepLine[][] = [];
+	let block: GrepLine[] = [];
+	let lastShown = 0;
+	for (const line of selected.slice(0, options.maxLines)) {
+		const from = Math.max(line - options.context, lastShown + 1, 1);
+		const to = Math.min(line + options.context, lines.length);
+		if (block.length > 0 && from > lastShown + 1) {
+			blocks.push(block);
+			block = [];
+		}
+		for (let shown = from; shown <= to; shown++) {
+			block.push(
+				Object.freeze({
+					line: shown,
+					text: lines[shown - 1] ?? '',
+					selected: isSelected.has(shown),
+				}),
+			);
+		}
+		lastShown = Math.max(lastShown, to);
+	}
+	if (block.length > 0) {
+		blocks.push(block);
+	}
+
+	return Object.freeze({
+		blocks: Object.freeze(blocks.map((entries) => Object.freeze(entries))),
+		selectedCount: selected.length,
+		truncated: selected.length > options.maxLines,
+	});
+}
+
+/**
+ * Render blocks as grep -n does: "12:text" for selected lines, "13-text"
+ * for context and "--" between blocks
+ */
+export function formatGrepOutput(result: GrepResult): string {
+	return result.blocks
+		.map((block) =>
+			block
+				.map((line) => `${line.line}${line.selected ? ':' : '-'}${line.text}`)
+				.join('\n'),
+		)
+		.join('\n--\n');
+}
@@ -0,0 +1,157 @@
+import { describe, expect, it } from 'vitest';
+import {
+	assessPattern,
+	compareFileRisk,
+	countRisks,
+	fileRisk,
+	formatInventoryReport,
+	inventoryFile,
+	languageIdForPath,
+} from './inventory';
+
+const OPTIONS = { redosDetectionEnabled: true };
+
+function firstEntry(source: string, redosDetectionEnabled = true) {
+	const inventory = inventoryFile('a.js', source, 'javascript', {
+		redosDetectionEnabled,
+	});
+	return inventory.entries[0];
+}
+
+describe('languageIdForPath', () => {
+	it('should map source extensions to language identifiers', () => {
+		expect(languageIdForPath('src/lib.rs')).toBe('rust');
+		expect(languageIdForPath('app/Main.KT')).toBe('kotlin');
+		expect(languageIdForPath('web/view.tsx')).toBe('typescriptreact');
+		expect(languageIdForPath('Program.cs')).toBe('csharp');
+	});
+
+	it('should fall back to JavaScript for unknown extensions', () => {
+		expect(languageIdForPath('README')).toBe('javascript');
+		expect(languageIdForPath('dir.d/file.vue')).toBe('javascript');
+	});
+});
+
+describe('assessPattern', () => {
+	it('should rate clean patterns as no risk', () => {
+		const entry = firstEntry('const re = /\\d+/g;');
+
+		expect(entry?.risk).toBe('none');
+		expect(entry?.redosSeverity).toBeUndefined();
+		expect(entry?.reasons).toEqual([]);
+	});
+
+	it('should not rate Unicode-aware classes of other dialects as risky', () => {
+		const inventory = inventoryFile(
+			'lib.rs',
+			'let re = Regex::new(r"\\w+\\s\\d").unwrap();',
+			'rust',
+			OPTIONS,
+		);
+
+		expect(inventory.entries[0]?.risk).toBe('none');
+		expect(inventory.entries[0]?.reasons).toEqual([]);
+	});
+
+	it('should rate invalid patterns as high risk', () => {
+		const inventory = inventoryFile(
+			'lib.rs',
+			'let re = Regex::new(r"(?<=a)b").unwrap();',
+			'rust',
+			OPTIONS,
+		);
+		const entry = inventory.entries[0];
+
+		expect(entry?.risk).toBe('high');
+		expect(entry?.valid).toBe(false);
+		expect(entry?.reasons[0]).toMatch(/^Invalid: /);
+	});
+
+	it('should rate exponential backtracking as high risk', () => {
+		const entry = firstEntry('const re = /(a+)+$/;');
+
+		expect(entry?.risk).toBe('high');
+		expect(entry?.valid).toBe(true);
+		expect(entry?.redosSeverity).toBe('high');
+		expect(entry?.reasons[0]).toMatch(/^ReDoS \(high\)/);
+	});
+
+	it('should skip ReDoS checks when disabled', () => {
+		const entry = firstEntry('const re = /(a+)+$/;', false);
+
+		expect(entry?.risk).toBe('none');
+	});
+
+	it('should rate partially resolved patterns as low risk', () => {
+		const entry = firstEntry('const re = new RegExp(prefix + "-\\\\d+");');
+
+		expect(entry?.risk).toBe('low');
+		expect(entry && assessPattern(entry.pattern, OPTIONS)).toEqual(entry);
+	});
+});
+
+describe('formatInventoryReport', () => {
+	const files = [
+		inventoryFile('src/clean.js', 'const a = /\\d+/;', 'javascript', OPTIONS),
+		inventoryFile(
+			'src/risky.js',
+			'const a = /\\w+/;\nconst b = /(a+)+$/;',
+			'javascript',
+			OPTIONS,
+		),
+		inventoryFile('src/empty.js', 'const a = 1;', 'javascript', OPTIONS),
+	];
+
+	it('should count occurrences per risk level', () => {
+		expect(countRisks(files)).toEqual({ high: 1, medium: 0, low: 0, none: 2 });
+		expect(files.map(fileRisk)).toEqual(['none', 'high', 'none']);
+	});
+
+	it('should order files by risk, then by path', () => {
+		const sorted = [...files].sort(compareFileRisk);
+
+		expect(sorted.map((file) => file.file)).toEqual([
+			'src/risky.js',
+			'src/clean.js',
+			'src/empty.js',
+		]);
+	});
+
+	it('should list the riskiest files first, grouped by risk level', () => {
+		const report = formatInventoryReport(files, {
+			filesScanned: 3,
+			filesSkipped: 1,
+			cancelled: false,
+		});
+
+		expect(report).toContain(
+			'Scanned 3 file(s): 3 pattern occurrence(s) in 2 file(s)',
+		);
+		expect(report).toContain('Skipped 1 file(s)');
+		expect(report).toContain('| 🔴 High | 1 |');
+		expect(report).not.toContain('src/empty.js');
+		expect(report.indexOf('## src/risky.js')).toBeLessThan(
+			report.indexOf('## src/clean.js'),
+		);
+		expect(report).toContain('### 🔴 High\n\n- Line 2:11: `/(a+)+$/`');
+	});
+
+	it('should flag cancelled scans as incomplete', () => {
+		const report = formatInventoryReport(files, {
+			filesScanned: 1,
+			filesSkipped: 0,
+			cancelled: true,
+		});
+
+		expect(report).toContain('Scan cancelled');
+	});
+
+	it('should fence patterns that contain backticks', () => {
+		const report = formatInventoryReport(
+			[inventoryFile('a.js', 'const a = /`+/;', 'javascript', OPTIONS)],
+			{ filesScanned: 1, filesSkipped: 0, cancelled: false },
+		);
+
+		expect(report).toContain('- Line 1:11: ``/`+/``');
+	});
+});
@@ -0,0 +1,268 @@
+/**
+ * Regex inventory across many files
+ * Each extracted occurrence is validated against its dialect, checked for
+ * ReDoS and given a risk level, so a workspace scan can be reported per
+ * file with the riskiest patterns first
+ */
+
+import { checkDialectSyntax, toJavaScriptPattern } from './dialects';
+import {
+	type ExtractedRegexPattern,
+	extractRegexPatterns,
+	formatLocation,
+} from './extractPatterns';
+import { inlineCode } from './outputFormats';
+import { detectReDoS, type ReDoSResult } from './redos';
+
+export type RiskLevel = 'high' | 'medium' | 'low' | 'none';
+
+/**
+ * Risk levels from most to least severe
+ */
+export const RISK_LEVELS: readonly RiskLevel[] = Object.freeze([
+	'high',
+	'medium',
+	'low',
+	'none',
+]);
+
+export interface InventoryEntry {
+	readonly pattern: ExtractedRegexPattern;
+	readonly valid: boolean; // Accepted by the engine of its dialect
+	readonly redosSeverity?: ReDoSResult['severity'] | undefined; // Set when ReDoS was detected
+	readonly risk: RiskLevel;
+	readonly reasons: readonly string[]; // Why the risk is above none
+}
+
+export interface FileInventory {
+	readonly file: string; // Workspace-relative path
+	readonly entries: readonly InventoryEntry[];
+}
+
+export interface InventoryOptions {
+	readonly redosDetectionEnabled: boolean;
+}
+
+export interface ScanSummary {
+	readonly filesScanned: number;
+	readonly filesSkipped: number; // Over the size limit or unreadable
+	readonly cancelled: boolean;
+}
+
+/**
+ * VS Code language identifiers for the file extensions the scan reads
+ */
+const LANGUAGE_BY_EXTENSION: Readonly<Record<string, string>> = Object.freeze({
+	js: 'javascript',
+	mjs: 'javascript',
+	cjs: 'javascript',
+	jsx: 'javascriptreact',
+	ts: 'typescript',
+	mts: 'typescript',
+	cts: 'typescript',
+	tsx: 'typescriptreact',
+	rs: 'rust',
+	py: 'python',
+	go: 'go',
+	java: 'java',
+	kt: 'kotlin',
+	kts: 'kotlin',
+	cs: 'csharp',
+});
+
+const RISK_HEADINGS: Readonly<Record<RiskLevel, string>> = Object.freeze({
+	high: '🔴 High',
+	medium: '🟠 Medium',
+	low: '🟡 Low',
+	none: '🟢 None',
+});
+
+/**
+ * Language identifier for a file path, from its extension
+ * JavaScript syntax is assumed for unknown extensions.
+ */
+export function languageIdForPath(path: string): string {
+	const extension = /\.([^./\\]+)$/.exec(path)?.[1]?.toLowerCase() ?? '';
+	return LANGUAGE_BY_EXTENSION[extension] ?? 'javascript';
+}
+
+/**
+ * Extract and assess every pattern in one file
+ */
+export function inventoryFile(
+	file: string,
+	text: string,
+	languageId: string,
+	options: InventoryOptions,
+): FileInventory {
+	const entries = extractRegexPatterns(text, languageId).map((pattern) =>
+		assessPattern(pattern, options),
+	);
+	return Object.freeze({ file, entries: Object.freeze(entries) });
+}
+
+/**
+ * Rate one occurrence
+ * Invalid syntax and exponential backtracking are high risk, other ReDoS
+ * findings follow their severity, and dialect warnings or partially
+ * resolved patterns are low risk.
+ */
+export function assessPattern(
+	pattern: ExtractedRegexPattern,
+	options: InventoryOptions,
+): InventoryEntry {
+	const reasons: string[] = [];
+	let risk: RiskLevel = 'none';
+	let redosSeverity: ReDoSResult['severity'] | undefined;
+	const raise = (level: RiskLevel, reason: string): void => {
+		reasons.push(reason);
+		if (RISK_LEVELS.indexOf(level) < RISK_LEVELS.indexOf(risk)) {
+			risk = level;
+		}
+	};
+
+	const syntaxCheck = checkDialectSyntax(
+		pattern.pattern,
+		pattern.flags,
+		pattern.dialect,
+	);
+	if (syntaxCheck.error) {
+		raise('high', `Invalid: ${syntaxCheck.error}`);
+	}
+	for (const diagnostic of syntaxCheck.diagnostics) {
+		if (diagnostic.severity === 'warning') {
+			raise('low', diagnostic.message);
+		}
+	}
+
+	// Linear-time engines cannot backtrack catastrophically
+	if (
+		options.redosDetectionEnabled &&
+		syntaxCheck.valid &&
+		!syntaxCheck.linearTime
+	) {
+		const translation = toJavaScriptPattern(
+			pattern.pattern,
+			pattern.flags,
+			pattern.dialect,
+		);
+		const redos = detectReDoS(translation.pattern, translation.flags);
+		if (redos.detected) {
+			redosSeverity = redos.severity;
+			raise(redos.severity, `ReDoS (${redos.severity}): ${redos.reason}`);
+		}
+	}
+
+	if (pattern.partial) {
+		raise('low', 'Partially resolved: non-constant parts were left out');
+	}
+
+	return Object.freeze({
+		pattern,
+		valid: syntaxCheck.valid,
+		...(redosSeverity ? { redosSeverity } : {}),
+		risk,
+		reasons: Object.freeze(reasons),
+	});
+}
+
+/**
+ * Count occurrences at each risk level
+ */
+export function countRisks(
+	files: readonly FileInventory[],
+): Readonly<Record<RiskLevel, number>> {
+	const counts: Record<RiskLevel, number> = {
+		high: 0,
+		medium: 0,
+		low: 0,
+		none: 0,
+	};
+	for (const file of files) {
+		for (const entry of file.entries) {
+			counts[entry.risk]++;
+		}
+	}
+	return Object.freeze(counts);
+}
+
+/**
+ * Highest risk level among a file's patterns
+ */
+export function fileRisk(file: FileInventory): RiskLevel {
+	return (
+		RISK_LEVELS.find((level) =>
+			file.entries.some((entry) => entry.risk === level),
+		) ?? 'none'
+	);
+}
+
+/**
+ * Order files by their most severe risk level, then by path
+ */
+export function compareFileRisk(a: FileInventory, b: FileInventory): number {
+	return (
+		RISK_LEVELS.indexOf(fileRisk(a)) - RISK_LEVELS.indexOf(fileRisk(b)) ||
+		a.file.localeCompare(b.file)
+	);
+}
+
+/**
+ * Build a Markdown report grouped by file and then by risk level
+ * Files with the riskiest patterns come first; files without patterns are
+ * left out.
+ */
+export function formatInventoryReport(
+	files: readonly FileInventory[],
+	summary: ScanSummary,
+): string {
+	const withPatterns = files
+		.filter((file) => file.entries.length > 0)
+		.sort(compareFileRisk);
+	const counts = countRisks(withPatterns);
+	const total = withPatterns.reduce(
+		(sum, file) => sum + file.entries.length,
+		0,
+	);
+
+	const lines: string[] = ['# Regex Workspace Scan', ''];
+	if (summary.cancelled) {
+		lines.push(
+			'**⚠️ Scan cancelled:** the results below are incomplete',
+			'',
+		);
+	}
+	lines.push(
+		`Scanned ${summary.filesScanned} file(s): ${total} pattern occurrence(s) in ${withPatterns.length} file(s)`,
+	);
+	if (summary.filesSkipped > 0) {
+		lines.push(
+			`Skipped ${summary.filesSkipped} file(s) over the size limit or unreadable`,
+		);
+	}
+	lines.push('', '| Risk | Occurrences |', '| --- | --- |');
+	for (const level of RISK_LEVELS) {
+		lines.push(`| ${RISK_HEADINGS[level]} | ${counts[level]} |`);
+	}
+
+	for (const file of withPatterns) {
+		lines.push('', `## ${file.file}`);
+		for (const level of RISK_LEVELS) {
+			const entries = file.entries.filter((entry) => entry.risk === level);
+			if (entries.length === 0) continue;
+			lines.push('', `### ${RISK_HEADINGS[level]}`, '');
+			for (const entry of entries) {
+				const p = entry.pattern;
+				const dialect = p.dialect === 'javascript' ? '' : ` (${p.dialect})`;
+				lines.push(
+					`- Line ${formatLocation(p)}: ${inlineCode(`/${p.pattern}/${p.flags}`)}${dialect}`,
+				);
+				for (const reason of entry.reasons) {
+					lines.push(`  - ${reason}`);
+				}
+			}
+		}
+	}
+
+	return lines.join('\n');
+}
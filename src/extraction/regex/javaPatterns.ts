@@ -0,0 +1,244 @@
+/**
+ * Extract regex construction sites from Java and Kotlin source
+ * Both run on java.util.regex, so patterns share the java dialect; string
+ * literals are unescaped with each language's own rules (the doubled
+ * backslashes in "\\d+" become the \d the engine sees)
+ */
+
+import type { ExtractedRegexPattern } from './extractPatterns';
+import { createLineIndex, type LineIndex } from './lineIndex';
+import {
+	findClosingBracket,
+	findStringLiterals,
+	locationAt,
+	mapFlagNames,
+	maskNonCode,
+	readCallArguments,
+	readLiteralArgument,
+	type SourceRange,
+	type SourceSyntax,
+	skipTrivia,
+} from './sourceScanner';
+import { readJavaString, readKotlinString } from './stringLiterals';
+
+const JAVA_SYNTAX: SourceSyntax = Object.freeze({
+	lineComments: Object.freeze(['//']),
+	blockComment: Object.freeze(['/*', '*/'] as const),
+	readString: readJavaString,
+});
+
+const KOTLIN_SYNTAX: SourceSyntax = Object.freeze({
+	lineComments: Object.freeze(['//']),
+	blockComment: Object.freeze(['/*', '*/'] as const),
+	nestedBlockComments: true,
+	readString: readKotlinString,
+});
+
+/**
+ * java.util.regex.Pattern flag constants and their inline flag equivalents
+ */
+const PATTERN_FLAGS: Readonly<Record<string, string>> = Object.freeze({
+	CASE_INSENSITIVE: 'i',
+	MULTILINE: 'm',
+	DOTALL: 's',
+	COMMENTS: 'x',
+	UNICODE_CASE: 'u',
+	UNICODE_CHARACTER_CLASS: 'U',
+	UNIX_LINES: 'd',
+});
+
+/**
+ * kotlin.text.RegexOption members and their inline flag equivalents
+ */
+const REGEX_OPTIONS: Readonly<Record<string, string>> = Object.freeze({
+	IGNORE_CASE: 'i',
+	MULTILINE: 'm',
+	DOT_MATCHES_ALL: 's',
+	COMMENTS: 'x',
+	UNIX_LINES: 'd',
+});
+
+/**
+ * Kotlin named arguments, e.g. option = RegexOption.IGNORE_CASE
+ */
+const NAMED_ARGUMENT = /^\s*(\w+)\s*=(?!=)/;
+
+interface LocatedPattern {
+	readonly offset: number;
+	readonly pattern: ExtractedRegexPattern;
+}
+
+/**
+ * Extract all regex patterns from Java source text
+ */
+export function extractJavaPatterns(
+	text: string,
+): readonly ExtractedRegexPattern[] {
+	const masked = maskNonCode(text, JAVA_SYNTAX);
+	const lines = createLineIndex(text);
+	return Object.freeze(
+		findPatternCalls(text, lines, masked, JAVA_SYNTAX).map(
+			(entry) => entry.pattern,
+		),
+	);
+}
+
+/**
+ * Extract all regex patterns from Kotlin source text
+ */
+export function extractKotlinPatterns(
+	text: string,
+): readonly ExtractedRegexPattern[] {
+	const masked = maskNonCode(text, KOTLIN_SYNTAX);
+	const lines = createLineIndex(text);
+	const located = [...findPatternCalls(text, lines, masked, KOTLIN_SYNTAX)];
+
+	// Regex("..."), Regex("...", RegexOption.IGNORE_CASE), Regex(pattern = ...)
+	const constructorSite = /(?<![\w.])Regex\s*\(/g;
+	for (const site of masked.matchAll(constructorSite)) {
+		const siteOffset = site.index ?? 0;
+		const openParen = siteOffset + site[0].length - 1;
+		const closeParen = findClosingBracket(masked, openParen);
+		if (closeParen === -1) {
+			continue;
+		}
+
+		const args = readCallArguments(
+			masked,
+			openParen,
+			closeParen,
+			NAMED_ARGUMENT,
+		);
+		const patternArgument = args.named.get('pattern') ?? args.positional[0];
+		const optionArgument =
+			args.named.get('option') ??
+			args.named.get('options') ??
+			args.positional[1];
+		const literal =
+			patternArgument &&
+			readLiteralArgument(text, patternArgument, KOTLIN_SYNTAX);
+		if (!literal) {
+			continue;
+		}
+
+		located.push({
+			offset: siteOffset,
+			pattern: createPattern(
+				lines,
+				siteOffset,
+				literal.value,
+				readFlags(text, optionArgument, REGEX_OPTIONS),
+				text.slice(siteOffset, closeParen + 1),
+			),
+		});
+	}
+
+	// "...".toRegex() and """...""".toRegex(setOf(RegexOption.MULTILINE))
+	const receivers = new Map(
+		findStringLiterals(text, KOTLIN_SYNTAX).map((literal) => [
+			skipTrivia(text, literal.end, KOTLIN_SYNTAX),
+			literal,
+		]),
+	);
+	for (const site of masked.matchAll(/\.\s*toRegex\s*\(/g)) {
+		const siteOffset = site.index ?? 0;
+		const literal = receivers.get(siteOffset);
+		const openParen = siteOffset + site[0].length - 1;
+		const closeParen = findClosingBracket(masked, openParen);
+		if (
+			!literal ||
+			literal.character ||
+			literal.interpolated ||
+			closeParen === -1
+		) {
+			continue;
+		}
+
+		const args = readCallArguments(masked, openParen, closeParen);
+		located.push({
+			offset: literal.start,
+			pattern: createPattern(
+				lines,
+				literal.start,
+				literal.value,
+				readFlags(text, args.positional[0], REGEX_OPTIONS),
+				text.slice(literal.start, closeParen + 1),
+			),
+		});
+	}
+
+	located.sort((a, b) => a.offset - b.offset);
+	return Object.freeze(located.map((entry) => entry.pattern));
+}
+
+/**
+ * Find Pattern.compile(regex, flags) and Pattern.matches(regex, input)
+ */
+function findPatternCalls(
+	text: string,
+	lines: LineIndex,
+	masked: string,
+	syntax: SourceSyntax,
+): readonly LocatedPattern[] {
+	const located: LocatedPattern[] = [];
+
+	const callSite = /\bPattern\s*\.\s*(compile|matches)\s*\(/g;
+	for (const site of masked.matchAll(callSite)) {
+		const siteOffset = site.index ?? 0;
+		const openParen = siteOffset + site[0].length - 1;
+		const closeParen = findClosingBracket(masked, openParen);
+		if (closeParen === -1) {
+			continue;
+		}
+
+		const args = readCallArguments(masked, openParen, closeParen);
+		const patternArgument = args.positional[0];
+		const literal =
+			patternArgument && readLiteralArgument(text, patternArgument, syntax);
+		if (!literal) {
+			continue;
+		}
+
+		// Only compile() takes flags; the second argument of matches() is input
+		const flagsArgument =
+			site[1] === 'compile' ? args.positional[1] : undefined;
+		located.push({
+			offset: siteOffset,
+			pattern: createPattern(
+				lines,
+				siteOffset,
+				literal.value,
+				readFlags(text, flagsArgument, PATTERN_FLAGS),
+				text.slice(siteOffset, closeParen + 1),
+			),
+		});
+	}
+
+	return located;
+}
+
+function readFlags(
+	text: string,
+	argument: SourceRange | undefined,
+	names: Readonly<Record<string, string>>,
+): string {
+	return argument
+		? mapFlagNames(text.slice(argument.start, argument.end), names)
+		: '';
+}
+
+function createPattern(
+	lines: LineIndex,
+	offset: number,
+	pattern: string,
+	flags: string,
+	match: string,
+): ExtractedRegexPattern {
+	return Object.freeze({
+		pattern,
+		flags,
+		...locationAt(lines, offset, offset + match.length),
+		match,
+		dialect: 'java' as const,
+	});
+}
@@ -0,0 +1,356 @@
+/**
+ * Minimal JavaScript/TypeScript lexer
+ * Splits source into comments, strings, template chunks, regex literals,
+ * identifiers, numbers and punctuators. A / starts a regex literal only
+ * where an expression is expected, so division, URLs in strings and
+ * comments are never mistaken for patterns.
+ */
+
+export type JavaScriptTokenKind =
+	| 'comment'
+	| 'string'
+	| 'template' // `...`, `...${, }...${ or }...`
+	| 'regex'
+	| 'identifier'
+	| 'number'
+	| 'punctuator';
+
+export interface JavaScriptToken {
+	readonly kind: JavaScriptTokenKind;
+	readonly start: number;
+	readonly end: number; // Exclusive
+	readonly text: string;
+}
+
+/**
+ * Keywords after which an expression (and so a regex literal) may follow
+ */
+const EXPRESSION_KEYWORDS: ReadonlySet<string> = new Set([
+	'await',
+	'case',
+	'delete',
+	'do',
+	'else',
+	'extends',
+	'in',
+	'instanceof',
+	'new',
+	'of',
+	'return',
+	'throw',
+	'typeof',
+	'void',
+	'yield',
+]);
+
+/**
+ * Keywords whose parenthesised head is followed by a statement, so a /
+ * after the closing paren starts a regex: if (x) /re/.test(y)
+ */
+const STATEMENT_HEAD_KEYWORDS: ReadonlySet<string> = new Set([
+	'for',
+	'if',
+	'while',
+	'with',
+]);
+
+/**
+ * Keywords that start a block rather than an object literal
+ */
+const BLOCK_KEYWORDS: ReadonlySet<string> = new Set([
+	'do',
+	'else',
+	'finally',
+	'try',
+]);
+
+const PUNCTUATORS: readonly string[] = Object.freeze([
+	'>>>=',
+	'...',
+	'===',
+	'!==',
+	'**=',
+	'<<=',
+	'>>=',
+	'>>>',
+	'&&=',
+	'||=',
+	'??=',
+	'=>',
+	'==',
+	'!=',
+	'<=',
+	'>=',
+	'&&',
+	'||',
+	'??',
+	'?.',
+	'++',
+	'--',
+	'+=',
+	'-=',
+	'*=',
+	'/=',
+	'%=',
+	'&=',
+	'|=',
+	'^=',
+	'**',
+	'<<',
+	'>>',
+]);
+
+const NUMBER =
+	/^(?:0[xXbBoO][\da-fA-F_]+n?|(?:\d[\d_]*(?:\.[\d_]*)?|\.\d[\d_]*)(?:[eE][+-]?\d+)?n?)/;
+
+type BraceKind = 'block' | 'object' | 'template';
+
+interface TemplateChunk {
+	readonly end: number;
+	readonly substitution: boolean; // Ends with ${ rather than a backtick
+}
+
+/**
+ * Tokenize JavaScript or TypeScript source; whitespace is skipped
+ * Unterminated strings end at the line break and unterminated comments,
+ * templates and regex literals at the end of the text, so scanning always
+ * recovers from malformed input.
+ */
+export function tokenizeJavaScript(text: string): readonly JavaScriptToken[] {
+	const tokens: JavaScriptToken[] = [];
+	const braces: BraceKind[] = [];
+	const parens: boolean[] = []; // Whether a / after the ) starts a regex
+	let previous: JavaScriptToken | undefined;
+	let beforePrevious: JavaScriptToken | undefined;
+	let regexAllowed = true;
+	let i = 0;
+
+	const push = (kind: JavaScriptTokenKind, end: number): JavaScriptToken => {
+		const token = Object.freeze({
+			kind,
+			start: i,
+			end,
+			text: text.slice(i, end),
+		});
+		tokens.push(token);
+		if (kind !== 'comment') {
+			beforePrevious = previous;
+			previous = token;
+		}
+		i = end;
+		return token;
+	};
+
+	// #! line at the very start of a script
+	if (text.startsWith('#!')) {
+		push('comment', lineEnd(text, 0));
+	}
+
+	while (i < text.length) {
+		const char = text[i] || '';
+		const next = text[i + 1] || '';
+
+		if (/\s/.test(char)) {
+			i++;
+			continue;
+		}
+
+		if (char === '/' && next === '/') {
+			push('comment', lineEnd(text, i));
+			continue;
+		}
+		if (char === '/' && next === '*') {
+			const close = text.indexOf('*/', i + 2);
+			push('comment', close === -1 ? text.length : close + 2);
+			continue;
+		}
+
+		if (char === "'" || char === '"') {
+			push('string', readStringEnd(text, i));
+			regexAllowed = false;
+			continue;
+		}
+
+		const closesSubstitution =
+			char === '}' && braces[braces.length - 1] === 'template';
+		if (char === '`' || closesSubstitution) {
+			if (closesSubstitution) {
+				braces.pop();
+			}
+			const chunk = readTemplateChunk(text, i);
+			push('template', chunk.end);
+			if (chunk.substitution) {
+				braces.push('template');
+			}
+			regexAllowed = chunk.substitution;
+			continue;
+		}
+
+		if (char === '/' && regexAllowed) {
+			const end = readRegexEnd(text, i);
+			if (end !== -1) {
+				push('regex', end);
+				regexAllowed = false;
+				continue;
+			}
+		}
+
+		const number = /[\d.]/.test(char) ? NUMBER.exec(text.slice(i)) : null;
+		if (number) {
+			push('number', i + number[0].length);
+			regexAllowed = false;
+			continue;
+		}
+
+		if (isIdentifierStart(char)) {
+			let end = i + 1;
+			while (end < text.length && isIdentifierPart(text[end] || '')) {
+				end++;
+			}
+			const isProperty = previous?.text === '.' || previous?.text === '?.';
+			const word = push('identifier', end).text;
+			regexAllowed = !isProperty && EXPRESSION_KEYWORDS.has(word);
+			continue;
+		}
+
+		const punctuator =
+			PUNCTUATORS.find((candidate) => text.startsWith(candidate, i)) ?? char;
+		const before = previous;
+		const beforeThat = beforePrevious;
+		push('punctuator', i + punctuator.length);
+		switch (punctuator) {
+			case '(':
+				parens.push(
+					before?.kind === 'identifier' &&
+						STATEMENT_HEAD_KEYWORDS.has(before.text) &&
+						beforeThat?.text !== '.',
+				);
+				regexAllowed = true;
+				break;
+			case ')':
+				regexAllowed = parens.pop() ?? false;
+				break;
+			case '{':
+				braces.push(braceKind(before));
+				regexAllowed = true;
+				break;
+			case '}':
+				regexAllowed = braces.pop() === 'block';
+				break;
+			case ']':
+			case '<': // JSX closing tags (</div>), never a comparison with a regex
+			case '++':
+			case '--':
+				regexAllowed = false;
+				break;
+			default:
+				regexAllowed = true;
+		}
+	}
+
+	return Object.freeze(tokens);
+}
+
+/**
+ * Decide whether a { opens a block or an object literal from the token
+ * before it; an object literal ends an expression, so / after it divides
+ */
+function braceKind(before: JavaScriptToken | undefined): BraceKind {
+	if (!before) {
+		return 'block';
+	}
+	if (before.kind === 'identifier') {
+		return EXPRESSION_KEYWORDS.has(before.text) &&
+			!BLOCK_KEYWORDS.has(before.text)
+			? 'object'
+			: 'block';
+	}
+	if (before.kind === 'punctuator') {
+		return [')', ';', '{', '}', '=>'].includes(before.text)
+			? 'block'
+			: 'object';
+	}
+	return 'object';
+}
+
+function readStringEnd(text: string, offset: number): number {
+	const quote = text[offset];
+	let i = offset + 1;
+	while (i < text.length) {
+		const char = text[i];
+		if (char === quote) {
+			return i + 1;
+		}
+		if (char === '\n') {
+			return i;
+		}
+		i += char === '\\' ? 2 : 1;
+	}
+	return text.length;
+}
+
+/**
+ * Read a template chunk starting at ` or at the } closing a substitution
+ */
+function readTemplateChunk(text: string, offset: number): TemplateChunk {
+	let i = offset + 1;
+	while (i < text.length) {
+		const char = text[i];
+		if (char === '`') {
+			return { end: i + 1, substitution: false };
+		}
+		if (char === '$' && text[i + 1] === '{') {
+			return { end: i + 2, substitution: true };
+		}
+		i += char === '\\' ? 2 : 1;
+	}
+	return { end: text.length, substitution: false };
+}
+
+/**
+ * Find the end of a regex literal (including flags), or -1 when the
+ * slash cannot start one because the literal would cross a line break
+ */
+function readRegexEnd(text: string, offset: number): number {
+	let inClass = false;
+	let i = offset + 1;
+	while (i < text.length) {
+		const char = text[i] || '';
+		if (char === '\n' || char === '\r') {
+			return -1;
+		}
+		if (char === '\\') {
+			if (text[i + 1] === '\n' || text[i + 1] === '\r') {
+				return -1;
+			}
+			i += 2;
+			continue;
+		}
+		if (char === '[') {
+			inClass = true;
+		} else if (char === ']') {
+			inClass = false;
+		} else if (char === '/' && !inClass) {
+			let end = i + 1;
+			while (end < text.length && isIdentifierPart(text[end] || '')) {
+				end++;
+			}
+			return end;
+		}
+		i++;
+	}
+	return -1;
+}
+
+function lineEnd(text: string, offset: number): number {
+	const newline = text.indexOf('\n', offset);
+	return newline === -1 ? text.length : newline;
+}
+
+function isIdentifierStart(char: string): boolean {
+	return /[A-Za-z_$#\u0080-\uFFFF]/.test(char);
+}
+
+function isIdentifierPart(char: string): boolean {
+	return /[\w$\u0080-\uFFFF]/.test(char);
+}
@@ -0,0 +1,301 @@
+/**
+ * Extract regex patterns from JavaScript and TypeScript source
+ * Works on the token stream, so only genuine /pattern/flags literals and
+ * RegExp calls in code are reported; slashes in strings, comments and
+ * division expressions are not. Constructor arguments may span lines and
+ * be built by concatenating constant strings.
+ */
+
+import type { ExtractedRegexPattern } from './extractPatterns';
+import { type JavaScriptToken, tokenizeJavaScript } from './javascriptLexer';
+import { createLineIndex, type LineIndex } from './lineIndex';
+import { locationAt } from './sourceScanner';
+import { readJavaScriptString } from './stringLiterals';
+
+/**
+ * Extract /pattern/flags literals and RegExp constructor calls
+ */
+export function extractJavaScriptPatterns(
+	text: string,
+): readonly ExtractedRegexPattern[] {
+	const tokens = tokenizeJavaScript(text).filter(
+		(token) => token.kind !== 'comment',
+	);
+	const lines = createLineIndex(text);
+	const patterns: ExtractedRegexPattern[] = [];
+
+	for (let index = 0; index < tokens.length; index++) {
+		const token = tokens[index];
+		if (token?.kind === 'regex') {
+			const close = token.text.lastIndexOf('/');
+			patterns.push(
+				createPattern(
+					lines,
+					token.start,
+					token.text.slice(1, close),
+					token.text.slice(close + 1),
+					token.text,
+				),
+			);
+			continue;
+		}
+
+		// new RegExp('pattern', 'flags') or RegExp('pattern', 'flags')
+		if (token?.kind === 'identifier' && token.text === 'RegExp') {
+			const constructor = readConstructorCall(text, tokens, index);
+			if (!constructor) {
+				continue;
+			}
+			const previous = tokens[index - 1];
+			const start = previous?.text === 'new' ? previous.start : token.start;
+			patterns.push(
+				createPattern(
+					lines,
+					start,
+					constructor.pattern,
+					constructor.flags,
+					text.slice(start, constructor.end),
+					!constructor.resolved,
+				),
+			);
+		}
+	}
+
+	return Object.freeze(patterns);
+}
+
+interface ConstructorCall {
+	readonly pattern: string;
+	readonly flags: string;
+	readonly end: number; // Offset just past the closing paren
+	readonly resolved: boolean; // False when non-constant parts were left out
+}
+
+interface StringExpression {
+	readonly value: string;
+	readonly resolved: boolean;
+	readonly next: number; // Index of the token after the expression
+}
+
+/**
+ * Read RegExp(pattern) or RegExp(pattern, flags) starting at the RegExp
+ * identifier; each argument may be a + concatenation of constant strings
+ */
+function readConstructorCall(
+	text: string,
+	tokens: readonly JavaScriptToken[],
+	index: number,
+): ConstructorCall | undefined {
+	if (tokens[index + 1]?.text !== '(') {
+		return undefined;
+	}
+	const pattern = readConcatenation(text, tokens, index + 2);
+	if (!pattern) {
+		return undefined;
+	}
+
+	let flags = '';
+	let resolved = pattern.resolved;
+	let close = tokens[pattern.next];
+	if (close?.text === ',') {
+		const flagsArgument = readConcatenation(text, tokens, pattern.next + 1);
+		const next =
+			flagsArgument?.next ?? skipExpression(tokens, pattern.next + 1);
+		if (flagsArgument?.resolved) {
+			flags = flagsArgument.value;
+		} else {
+			// Flags that are only known at runtime are left out
+			resolved = false;
+		}
+		close = tokens[next];
+	}
+
+	if (
+		close?.text !== ')' ||
+		pattern.value === '' ||
+		!/^[dgimsuvy]*$/.test(flags)
+	) {
+		return undefined;
+	}
+	return { pattern: pattern.value, flags, end: close.end, resolved };
+}
+
+/**
+ * Read a + concatenation up to the next top-level , or )
+ * Non-constant operands are skipped and mark the result unresolved;
+ * nothing is returned when no operand is constant.
+ */
+function readConcatenation(
+	text: string,
+	tokens: readonly JavaScriptToken[],
+	index: number,
+): StringExpression | undefined {
+	let value = '';
+	let constantParts = 0;
+	let resolved = true;
+	let i = index;
+
+	while (i < tokens.length) {
+		const operand = readOperand(text, tokens, i, constantParts > 0);
+		if (operand) {
+			value += operand.value;
+			constantParts++;
+			resolved = resolved && operand.resolved;
+			i = operand.next;
+		} else {
+			resolved = false;
+			i = skipOperand(tokens, i);
+		}
+		if (tokens[i]?.text !== '+') {
+			break;
+		}
+		i++;
+	}
+
+	return constantParts > 0 ? { value, resolved, next: i } : undefined;
+}
+
+/**
+ * Read one constant operand of a concatenation: a string, a template
+ * without substitutions, String.raw`...`, a parenthesised concatenation,
+ * or a number once the expression is already a string ('a{' + 2 + '}')
+ */
+function readOperand(
+	text: string,
+	tokens: readonly JavaScriptToken[],
+	index: number,
+	afterString: boolean,
+): StringExpression | undefined {
+	const token = tokens[index];
+	let operand: StringExpression | undefined;
+
+	if (token?.text === '(') {
+		const inner = readConcatenation(text, tokens, index + 1);
+		operand =
+			inner && tokens[inner.next]?.text === ')'
+				? { ...inner, next: inner.next + 1 }
+				: undefined;
+	} else if (token?.kind === 'number' && afterString) {
+		operand = {
+			value: String(Number(token.text.replace(/_/g, ''))),
+			resolved: true,
+			next: index + 1,
+		};
+	} else {
+		operand = readStringArgument(text, tokens, index);
+	}
+
+	// 'x'.repeat(3) and similar are not constant
+	const after = tokens[operand?.next ?? index]?.text;
+	return after === '+' || after === ',' || after === ')' ? operand : undefined;
+}
+
+/**
+ * Read a quoted string, a template without substitutions or a String.raw
+ * template, decoded as the engine sees it
+ */
+function readStringArgument(
+	text: string,
+	tokens: readonly JavaScriptToken[],
+	index: number,
+): StringExpression | undefined {
+	const token = tokens[index];
+	if (token?.kind === 'string' || token?.kind === 'template') {
+		const literal = readJavaScriptString(text, token.start);
+		return literal && literal.end === token.end && !literal.interpolated
+			? { value: literal.value, resolved: true, next: index + 1 }
+			: undefined;
+	}
+
+	// String.raw`...` keeps backslashes; only line breaks are normalized
+	const template = tokens[index + 3];
+	if (
+		token?.text === 'String' &&
+		tokens[index + 1]?.text === '.' &&
+		tokens[index + 2]?.text === 'raw' &&
+		template?.kind === 'template' &&
+		isCompleteTemplate(template.text)
+	) {
+		return {
+			value: template.text.slice(1, -1).replace(/\r\n?/g, '\n'),
+			resolved: true,
+			next: index + 4,
+		};
+	}
+
+	return undefined;
+}
+
+/**
+ * Skip a whole argument expression, stopping at the next top-level , or )
+ */
+function skipExpression(
+	tokens: readonly JavaScriptToken[],
+	index: number,
+): number {
+	let i = skipOperand(tokens, index);
+	while (tokens[i]?.text === '+') {
+		i = skipOperand(tokens, i + 1);
+	}
+	return i;
+}
+
+/**
+ * Skip a non-constant operand, stopping at the next top-level +, , or )
+ */
+function skipOperand(
+	tokens: readonly JavaScriptToken[],
+	index: number,
+): number {
+	let depth = 0;
+	let i = index;
+	while (i < tokens.length) {
+		const token = tokens[i];
+		const value = token?.text ?? '';
+		if (token?.kind === 'template') {
+			// Substitutions open with ...${ and close with }...
+			if (value.startsWith('}')) {
+				depth--;
+			}
+			if (value.endsWith('${')) {
+				depth++;
+			}
+		} else if (value === '(' || value === '[' || value === '{') {
+			depth++;
+		} else if (value === ')' || value === ']' || value === '}') {
+			if (depth === 0) {
+				break;
+			}
+			depth--;
+		} else if (depth === 0 && [',', '+', ';'].includes(value)) {
+			break;
+		}
+		i++;
+	}
+	return i;
+}
+
+/**
+ * A template token spanning the whole literal, with no ${...} chunks
+ */
+function isCompleteTemplate(text: string): boolean {
+	return text.length >= 2 && text.startsWith('`') && text.endsWith('`');
+}
+
+function createPattern(
+	lines: LineIndex,
+	offset: number,
+	pattern: string,
+	flags: string,
+	match: string,
+	partial = false,
+): ExtractedRegexPattern {
+	return Object.freeze({
+		pattern,
+		flags,
+		...locationAt(lines, offset, offset + match.length),
+		match,
+		dialect: 'javascript' as const,
+		...(partial ? { partial } : {}),
+	});
+}
@@ -0,0 +1,48 @@
+import { describe, expect, it } from 'vitest';
+import { createLineIndex } from './lineIndex';
+
+describe('createLineIndex', () => {
+	const text = 'ab\ncd\r\n\nef';
+	const lines = createLineIndex(text);
+
+	it('should find the line of an offset', () => {
+		expect(lines.lineAt(0)).toBe(1);
+		expect(lines.lineAt(2)).toBe(1);
+		expect(lines.lineAt(3)).toBe(2);
+		expect(lines.lineAt(7)).toBe(3);
+		expect(lines.lineAt(9)).toBe(4);
+		expect(lines.lineAt(100)).toBe(4);
+		expect(lines.lineStart(4)).toBe(8);
+	});
+
+	it('should give 0-based columns from the line start', () => {
+		expect(lines.position(4)).toEqual({ line: 2, column: 1 });
+		expect(lines.position(10)).toEqual({ line: 4, column: 2 });
+	});
+
+	it('should count a surrogate pair as one column', () => {
+		const emoji = createLineIndex('😀a😀b\n😀c');
+
+		expect(emoji.position('😀a😀'.length)).toEqual({ line: 1, column: 3 });
+		expect(emoji.position('😀a😀b\n😀'.length)).toEqual({
+			line: 2,
+			column: 1,
+		});
+	});
+
+	it('should agree with splitting the text on every line break', () => {
+		const log = Array.from(
+			{ length: 50 },
+			(_, i) => `${i}: ${'x'.repeat(i % 7)}`,
+		).join('\n');
+		const index = createLineIndex(log);
+
+		for (let offset = 0; offset <= log.length; offset += 3) {
+			const before = log.slice(0, offset).split('\n');
+			expect(index.position(offset)).toEqual({
+				line: before.length,
+				column: before[before.length - 1]?.length ?? 0,
+			});
+		}
+	});
+});
@@ -0,0 +1,81 @@
+/**
+ * Line and column lookup for character offsets
+ * Line starts are found once per text and searched by bisection, so mapping
+ * thousands of matches stays linear in the size of the text. Columns can be
+ * counted in code points, so an emoji or other astral character before a
+ * match counts as one column rather than two UTF-16 code units.
+ */
+
+export interface TextPosition {
+	readonly line: number; // 1-based
+	readonly column: number; // 0-based
+}
+
+export interface LineIndex {
+	/** 1-based line containing offset; offsets past the end give the last line */
+	lineAt(offset: number): number;
+	/** Offset where a 1-based line starts */
+	lineStart(line: number): number;
+	/** Line and column of offset, the column counted in code points */
+	position(offset: number): TextPosition;
+}
+
+/**
+ * Index the line starts and surrogate pairs of a text
+ */
+export function createLineIndex(text: string): LineIndex {
+	const starts = [0];
+	const pairEnds: number[] = []; // Offset of the low half of each pair
+	for (let i = 0; i < text.length; i++) {
+		const code = text.charCodeAt(i);
+		if (code === 0x0a) {
+			starts.push(i + 1);
+		} else if (
+			isHighSurrogate(code) &&
+			isLowSurrogate(text.charCodeAt(i + 1))
+		) {
+			pairEnds.push(i + 1);
+			i++;
+		}
+	}
+
+	const lineAt = (offset: number): number => countAtOrBelow(starts, offset);
+	const lineStart = (line: number): number => starts[line - 1] ?? 0;
+
+	const position = (offset: number): TextPosition => {
+		const line = lineAt(offset);
+		const start = lineStart(line);
+		// Each pair inside [start, offset) is one column, not two
+		const pairs =
+			countAtOrBelow(pairEnds, offset - 1) -
+			countAtOrBelow(pairEnds, start - 1);
+		return { line, column: offset - start - pairs };
+	};
+
+	return Object.freeze({ lineAt, lineStart, position });
+}
+
+/**
+ * Number of values in a sorted array that are at most limit
+ */
+function countAtOrBelow(sorted: readonly number[], limit: number): number {
+	let low = 0;
+	let high = sorted.length;
+	while (low < high) {
+		const middle = (low + high) >>> 1;
+		if ((sorted[middle] ?? 0) <= limit) {
+			low = middle + 1;
+		} else {
+			high = middle;
+		}
+	}
+	return low;
+}
+
+function isHighSurrogate(code: number): boolean {
+	return code >= 0xd800 && code <= 0xdbff;
+}
+
+function isLowSurrogate(code: number): boolean {
+	return code >= 0xdc00 && code <= 0xdfff;
+}
@@ -0,0 +1,246 @@
+import { describe, expect, it } from 'vitest';
+import { aggregateMatches } from './aggregate';
+import { extractRegexPatterns } from './extractPatterns';
+import {
+	formatExtractedPatterns,
+	formatFrequencies,
+	formatMatches,
+	groupColumns,
+	inlineCode,
+	isExtractOutputFormat,
+} from './outputFormats';
+import { captureGroupNames, testRegexPattern } from './regexTest';
+
+const SOURCE = [
+	'const digits = /\\d+/g;',
+	'const csv = new RegExp(',
+	'\t"a,b|c"',
+	');',
+	'const again = /\\d+/g;',
+].join('\n');
+
+describe('formatExtractedPatterns', () => {
+	const patterns = extractRegexPatterns(SOURCE, 'javascript');
+
+	it('should group occurrences under each pattern as text', () => {
+		const output = formatExtractedPatterns(patterns, 'text', 'src/app.js');
+
+		expect(output.language).toBe('plaintext');
+		expect(output.content).toBe(
+			['/\\d+/g', '  1:16', '  5:15', '/a,b|c/', '  2:13-4:2'].join('\n'),
+		);
+	});
+
+	it('should write one JSON entry per occurrence with file and range', () => {
+		const output = formatExtractedPatterns(patterns, 'json', 'src/app.js');
+		const entries = JSON.parse(output.content);
+
+		expect(output.language).toBe('json');
+		expect(entries).toHaveLength(3);
+		expect(entries[1]).toEqual({
+			file: 'src/app.js',
+			pattern: 'a,b|c',
+			flags: '',
+			dialect: 'javascript',
+			range: {
+				start: { line: 2, column: 13 },
+				end: { line: 4, column: 2 },
+			},
+			source: 'new RegExp(\n\t"a,b|c"\n)',
+			partial: false,
+		});
+	});
+
+	it('should quote CSV fields holding commas, quotes or line breaks', () => {
+		const output = formatExtractedPatterns(patterns, 'csv', 'src/app.js');
+		const rows = output.content.split('\r\n');
+
+		expect(rows[0]).toBe(
+			'file,line,column,endLine,endColumn,pattern,flags,dialect,partial,source',
+		);
+		expect(rows[1]).toBe(
+			'src/app.js,1,16,1,22,\\d+,g,javascript,false,/\\d+/g',
+		);
+		expect(rows[2]).toBe(
+			'src/app.js,2,13,4,2,"a,b|c",,javascript,false,"new RegExp(\n\t""a,b|c""\n)"',
+		);
+	});
+
+	it('should separate TSV fields with tabs', () => {
+		const output = formatExtractedPatterns(patterns, 'tsv', 'src/app.js');
+		const rows = output.content.split('\r\n');
+
+		expect(rows[1]).toBe(
+			'src/app.js\t1\t16\t1\t22\t\\d+\tg\tjavascript\tfalse\t/\\d+/g',
+		);
+	});
+
+	it('should escape pipes and line breaks in Markdown table cells', () => {
+		const output = formatExtractedPatterns(patterns, 'markdown', 'app.js');
+		const lines = output.content.split('\n');
+
+		expect(output.language).toBe('markdown');
+		expect(lines[0]).toBe('# Regex Patterns in app.js');
+		expect(lines).toHaveLength(7);
+		expect(lines[5]).toBe(
+			'| 2:13-4:2 | `a,b\\|c` |  | javascript | `new RegExp( "a,b\\|c" )` |',
+		);
+	});
+
+	it('should lengthen the code fence around backticks', () => {
+		const templates = extractRegexPatterns(
+			'const tick = new RegExp(String.raw`a\\`b`);',
+			'javascript',
+		);
+		const output = formatExtractedPatterns(templates, 'markdown', 'app.js');
+
+		expect(output.content).toContain('``a\\`b``');
+	});
+
+	it('should mark partially resolved patterns', () => {
+		const partial = extractRegexPatterns(
+			'const re = new RegExp(prefix + "\\\\d+");',
+			'javascript',
+		);
+
+		const json = formatExtractedPatterns(partial, 'json', 'a.js');
+		const markdown = formatExtractedPatterns(partial, 'markdown', 'a.js');
+
+		expect(partial[0]?.partial).toBe(true);
+		expect(JSON.parse(json.content)[0].partial).toBe(true);
+		expect(markdown.content).toContain('1:12 ⚠️ partial');
+	});
+});
+
+describe('inlineCode', () => {
+	it('should fence values with a longer run than any backticks inside', () => {
+		expect(inlineCode('/a/')).toBe('`/a/`');
+		expect(inlineCode('/`+/')).toBe('``/`+/``');
+		expect(inlineCode('/```/')).toBe('````/```/````');
+		expect(inlineCode('`a')).toBe('`` `a ``');
+	});
+
+	it('should keep the span on one line', () => {
+		expect(inlineCode('a\n  b')).toBe('`a b`');
+	});
+});
+
+describe('isExtractOutputFormat', () => {
+	it('should accept known formats only', () => {
+		expect(isExtractOutputFormat('csv')).toBe(true);
+		expect(isExtractOutputFormat('markdown')).toBe(true);
+		expect(isExtractOutputFormat('ask')).toBe(false);
+		expect(isExtractOutputFormat('xml')).toBe(false);
+	});
+});
+
+describe('formatMatches', () => {
+	const LOG = [
+		'2025-01-27 10:00:00 [INFO] Application started',
+		'2025-01-27 10:01:20 [WARN] Slow query detected: 2.5s, "users"',
+	].join('\n');
+	const PATTERN =
+		'^(\\S+) (\\S+) \\[(?<level>\\w+)\\] (?<message>.*?)(?:: (.+))?$';
+	const names = captureGroupNames(PATTERN, 'gm');
+	const { matches } = testRegexPattern(PATTERN, 'gm', LOG);
+
+	it('should head CSV columns with group names or numbers', () => {
+		const output = formatMatches(matches, names, 'csv');
+		const rows = output.content.split('\r\n');
+
+		expect(output.language).toBe('plaintext');
+		expect(rows).toHaveLength(3);
+		expect(rows[0]).toBe(
+			'line,column,match,group1,group2,level,message,group5',
+		);
+		expect(rows[1]).toBe(
+			'1,1,2025-01-27 10:00:00 [INFO] Application started,2025-01-27,10:00:00,INFO,Application started,',
+		);
+		expect(rows[2]).toBe(
+			'2,1,"2025-01-27 10:01:20 [WARN] Slow query detected: 2.5s, ""users""",2025-01-27,10:01:20,WARN,Slow query detected,"2.5s, ""users"""',
+		);
+	});
+
+	it('should write one JSON object per match with null for unmatched groups', () => {
+		const output = formatMatches(matches, names, 'json');
+		const entries = JSON.parse(output.content);
+
+		expect(output.language).toBe('json');
+		expect(entries[0]).toEqual({
+			line: 1,
+			column: 1,
+			match: '2025-01-27 10:00:00 [INFO] Application started',
+			group1: '2025-01-27',
+			group2: '10:00:00',
+			level: 'INFO',
+			message: 'Application started',
+			group5: null,
+		});
+	});
+
+	it('should separate TSV fields with tabs', () => {
+		const words = testRegexPattern('(\\w)\\w*', 'g', 'ab cd').matches;
+		const output = formatMatches(words, [undefined], 'tsv');
+
+		expect(output.content).toBe(
+			['line\tcolumn\tmatch\tgroup1', '1\t1\tab\ta', '1\t4\tcd\tc'].join(
+				'\r\n',
+			),
+		);
+	});
+});
+
+describe('formatFrequencies', () => {
+	it('should write one CSV row per distinct value with rounded percentages', () => {
+		const { matches } = testRegexPattern('[ab,]', 'g', 'a\nb\na\n,');
+		const output = formatFrequencies(aggregateMatches(matches).frequencies);
+
+		expect(output.content.split('\r\n')).toEqual([
+			'value,count,percentage,firstLine,lastLine',
+			'a,2,50.00,1,3',
+			'b,1,25.00,2,2',
+			'",",1,25.00,4,4',
+		]);
+	});
+});
+
+describe('groupColumns', () => {
+	it('should fall back to the number for names that clash with match columns', () => {
+		expect(groupColumns(['line', undefined, 'year'])).toEqual([
+			'group1',
+			'group2',
+			'year',
+		]);
+	});
+
+	it('should suffix names taken by an earlier column', () => {
+		expect(groupColumns(['group2', undefined, 'group2'])).toEqual([
+			'group2',
+			'group2_2',
+			'group2_3',
+		]);
+	});
+
+	it('should keep every group as its own JSON key', () => {
+		const output = formatMatches(
+			[
+				{
+					match: 'ab',
+					index: 0,
+					line: 1,
+					column: 0,
+					groups: [
+						{ index: 0, name: 'group2', value: 'a', start: 0, end: 1 },
+						{ index: 1, value: 'b', start: 1, end: 2 },
+					],
+				},
+			],
+			['group2', undefined],
+			'json',
+		);
+
+		expect(JSON.parse(output.content)).toEqual([
+			{ line: 1, column: 1, match: 'ab', group2: 'a', group2_2: 'b' },
+		]);
+	});
+});
@@ -0,0 +1,321 @@
+/**
+ * Render extracted patterns for the Extract command
+ * Plain text groups occurrences under each pattern for reading; JSON, CSV,
+ * TSV and Markdown list one row per occurrence with its file, range, source
+ * form and dialect so the inventory can be fed into scripts and spreadsheets.
+ * Extract Matches writes the matches of a pattern the same way, one row per
+ * match with a column per capture group, and match frequencies export as
+ * CSV with one row per distinct value.
+ */
+
+import type {
+	ExtractOutputFormat,
+	MatchOutputFormat,
+	RegexMatch,
+} from '../../types';
+import type { ValueFrequency } from './aggregate';
+import {
+	type ExtractedRegexPattern,
+	formatLocation,
+	groupPatterns,
+} from './extractPatterns';
+
+export const EXTRACT_OUTPUT_FORMATS: readonly ExtractOutputFormat[] =
+	Object.freeze(['text', 'json', 'csv', 'tsv', 'markdown']);
+
+export interface FormattedOutput {
+	readonly content: string;
+	readonly language: string; // Language id for the result document
+}
+
+/**
+ * Column order shared by the CSV and TSV formats
+ */
+const DELIMITED_COLUMNS: readonly string[] = Object.freeze([
+	'file',
+	'line',
+	'column',
+	'endLine',
+	'endColumn',
+	'pattern',
+	'flags',
+	'dialect',
+	'partial',
+	'source',
+]);
+
+/**
+ * Columns written before the capture groups of each match
+ */
+const MATCH_COLUMNS: readonly string[] = Object.freeze([
+	'line',
+	'column',
+	'match',
+]);
+
+export function isExtractOutputFormat(
+	value: unknown,
+): value is ExtractOutputFormat {
+	return EXTRACT_OUTPUT_FORMATS.some((format) => format === value);
+}
+
+/**
+ * Format extracted patterns; file is the path shown for each occurrence
+ */
+export function formatExtractedPatterns(
+	patterns: readonly ExtractedRegexPattern[],
+	format: ExtractOutputFormat,
+	file: string,
+): FormattedOutput {
+	switch (format) {
+		case 'json':
+			return Object.freeze({
+				content: formatJson(patterns, file),
+				language: 'json',
+			});
+		case 'csv':
+			return Object.freeze({
+				content: formatDelimited(patterns, file, ','),
+				language: 'plaintext',
+			});
+		case 'tsv':
+			return Object.freeze({
+				content: formatDelimited(patterns, file, '\t'),
+				language: 'plaintext',
+			});
+		case 'markdown':
+			return Object.freeze({
+				content: formatMarkdown(patterns, file),
+				language: 'markdown',
+			});
+		default:
+			return Object.freeze({
+				content: formatText(patterns),
+				language: 'plaintext',
+			});
+	}
+}
+
+/**
+ * Each unique pattern followed by every line:column where it occurs
+ */
+function formatText(patterns: readonly ExtractedRegexPattern[]): string {
+	const lines: string[] = [];
+	for (const group of groupPatterns(patterns)) {
+		lines.push(`/${group.pattern}/${group.flags}`);
+		for (const occurrence of group.occurrences) {
+			lines.push(`  ${formatLocation(occurrence)}`);
+		}
+	}
+	return lines.join('\n');
+}
+
+function formatJson(
+	patterns: readonly ExtractedRegexPattern[],
+	file: string,
+): string {
+	const entries = patterns.map((p) => ({
+		file,
+		pattern: p.pattern,
+		flags: p.flags,
+		dialect: p.dialect,
+		range: {
+			start: { line: p.line, column: p.column },
+			end: { line: p.endLine, column: p.endColumn },
+		},
+		source: p.match,
+		partial: p.partial === true,
+	}));
+	return JSON.stringify(entries, null, 2);
+}
+
+function formatDelimited(
+	patterns: readonly ExtractedRegexPattern[],
+	file: string,
+	delimiter: string,
+): string {
+	const rows = patterns.map((p): string[] => [
+		file,
+		String(p.line),
+		String(p.column),
+		String(p.endLine),
+		String(p.endColumn),
+		p.pattern,
+		p.flags,
+		p.dialect,
+		String(p.partial === true),
+		p.match,
+	]);
+	return delimitedRows([DELIMITED_COLUMNS, ...rows], delimiter);
+}
+
+function delimitedRows(
+	rows: readonly (readonly string[])[],
+	delimiter: string,
+): string {
+	return rows
+		.map((row) =>
+			row.map((field) => quoteField(field, delimiter)).join(delimiter),
+		)
+		.join('\r\n');
+}
+
+/**
+ * Quote a field as RFC 4180 does when it holds the delimiter, a quote or a
+ * line break; spreadsheets read quoted TSV fields the same way
+ */
+function quoteField(field: string, delimiter: string): string {
+	if (!field.includes(delimiter) && !/["\r\n]/.test(field)) {
+		return field;
+	}
+	const escaped = field.replace(/"/g, '""');
+	return `"${escaped}"`;
+}
+
+function formatMarkdown(
+	patterns: readonly ExtractedRegexPattern[],
+	file: string,
+): string {
+	const lines = [
+		`# Regex Patterns in ${file}`,
+		'',
+		'| Location | Pattern | Flags | Dialect | Source |',
+		'| --- | --- | --- | --- | --- |',
+	];
+	for (const p of patterns) {
+		const location = p.partial
+			? `${formatLocation(p)} ⚠️ partial`
+			: formatLocation(p);
+		lines.push(
+			`| ${location} | ${codeCell(p.pattern)} | ${codeCell(p.flags)} | ${p.dialect} | ${codeCell(p.match)} |`,
+		);
+	}
+	return lines.join('\n');
+}
+
+/**
+ * Render a value as an inline code span that is safe inside a table cell
+ * As inlineCode, with pipes escaped.
+ */
+export function codeCell(value: string): string {
+	if (value === '') {
+		return '';
+	}
+	return inlineCode(value.replace(/\|/g, '\\|'));
+}
+
+/**
+ * Render a value as a Markdown inline code span, e.g. a pattern in a report
+ * Line breaks collapse to a space and the fence is made longer than any run
+ * of backticks in the value, so a backtick cannot end the span early.
+ */
+export function inlineCode(value: string): string {
+	const text = value.replace(/\s*\r?\n\s*/g, ' ');
+	const longestRun = Math.max(
+		0,
+		...(text.match(/`+/g) ?? []).map((run) => run.length),
+	);
+	const fence = '`'.repeat(longestRun + 1);
+	const padding = text.startsWith('`') || text.endsWith('`') ? ' ' : '';
+	return `${fence}${padding}${text}${padding}${fence}`;
+}
+
+/**
+ * Format the matches of a pattern with one row per match
+ * groupNames lists every capture group of the pattern in order (see
+ * captureGroupNames), so groups that did not take part in a match still get
+ * a column: empty in CSV and TSV, null in JSON. Lines and columns are
+ * 1-based.
+ */
+export function formatMatches(
+	matches: readonly RegexMatch[],
+	groupNames: readonly (string | undefined)[],
+	format: MatchOutputFormat,
+): FormattedOutput {
+	const columns = groupColumns(groupNames);
+	if (format === 'json') {
+		const entries = matches.map((match) => {
+			const entry: Record<string, string | number | null> = {
+				line: match.line ?? 1,
+				column: (match.column ?? 0) + 1,
+				match: match.match,
+			};
+			columns.forEach((column, index) => {
+				entry[column] = groupValue(match, index) ?? null;
+			});
+			return entry;
+		});
+		return Object.freeze({
+			content: JSON.stringify(entries, null, 2),
+			language: 'json',
+		});
+	}
+
+	const rows = matches.map((match): string[] => [
+		String(match.line ?? 1),
+		String((match.column ?? 0) + 1),
+		match.match,
+		...columns.map((_column, index) => groupValue(match, index) ?? ''),
+	]);
+	return Object.freeze({
+		content: delimitedRows(
+			[[...MATCH_COLUMNS, ...columns], ...rows],
+			format === 'tsv' ? '\t' : ',',
+		),
+		language: 'plaintext',
+	});
+}
+
+/**
+ * Column names for the capture groups of a pattern
+ * Named groups use their name and the rest group1, group2 and so on; a name
+ * that clashes with a match column falls back to its number. A name already
+ * taken by an earlier column, e.g. (?<group2>a)(b), gets a suffix (group2_2)
+ * so no JSON key is overwritten.
+ */
+export function groupColumns(
+	groupNames: readonly (string | undefined)[],
+): readonly string[] {
+	const taken = new Set<string>(MATCH_COLUMNS);
+	return Object.freeze(
+		groupNames.map((name, index) => {
+			const base =
+				name !== undefined && !MATCH_COLUMNS.includes(name)
+					? name
+					: `group${index + 1}`;
+			let column = base;
+			for (let suffix = 2; taken.has(column); suffix++) {
+				column = `${base}_${suffix}`;
+			}
+			taken.add(column);
+			return column;
+		}),
+	);
+}
+
+function groupValue(match: RegexMatch, index: number): string | undefined {
+	return match.groups?.find((group) => group.index === index)?.value;
+}
+
+/**
+ * Format match frequencies as CSV, most frequent first
+ * Percentages are rounded to two decimals.
+ */
+export function formatFrequencies(
+	frequencies: readonly ValueFrequency[],
+): FormattedOutput {
+	const rows = frequencies.map((frequency): string[] => [
+		frequency.value,
+		String(frequency.count),
+		frequency.percentage.toFixed(2),
+		String(frequency.firstLine),
+		String(frequency.lastLine),
+	]);
+	return Object.freeze({
+		content: delimitedRows(
+			[['value', 'count', 'percentage', 'firstLine', 'lastLine'], ...rows],
+			',',
+		),
+		language: 'plaintext',
+	});
+}
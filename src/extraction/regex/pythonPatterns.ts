@@ -0,0 +1,155 @@
+/**
+ * Extract regex call sites from Python source
+ * Understands the module-level functions of re and the third-party regex
+ * module, including raw, triple-quoted and implicitly concatenated literals
+ */
+
+import type { ExtractedRegexPattern } from './extractPatterns';
+import { createLineIndex } from './lineIndex';
+import {
+	findClosingBracket,
+	locationAt,
+	mapFlagNames,
+	maskNonCode,
+	readCallArguments,
+	type SourceSyntax,
+	skipTrivia,
+} from './sourceScanner';
+import { readPythonString } from './stringLiterals';
+
+const PYTHON_SYNTAX: SourceSyntax = Object.freeze({
+	lineComments: Object.freeze(['#']),
+	readString: readPythonString,
+});
+
+/**
+ * Positional index of the flags argument for each function
+ */
+const FLAGS_ARGUMENT: Readonly<Record<string, number>> = Object.freeze({
+	compile: 1,
+	search: 2,
+	match: 2,
+	fullmatch: 2,
+	findall: 2,
+	finditer: 2,
+	split: 3,
+	sub: 4,
+	subn: 4,
+});
+
+/**
+ * Keyword arguments, e.g. flags=re.I
+ */
+const KEYWORD_ARGUMENT = /^\s*(\w+)\s*=(?!=)/;
+
+/**
+ * re.RegexFlag members and their inline flag equivalents
+ * Module qualifiers (re., regex.) and unknown names such as 0 are ignored
+ */
+const FLAG_NAMES: Readonly<Record<string, string>> = Object.freeze({
+	I: 'i',
+	IGNORECASE: 'i',
+	M: 'm',
+	MULTILINE: 'm',
+	S: 's',
+	DOTALL: 's',
+	X: 'x',
+	VERBOSE: 'x',
+	A: 'a',
+	ASCII: 'a',
+	U: 'u',
+	UNICODE: 'u',
+	L: 'L',
+	LOCALE: 'L',
+});
+
+/**
+ * Extract all regex patterns from Python source text
+ */
+export function extractPythonPatterns(
+	text: string,
+): readonly ExtractedRegexPattern[] {
+	const masked = maskNonCode(text, PYTHON_SYNTAX);
+	const lines = createLineIndex(text);
+	const patterns: ExtractedRegexPattern[] = [];
+
+	// re.compile(...), regex.sub(...), ... but not obj.re.compile(...)
+	const functions = Object.keys(FLAGS_ARGUMENT).join('|');
+	const callSite = new RegExp(
+		`(?<![\\w.])(?:re|regex)\\s*\\.\\s*(${functions})\\s*\\(`,
+		'g',
+	);
+	for (const site of masked.matchAll(callSite)) {
+		const siteOffset = site.index ?? 0;
+		const openParen = siteOffset + site[0].length - 1;
+		const closeParen = findClosingBracket(masked, openParen);
+		if (closeParen === -1) {
+			continue;
+		}
+
+		const args = readCallArguments(
+			masked,
+			openParen,
+			closeParen,
+			KEYWORD_ARGUMENT,
+		);
+		const patternArgument = args.named.get('pattern') ?? args.positional[0];
+		const flagsArgument =
+			args.named.get('flags') ??
+			args.positional[FLAGS_ARGUMENT[site[1] || ''] ?? -1];
+		if (!patternArgument) {
+			continue;
+		}
+
+		const pattern = readConcatenatedStrings(
+			text,
+			patternArgument.start,
+			patternArgument.end,
+		);
+		if (pattern === undefined) {
+			continue;
+		}
+
+		const flags = flagsArgument
+			? mapFlagNames(
+					text.slice(flagsArgument.start, flagsArgument.end),
+					FLAG_NAMES,
+				)
+			: '';
+		patterns.push(
+			Object.freeze({
+				pattern,
+				flags,
+				...locationAt(lines, siteOffset, closeParen + 1),
+				match: text.slice(siteOffset, closeParen + 1),
+				dialect: 'python' as const,
+			}),
+		);
+	}
+
+	return Object.freeze(patterns);
+}
+
+/**
+ * Read adjacent string literals ("a" "b") as one value
+ * Returns nothing unless the argument consists only of constant literals
+ */
+function readConcatenatedStrings(
+	text: string,
+	start: number,
+	end: number,
+): string | undefined {
+	let value: string | undefined;
+	let cursor = skipTrivia(text, start, PYTHON_SYNTAX);
+
+	while (cursor < end) {
+		const literal = readPythonString(text, cursor);
+		if (!literal || literal.interpolated) {
+			return undefined;
+		}
+		value = (value ?? '') + literal.value;
+		cursor = skipTrivia(text, literal.end, PYTHON_SYNTAX);
+	}
+
+	return cursor === end ? value : undefined;
+}
@@ -0,0 +1,157 @@
+import { Worker } from 'node:worker_threads';
+import { afterEach, describe, expect, it, vi } from 'vitest';
+import type { RegexTestResult } from '../../types';
+import {
+	type CancellationSignal,
+	cancelledError,
+	createRegexRunner,
+	isCancelled,
+	isTimedOut,
+	type RegexRunner,
+	timeoutError,
+} from './regexRunner';
+
+function stopped(error: RegexTestResult['errors'][number]): RegexTestResult {
+	return {
+		success: false,
+		pattern: 'a',
+		flags: '',
+		matches: [],
+		errors: [error],
+	};
+}
+
+/**
+ * Runner whose worker runs the given script body, with the request bound to
+ * request and post() sending a RegexWorkerMessage
+ * The compiled regexWorker.js only exists in a build, so tests stand in
+ * their own worker.
+ */
+function runnerWith(body: string): RegexRunner {
+	const script = `
+import { parentPort, workerData as request } from 'node:worker_threads';
+const post = (message) => parentPort.postMessage(message);
+${body}`;
+	return createRegexRunner(
+		new URL(`data:text/javascript,${encodeURIComponent(script)}`),
+	);
+}
+
+/**
+ * Worker script lines posting count matches of "a" at offsets 0, 1, ...
+ */
+function postMatches(count: number): string {
+	return `for (let index = 0; index < ${count}; index++) {
+	post({ type: 'match', match: { match: 'a', index, line: 1, column: index + 1 } });
+}`;
+}
+
+describe('runRegexTest', () => {
+	afterEach(() => {
+		vi.restoreAllMocks();
+	});
+
+	it('should time out a catastrophic pattern within its budget', async () => {
+		const run = runnerWith(`
+const match = new RegExp(request.pattern, request.flags).exec(request.text);
+post({ type: 'done', success: true, errors: [], totalMatches: match ? 1 : 0 });
+`);
+
+		const result = await run('(a+)+$', '', `${'a'.repeat(40)}!`, 10, 200);
+
+		expect(isTimedOut(result)).toBe(true);
+		expect(result.success).toBe(false);
+		expect(result.performance.duration).toBeLessThan(2000);
+	});
+
+	it('should return the matches found before timing out', async () => {
+		const run = runnerWith(`${postMatches(2)}\nfor (;;) {}`);
+
+		const result = await run('a', 'g', 'aa', 10, 300);
+
+		expect(isTimedOut(result)).toBe(true);
+		expect(result.matches.map((m) => m.index)).toEqual([0, 1]);
+		expect(result.errors[0]?.message).toContain('2 match(es)');
+	});
+
+	it('should mark a test truncated when the limit was reached before the budget ran out', async () => {
+		const run = runnerWith(`${postMatches(3)}\nfor (;;) {}`);
+
+		const result = await run('a', 'g', 'aaaa', 3, 300);
+
+		expect(result.success).toBe(true);
+		expect(result.errors).toHaveLength(0);
+		expect(result.matches).toHaveLength(3);
+		expect(result.truncated).toBe(true);
+		expect(result.totalMatches).toBeUndefined();
+	});
+
+	it('should pass on the total counted by the worker', async () => {
+		const run = runnerWith(`${postMatches(1)}
+post({ type: 'done', success: true, errors: [], truncated: true, totalMatches: 4 });
+`);
+
+		const result = await run('a', 'g', 'aaaa', 1, 5000);
+
+		expect(result.success).toBe(true);
+		expect(result.truncated).toBe(true);
+		expect(result.totalMatches).toBe(4);
+	});
+
+	it('should terminate the worker on cancellation and keep its matches', async () => {
+		const terminate = vi.spyOn(Worker.prototype, 'terminate');
+		const run = runnerWith(`${postMatches(2)}\nfor (;;) {}`);
+		const listeners: (() => void)[] = [];
+		const cancellation: CancellationSignal = {
+			isCancellationRequested: false,
+			onCancellationRequested: (listener) => {
+				listeners.push(listener);
+				return { dispose: () => undefined };
+			},
+		};
+		setTimeout(() => {
+			for (const listener of listeners) {
+				listener();
+			}
+		}, 500);
+
+		const result = await run(
+			'a',
+			'g',
+			'aa',
+			10,
+			10000,
+			undefined,
+			cancellation,
+		);
+
+		expect(isCancelled(result)).toBe(true);
+		expect(isTimedOut(result)).toBe(false);
+		expect(result.matches).toHaveLength(2);
+		expect(terminate).toHaveBeenCalled();
+	});
+});
+
+describe('regexRunner', () => {
+	it('should report a timeout with the budget and partial count', () => {
+		const result = stopped(timeoutError(2000, 3));
+
+		expect(isTimedOut(result)).toBe(true);
+		expect(isCancelled(result)).toBe(false);
+		expect(result.errors[0]?.message).toContain('2000ms with 3 match(es)');
+	});
+
+	it('should report a cancellation with the partial count', () => {
+		const result = stopped(cancelledError(5));
+
+		expect(isCancelled(result)).toBe(true);
+		expect(isTimedOut(result)).toBe(false);
+		expect(result.errors[0]?.message).toContain('5 match(es)');
+	});
+
+	it('should not treat syntax errors as stopped early', () => {
+		const result = stopped({ type: 'parse-error', message: 'bad' });
+
+		expect(isTimedOut(result) || isCancelled(result)).toBe(false);
+	});
+});
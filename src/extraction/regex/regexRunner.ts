@@ -0,0 +1,171 @@
+/**
+ * Run regex tests in a worker thread with a wall-clock budget
+ * A catastrophic pattern would otherwise block the extension host; here the
+ * worker is terminated when the budget runs out or the test is cancelled,
+ * and the matches found so far are returned with a timeout or cancelled
+ * error. Running out of time after maxMatches were found only cuts short
+ * the count of the rest, so that result is truncated without a total.
+ */
+
+import { join } from 'node:path';
+import { Worker } from 'node:worker_threads';
+import type {
+	ParseError,
+	PerformanceMetrics,
+	RegexMatch,
+	RegexTestResult,
+} from '../../types';
+import { type TextRange, withPerformance } from './regexTest';
+import type { RegexWorkerMessage, RegexWorkerRequest } from './regexWorker';
+
+const WORKER_SCRIPT = join(__dirname, 'regexWorker.js');
+
+/**
+ * The part of a cancellation token the runner needs
+ * vscode.CancellationToken fits, without tying matching to the editor API.
+ */
+export interface CancellationSignal {
+	readonly isCancellationRequested: boolean;
+	onCancellationRequested(listener: () => void): { dispose(): void };
+}
+
+/**
+ * Test of a pattern in a worker, given up after timeoutMs or on cancellation
+ * With ranges, only those parts of the text are matched.
+ */
+export type RegexRunner = (
+	pattern: string,
+	flags: string,
+	text: string,
+	maxMatches: number,
+	timeoutMs: number,
+	ranges?: readonly TextRange[],
+	cancellation?: CancellationSignal,
+) => Promise<RegexTestResult & { performance: PerformanceMetrics }>;
+
+/**
+ * Create a runner whose workers run the given script
+ * The script receives a RegexWorkerRequest as workerData and answers with
+ * RegexWorkerMessages; tests pass their own, as the compiled regexWorker.js
+ * only exists in a build.
+ */
+export function createRegexRunner(workerScript: string | URL): RegexRunner {
+	return (pattern, flags, text, maxMatches, timeoutMs, ranges, cancellation) =>
+		new Promise((resolve) => {
+			const startTime = performance.now();
+			const request: RegexWorkerRequest = {
+				pattern,
+				flags,
+				text,
+				maxMatches,
+				ranges,
+			};
+			const matches: RegexMatch[] = [];
+			const worker = new Worker(workerScript, { workerData: request });
+			let settled = false;
+
+			const timer = setTimeout(() => {
+				if (matches.length >= maxMatches) {
+					finish(true, [], { truncated: true });
+				} else {
+					finish(false, [timeoutError(timeoutMs, matches.length)]);
+				}
+			}, timeoutMs);
+			const cancelListener = cancellation?.onCancellationRequested(() =>
+				finish(false, [cancelledError(matches.length)]),
+			);
+			if (cancellation?.isCancellationRequested) {
+				finish(false, [cancelledError(0)]);
+			}
+
+			function finish(
+				success: boolean,
+				errors: readonly ParseError[],
+				limit: Pick<RegexTestResult, 'truncated' | 'totalMatches'> = {},
+			): void {
+				if (settled) {
+					return;
+				}
+				settled = true;
+				clearTimeout(timer);
+				cancelListener?.dispose();
+				void worker.terminate();
+				const result: RegexTestResult = Object.freeze({
+					success,
+					pattern,
+					flags,
+					matches: Object.freeze(matches),
+					errors: Object.freeze(errors),
+					truncated: limit.truncated,
+					totalMatches: limit.totalMatches,
+				});
+				resolve(withPerformance(result, startTime, text, ranges));
+			}
+
+			worker.on('message', (message: RegexWorkerMessage) => {
+				if (message.type === 'match') {
+					matches.push(message.match);
+				} else {
+					finish(message.success, message.errors, message);
+				}
+			});
+			worker.on('error', (error) => {
+				finish(false, [
+					Object.freeze({
+						type: 'parse-error' as const,
+						message: error.message,
+					}),
+				]);
+			});
+			worker.on('exit', (code) => {
+				finish(false, [
+					Object.freeze({
+						type: 'parse-error' as const,
+						message: `Regex worker exited unexpectedly (code ${code})`,
+					}),
+				]);
+			});
+		});
+}
+
+/**
+ * Test a pattern in a worker running regexWorker.js
+ */
+export const runRegexTest: RegexRunner = createRegexRunner(WORKER_SCRIPT);
+
+/**
+ * Error reported when a test runs out of time
+ */
+export function timeoutError(
+	timeoutMs: number,
+	matchesFound: number,
+): ParseError {
+	return Object.freeze({
+		type: 'timeout',
+		message: `Stopped after ${timeoutMs}ms with ${matchesFound} match(es) found; the pattern may backtrack catastrophically`,
+	});
+}
+
+/**
+ * Error reported when a test is cancelled while running
+ */
+export function cancelledError(matchesFound: number): ParseError {
+	return Object.freeze({
+		type: 'cancelled',
+		message: `Cancelled with ${matchesFound} match(es) found`,
+	});
+}
+
+/**
+ * Whether a test was stopped by its time budget
+ */
+export function isTimedOut(result: RegexTestResult): boolean {
+	return result.errors.some((error) => error.type === 'timeout');
+}
+
+/**
+ * Whether a test was cancelled before it finished
+ */
+export function isCancelled(result: RegexTestResult): boolean {
+	return result.errors.some((error) => error.type === 'cancelled');
+}
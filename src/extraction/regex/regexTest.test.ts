@@ -0,0 +1,187 @@
+import { describe, expect, it } from 'vitest';
+import {
+	captureGroupNames,
+	testRegexInRanges,
+	testRegexPattern,
+	testRegexWithPerformance,
+} from './regexTest';
+
+describe('testRegexPattern', () => {
+	it('should give repeated groups their own offsets', () => {
+		const result = testRegexPattern('(a)(a)', '', 'xaa');
+		const groups = result.matches[0]?.groups ?? [];
+
+		expect(groups.map((g) => [g.start, g.end])).toEqual([
+			[1, 2],
+			[2, 3],
+		]);
+	});
+
+	it('should locate groups by line and column', () => {
+		const result = testRegexPattern('a\\n(b+)', 'g', 'xa\nbb');
+		const group = result.matches[0]?.groups?.[0];
+
+		expect(group?.value).toBe('bb');
+		expect([group?.start, group?.end]).toEqual([3, 5]);
+		expect([group?.line, group?.column]).toEqual([2, 0]);
+	});
+
+	it('should count columns in code points', () => {
+		const result = testRegexPattern('(b)', 'g', '😀 a\n🎉b');
+		const match = result.matches[0];
+
+		expect([match?.line, match?.column]).toEqual([2, 1]);
+		expect(match?.groups?.[0]?.column).toBe(1);
+		expect(match?.index).toBe(7);
+	});
+
+	it('should skip groups that did not take part in the match', () => {
+		const result = testRegexPattern('(x)?(y)', '', 'y');
+
+		expect(result.matches[0]?.groups?.map((g) => g.index)).toEqual([1]);
+	});
+
+	it('should name named groups', () => {
+		const result = testRegexPattern(
+			'(?<year>\\d{4})-(\\d{2})-(?<day>\\d{2})',
+			'',
+			'on 2024-05-17',
+		);
+		const groups = result.matches[0]?.groups ?? [];
+
+		expect(groups.map((g) => [g.name, g.value])).toEqual([
+			['year', '2024'],
+			[undefined, '05'],
+			['day', '17'],
+		]);
+	});
+
+	it('should pass each match to the listener as it is found', () => {
+		const seen: number[] = [];
+		const result = testRegexPattern('\\d', 'g', 'a1b2', 10, (match) =>
+			seen.push(match.index),
+		);
+
+		expect(seen).toEqual([1, 3]);
+		expect(result.matches.map((m) => m.index)).toEqual(seen);
+	});
+
+	it('should report syntax errors with the flags it was given', () => {
+		const result = testRegexPattern('(', 'g', 'a');
+
+		expect(result.success).toBe(false);
+		expect(result.errors[0]?.message).toContain('/(/g:');
+	});
+
+	it('should keep the flags it was given', () => {
+		const result = testRegexPattern('a', 'g', 'aa');
+
+		expect(result.flags).toBe('g');
+		expect(result.matches).toHaveLength(2);
+	});
+
+	it('should count every match past the limit', () => {
+		const result = testRegexPattern('a*', 'g', 'aa-a-aaa', 2);
+
+		expect(result.matches.map((m) => m.match)).toEqual(['aa', '']);
+		expect(result.truncated).toBe(true);
+		expect(result.totalMatches).toBe(6);
+	});
+
+	it('should not mark a result that fits the limit as truncated', () => {
+		const global = testRegexPattern('a', 'g', 'aa', 2);
+		const single = testRegexPattern('a', '', 'aa', 1);
+
+		expect(global.truncated).toBe(false);
+		expect(global.totalMatches).toBe(2);
+		expect(single.truncated).toBe(false);
+		expect(single.totalMatches).toBe(1);
+	});
+
+	it('should keep finding empty matches up to the limit', () => {
+		const result = testRegexPattern('', 'g', '-'.repeat(20000), 30000);
+
+		expect(result.matches).toHaveLength(20001);
+		expect(result.truncated).toBe(false);
+	});
+});
+
+describe('testRegexInRanges', () => {
+	const text = 'id 1\nid 22\nid 333';
+
+	it('should report matches at their position in the whole text', () => {
+		const result = testRegexInRanges('\\d+', 'g', text, [
+			{ start: 5, end: 10 },
+			{ start: 11, end: 17 },
+		]);
+
+		expect(result.success).toBe(true);
+		expect(result.matches.map((m) => [m.match, m.index, m.line])).toEqual([
+			['22', 8, 2],
+			['333', 14, 3],
+		]);
+		expect(result.matches[0]?.column).toBe(3);
+	});
+
+	it('should anchor at the bounds of each range', () => {
+		const result = testRegexInRanges('^id', 'g', text, [{ start: 8, end: 17 }]);
+
+		expect(result.matches.map((m) => m.index)).toEqual([]);
+	});
+
+	it('should share the match limit across ranges', () => {
+		const result = testRegexInRanges(
+			'\\d',
+			'g',
+			text,
+			[
+				{ start: 0, end: 10 },
+				{ start: 11, end: 17 },
+			],
+			2,
+		);
+
+		expect(result.matches.map((m) => m.index)).toEqual([3, 8]);
+		expect(result.truncated).toBe(true);
+		expect(result.totalMatches).toBe(6);
+	});
+
+	it('should shift capture groups into the whole text', () => {
+		const result = testRegexInRanges('id (\\d+)', 'g', text, [
+			{ start: 11, end: 17 },
+		]);
+
+		expect(result.matches[0]?.groups?.[0]?.start).toBe(14);
+		expect(result.matches[0]?.groups?.[0]?.end).toBe(17);
+		expect(result.matches[0]?.groups?.[0]?.line).toBe(3);
+	});
+
+	it('should measure only the selected input', () => {
+		const result = testRegexWithPerformance('\\d+', 'g', text, 10, 0, [
+			{ start: 5, end: 10 },
+		]);
+
+		expect(result.performance.inputSize).toBe(5);
+	});
+});
+
+describe('captureGroupNames', () => {
+	it('should number groups in source order', () => {
+		expect(captureGroupNames('(a)(?<b>b)(?:c)(?<d>d)', '')).toEqual([
+			undefined,
+			'b',
+			'd',
+		]);
+	});
+
+	it('should skip lookarounds, escapes and character classes', () => {
+		const pattern = '(?<=x)(?<!y)\\((?=z)[(]\\)(?<name>[)(])';
+
+		expect(captureGroupNames(pattern, '')).toEqual(['name']);
+	});
+
+	it('should nest character classes only with the v flag', () => {
+		expect(captureGroupNames('[[a]](b)', 'v')).toEqual([undefined]);
+		expect(captureGroupNames('[[(]](b)', '')).toEqual([undefined]);
+	});
+});
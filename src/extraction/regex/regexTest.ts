@@ -4,77 +4,103 @@ import type {
 	RegexMatch,
 	RegexTestResult,
 } from '../../types';
+import { createLineIndex, type LineIndex } from './lineIndex';
+
+/**
+ * A half-open span of character offsets, e.g. an editor selection
+ */
+export interface TextRange {
+	readonly start: number;
+	readonly end: number; // Exclusive
+}
+
+/**
+ * Match from exec with the d flag: the span of each group, where it matched
+ * (typed here because the ES2022 lib is not enabled)
+ */
+type IndexedExecArray = RegExpExecArray & {
+	readonly indices?: ReadonlyArray<readonly [number, number] | undefined>;
+};
+
+/**
+ * Called with each match as soon as it is found
+ */
+export type MatchListener = (match: RegexMatch) => void;
 
 /**
  * Test a regex pattern against text and return matches
+ * Group spans come from the d (hasIndices) flag, which is added internally
+ * when the pattern does not already use it. When more than maxMatches match,
+ * the result is marked truncated and the rest are counted without building
+ * match objects, so totalMatches is exact.
  */
 export function testRegexPattern(
 	pattern: string,
 	flags: string,
 	text: string,
 	maxMatches: number = 1000,
+	onMatch?: MatchListener,
 ): RegexTestResult {
 	try {
-		const regex = new RegExp(pattern, flags);
+		// Compiled as given first so syntax errors quote the user's flags
+		const regex = flags.includes('d')
+			? new RegExp(pattern, flags)
+			: new RegExp(new RegExp(pattern, flags), `${flags}d`);
+		const names = captureGroupNames(pattern, flags);
+		const lines = createLineIndex(text);
 		const matches: RegexMatch[] = [];
-		let match: RegExpExecArray | null = null;
-
-		// Use exec for proper global matching with groups
-		let execCount = 0;
-		while ((match = regex.exec(text)) !== null && matches.length < maxMatches) {
-			execCount++;
-
-			// Prevent infinite loops from zero-width matches
-			if (match.index === regex.lastIndex && match[0].length === 0) {
-				regex.lastIndex++;
-				if (execCount > 10000) {
-					break;
-				}
+		let match: IndexedExecArray | null = null;
+		let totalMatches: number | undefined;
+
+		// Use exec for proper global matching with groups; every pass either
+		// records a match or stops, so maxMatches bounds the loop
+		while ((match = regex.exec(text)) !== null) {
+			if (matches.length >= maxMatches) {
+				totalMatches =
+					matches.length + countMatches(pattern, flags, text, match.index);
+				break;
 			}
 
 			const groups: RegexGroup[] = [];
 
 			// Extract capture groups
 			for (let i = 1; i < match.length; i++) {
-				if (match[i] !== undefined) {
-					const groupName = getGroupName(regex, i - 1);
-					const groupMatchStart = match.index + getGroupStartPosition(match, i);
-					const groupMatchEnd = groupMatchStart + (match[i]?.length || 0);
-
+				const value = match[i];
+				const span = match.indices?.[i];
+				if (value !== undefined && span) {
+					const groupPosition = lines.position(span[0]);
 					groups.push(
 						Object.freeze({
 							index: i - 1,
-							name: groupName,
-							value: match[i] || '',
-							start: groupMatchStart,
-							end: groupMatchEnd,
+							name: groupName(match, names[i - 1]),
+							value,
+							start: span[0],
+							end: span[1],
+							line: groupPosition.line,
+							column: groupPosition.column,
 						}),
 					);
 				}
 			}
 
 			// Calculate line and column
-			const { line, column } = getPosition(text, match.index);
+			const { line, column } = lines.position(match.index);
 
-			matches.push(
-				Object.freeze({
-					match: match[0],
-					index: match.index,
-					groups: groups.length > 0 ? Object.freeze(groups) : undefined,
-					line,
-					column,
-				}),
-			);
+			const found: RegexMatch = Object.freeze({
+				match: match[0],
+				index: match.index,
+				groups: groups.length > 0 ? Object.freeze(groups) : undefined,
+				line,
+				column,
+			});
+			matches.push(found);
+			onMatch?.(found);
 
 			// If not global, break after first match
 			if (!flags.includes('g')) {
 				break;
 			}
-
-			// Prevent infinite loop
-			if (regex.lastIndex === match.index && match[0].length === 0) {
-				regex.lastIndex++;
-			}
+			skipEmptyMatch(regex, match);
 		}
 
 		return Object.freeze({
@@ -83,6 +109,8 @@ export function testRegexPattern(
 			flags,
 			matches: Object.freeze(matches),
 			errors: Object.freeze([]),
+			truncated: totalMatches !== undefined,
+			totalMatches: totalMatches ?? matches.length,
 		});
 	} catch (error) {
 		const errorMessage = error instanceof Error ? error.message : String(error);
@@ -102,49 +130,167 @@ export function testRegexPattern(
 }
 
 /**
- * Get the name of a capture group (if named)
+ * Count the matches from an offset on, without building results
+ * A pattern without the g flag only ever has one match.
  */
-function getGroupName(_regex: RegExp, _groupIndex: number): string | undefined {
-	// This is a simplified version - proper implementation would parse the regex
-	// to extract named groups. For now, we'll just return undefined.
-	return undefined;
+function countMatches(
+	pattern: string,
+	flags: string,
+	text: string,
+	from: number,
+): number {
+	if (!flags.includes('g')) {
+		return 1;
+	}
+	const regex = new RegExp(pattern, flags);
+	regex.lastIndex = from;
+	let count = 0;
+	let match: RegExpExecArray | null = null;
+	while ((match = regex.exec(text)) !== null) {
+		count++;
+		skipEmptyMatch(regex, match);
+	}
+	return count;
 }
 
 /**
- * Calculate the start position of a capture group
+ * Step past an empty match, which exec would otherwise find again forever
  */
-function getGroupStartPosition(
-	match: RegExpExecArray,
-	groupIndex: number,
-): number {
-	// Find where the group starts relative to the match start
-	const target = match[groupIndex];
-	if (!target) {
-		return 0;
+function skipEmptyMatch(regex: RegExp, match: RegExpExecArray): void {
+	if (match[0].length === 0 && regex.lastIndex === match.index) {
+		regex.lastIndex++;
 	}
-
-	// Simplified: assumes group text appears in order within the match
-	const matchText = match[0];
-	const groupStart = matchText.indexOf(target);
-	return groupStart >= 0 ? groupStart : 0;
 }
 
 /**
- * Get line and column from character index
+ * Test a regex pattern against parts of a text, e.g. editor selections
+ * Each range is matched as its own input, so anchors apply at its bounds,
+ * but match offsets, lines and columns refer to the whole text. Ranges past
+ * the limit are still counted towards totalMatches.
  */
-function getPosition(
+export function testRegexInRanges(
+	pattern: string,
+	flags: string,
 	text: string,
-	index: number,
-): { line: number; column: number } {
-	const beforeMatch = text.substring(0, index);
-	const lines = beforeMatch.split('\n');
-	const line = lines.length;
-	const column = lines[lines.length - 1]?.length || 0;
-	return { line, column };
+	ranges: readonly TextRange[],
+	maxMatches: number = 1000,
+	onMatch?: MatchListener,
+): RegexTestResult {
+	const matches: RegexMatch[] = [];
+	const lines = createLineIndex(text);
+	let truncated = false;
+	let totalMatches = 0;
+	for (const range of ranges) {
+		const result = testRegexPattern(
+			pattern,
+			flags,
+			text.slice(range.start, range.end),
+			Math.max(maxMatches - matches.length, 0),
+			(match) => {
+				const shifted = shiftMatch(match, range.start, lines);
+				matches.push(shifted);
+				onMatch?.(shifted);
+			},
+		);
+		if (!result.success) {
+			return result;
+		}
+		truncated = truncated || result.truncated === true;
+		totalMatches += result.totalMatches ?? result.matches.length;
+	}
+
+	return Object.freeze({
+		success: true,
+		pattern,
+		flags,
+		matches: Object.freeze(matches),
+		errors: Object.freeze([]),
+		truncated,
+		totalMatches,
+	});
+}
+
+/**
+ * Move a match found in a slice to its position in the whole text
+ */
+function shiftMatch(
+	match: RegexMatch,
+	offset: number,
+	lines: LineIndex,
+): RegexMatch {
+	const index = match.index + offset;
+	const { line, column } = lines.position(index);
+	const groups = match.groups?.map((group) => {
+		const start = group.start + offset;
+		return Object.freeze({
+			...group,
+			start,
+			end: group.end + offset,
+			...lines.position(start),
+		});
+	});
+	return Object.freeze({
+		...match,
+		index,
+		groups: groups ? Object.freeze(groups) : undefined,
+		line,
+		column,
+	});
+}
+
+/**
+ * Name of every capture group in source order, undefined for unnamed ones
+ * match.groups is keyed by name only, so the pattern is read to tie each
+ * name to its group number; this also gives names for groups that did not
+ * take part in a match, e.g. for column headers.
+ */
+export function captureGroupNames(
+	pattern: string,
+	flags: string,
+): readonly (string | undefined)[] {
+	const names: (string | undefined)[] = [];
+	const nestedClasses = flags.includes('v');
+	let classDepth = 0;
+	for (let i = 0; i < pattern.length; i++) {
+		const char = pattern[i];
+		if (char === '\\') {
+			i++;
+		} else if (char === '[') {
+			if (classDepth === 0 || nestedClasses) {
+				classDepth++;
+			}
+		} else if (char === ']') {
+			classDepth = Math.max(0, classDepth - 1);
+		} else if (char === '(' && classDepth === 0) {
+			if (pattern[i + 1] !== '?') {
+				names.push(undefined);
+				continue;
+			}
+			// (?<name> captures; (?<= and (?<! are lookbehinds
+			const named = /^\?<([^=!>][^>]*)>/.exec(pattern.slice(i + 1));
+			if (named) {
+				names.push(named[1]);
+			}
+		}
+	}
+	return Object.freeze(names);
+}
+
+/**
+ * Keep a parsed group name only if the engine reports it too
+ */
+function groupName(
+	match: RegExpExecArray,
+	name: string | undefined,
+): string | undefined {
+	return name !== undefined && match.groups && name in match.groups
+		? name
+		: undefined;
 }
 
 /**
  * Test regex with performance tracking
+ * With ranges, only those parts of the text are matched.
  */
 export function testRegexWithPerformance(
 	pattern: string,
@@ -152,8 +298,23 @@ export function testRegexWithPerformance(
 	text: string,
 	maxMatches: number,
 	startTime: number,
+	ranges?: readonly TextRange[],
+): RegexTestResult & { performance: PerformanceMetrics } {
+	const testResult = ranges
+		? testRegexInRanges(pattern, flags, text, ranges, maxMatches)
+		: testRegexPattern(pattern, flags, text, maxMatches);
+	return withPerformance(testResult, startTime, text, ranges);
+}
+
+/**
+ * Attach timing and size metrics to a finished test
+ */
+export function withPerformance(
+	testResult: RegexTestResult,
+	startTime: number,
+	text: string,
+	ranges?: readonly TextRange[],
 ): RegexTestResult & { performance: PerformanceMetrics } {
-	const testResult = testRegexPattern(pattern, flags, text, maxMatches);
 	const endTime = performance.now();
 	const duration = endTime - startTime;
 
@@ -162,7 +323,9 @@ export function testRegexWithPerformance(
 		startTime,
 		endTime,
 		duration,
-		inputSize: text.length,
+		inputSize: ranges
+			? ranges.reduce((sum, range) => sum + range.end - range.start, 0)
+			: text.length,
 		outputSize: testResult.matches.length,
 		itemCount: testResult.matches.length,
 		memoryUsage: 0, // Would need actual measurement
@@ -0,0 +1,64 @@
+/**
+ * Worker thread entry for regex tests
+ * Runs a test off the extension host and posts every match as it is found,
+ * so a test stopped for running too long still reports what it matched.
+ */
+
+import { parentPort, workerData } from 'node:worker_threads';
+import type { ParseError, RegexMatch } from '../../types';
+import {
+	type MatchListener,
+	type TextRange,
+	testRegexInRanges,
+	testRegexPattern,
+} from './regexTest';
+
+export interface RegexWorkerRequest {
+	readonly pattern: string;
+	readonly flags: string;
+	readonly text: string;
+	readonly maxMatches: number;
+	readonly ranges?: readonly TextRange[] | undefined;
+}
+
+export type RegexWorkerMessage =
+	| { readonly type: 'match'; readonly match: RegexMatch }
+	| {
+			readonly type: 'done';
+			readonly success: boolean;
+			readonly errors: readonly ParseError[];
+			readonly truncated?: boolean | undefined;
+			readonly totalMatches?: number | undefined;
+	  };
+
+function post(message: RegexWorkerMessage): void {
+	parentPort?.postMessage(message);
+}
+
+if (parentPort) {
+	const request: RegexWorkerRequest = workerData;
+	const onMatch: MatchListener = (match) => post({ type: 'match', match });
+	const result = request.ranges
+		? testRegexInRanges(
+				request.pattern,
+				request.flags,
+				request.text,
+				request.ranges,
+				request.maxMatches,
+				onMatch,
+			)
+		: testRegexPattern(
+				request.pattern,
+				request.flags,
+				request.text,
+				request.maxMatches,
+				onMatch,
+			);
+	post({
+		type: 'done',
+		success: result.success,
+		errors: result.errors,
+		truncated: result.truncated,
+		totalMatches: result.totalMatches,
+	});
+}
@@ -0,0 +1,86 @@
+import { describe, expect, it } from 'vitest';
+import { captureGroupNames, testRegexPattern } from './regexTest';
+import {
+	applyReplacements,
+	expandReplacement,
+	planReplacements,
+} from './replace';
+
+function replaceAll(
+	pattern: string,
+	flags: string,
+	text: string,
+	template: string,
+): string {
+	const result = testRegexPattern(pattern, flags, text);
+	const names = captureGroupNames(pattern, flags);
+	return applyReplacements(
+		text,
+		planReplacements(text, result.matches, template, names),
+	);
+}
+
+describe('replace', () => {
+	it('should substitute numbered and named groups like String.replace', () => {
+		const text = 'on 2024-05-17 and 2025-01-02';
+		const pattern = '(?<year>\\d{4})-(\\d{2})-(\\d{2})';
+		const template = '$3/$2/$<year>';
+
+		expect(replaceAll(pattern, 'g', text, template)).toBe(
+			text.replace(new RegExp(pattern, 'g'), template),
+		);
+	});
+
+	it('should expand $&, $$ and the text around the match', () => {
+		const text = 'a-b';
+		const template = "[$&|$$|$`|$']";
+
+		expect(replaceAll('-', 'g', text, template)).toBe(
+			text.replace(/-/g, template),
+		);
+	});
+
+	it('should keep references to missing groups as text', () => {
+		const text = 'ab';
+		const template = '$2$10$<x>$0';
+
+		expect(replaceAll('(a)', 'g', text, template)).toBe(
+			text.replace(/(a)/g, template),
+		);
+	});
+
+	it('should prefer two-digit group numbers that exist', () => {
+		const pattern = '(a)(b)(c)(d)(e)(f)(g)(h)(i)(j)(k)';
+		const text = 'abcdefghijk';
+
+		expect(replaceAll(pattern, '', text, '$11$12')).toBe(
+			text.replace(new RegExp(pattern), '$11$12'),
+		);
+	});
+
+	it('should leave groups that did not participate empty', () => {
+		const result = testRegexPattern('(x)?(y)', '', 'y');
+		const match = result.matches[0];
+		const names = captureGroupNames('(x)?(y)', '');
+
+		expect(match && expandReplacement('[$1$2]', match, 'y', names)).toBe(
+			'[y]',
+		);
+	});
+
+	it('should only replace the first match without the g flag', () => {
+		expect(replaceAll('o', '', 'foo', '0')).toBe('f0o');
+	});
+
+	it('should plan replacements with their position', () => {
+		const text = 'x\nab ab';
+		const result = testRegexPattern('ab', 'g', text);
+		const plan = planReplacements(text, result.matches, 'AB', []);
+
+		expect(plan.map((r) => [r.start, r.end, r.line, r.column])).toEqual([
+			[2, 4, 2, 0],
+			[5, 7, 2, 3],
+		]);
+		expect(plan[0]?.replacement).toBe('AB');
+	});
+});
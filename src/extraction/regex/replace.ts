@@ -0,0 +1,140 @@
+/**
+ * Replacement previews
+ * Expands JavaScript replacement templates ($1, $<name>, $&, $$, $` and $')
+ * for the matches of a test, so the result can be previewed and applied as
+ * editor edits instead of through String.prototype.replace.
+ */
+
+import type { RegexMatch } from '../../types';
+
+export interface Replacement {
+	readonly start: number;
+	readonly end: number; // Exclusive
+	readonly line: number;
+	readonly column: number;
+	readonly original: string;
+	readonly replacement: string;
+}
+
+/**
+ * Expand a replacement template for one match, as String.replace would
+ * groupNames lists every capture group of the pattern in order (see
+ * captureGroupNames); references to groups the pattern does not have are
+ * kept as literal text.
+ */
+export function expandReplacement(
+	template: string,
+	match: RegexMatch,
+	text: string,
+	groupNames: readonly (string | undefined)[],
+): string {
+	const values = new Map<number, string>();
+	const named = new Map<string, string>();
+	for (const group of match.groups ?? []) {
+		values.set(group.index + 1, group.value);
+		if (group.name) {
+			named.set(group.name, group.value);
+		}
+	}
+	const hasNamedGroups = groupNames.some((name) => name !== undefined);
+
+	let result = '';
+	for (let i = 0; i < template.length; i++) {
+		const char = template[i];
+		const next = template[i + 1];
+		if (char !== '$' || next === undefined) {
+			result += char;
+			continue;
+		}
+
+		if (next === '$') {
+			result += '$';
+			i++;
+		} else if (next === '&') {
+			result += match.match;
+			i++;
+		} else if (next === '`') {
+			result += text.slice(0, match.index);
+			i++;
+		} else if (next === "'") {
+			result += text.slice(match.index + match.match.length);
+			i++;
+		} else if (next === '<' && hasNamedGroups) {
+			const close = template.indexOf('>', i + 2);
+			if (close === -1) {
+				result += char;
+				continue;
+			}
+			result += named.get(template.slice(i + 2, close)) ?? '';
+			i = close;
+		} else if (/\d/.test(next)) {
+			const reference = groupReference(template, i + 1, groupNames.length);
+			if (reference === undefined) {
+				result += char;
+				continue;
+			}
+			result += values.get(reference.group) ?? '';
+			i += reference.length;
+		} else {
+			result += char;
+		}
+	}
+	return result;
+}
+
+/**
+ * Plan the replacement of every match, in document order
+ */
+export function planReplacements(
+	text: string,
+	matches: readonly RegexMatch[],
+	template: string,
+	groupNames: readonly (string | undefined)[],
+): readonly Replacement[] {
+	return Object.freeze(
+		matches.map(
+			(match): Replacement =>
+				Object.freeze({
+					start: match.index,
+					end: match.index + match.match.length,
+					line: match.line ?? 1,
+					column: match.column ?? 0,
+					original: match.match,
+					replacement: expandReplacement(template, match, text, groupNames),
+				}),
+		),
+	);
+}
+
+/**
+ * Apply planned replacements to the text they were planned for
+ */
+export function applyReplacements(
+	text: string,
+	replacements: readonly Replacement[],
+): string {
+	let result = '';
+	let offset = 0;
+	for (const replacement of replacements) {
+		result += text.slice(offset, replacement.start) + replacement.replacement;
+		offset = replacement.end;
+	}
+	return result + text.slice(offset);
+}
+
+/**
+ * Read $n or $nn; two digits win when they name an existing group
+ */
+function groupReference(
+	template: string,
+	start: number,
+	groupCount: number,
+): { group: number; length: number } | undefined {
+	const digits = /^\d{1,2}/.exec(template.slice(start))?.[0] ?? '';
+	const exists = (group: number): boolean => group >= 1 && group <= groupCount;
+	if (digits.length === 2 && exists(Number(digits))) {
+		return { group: Number(digits), length: 2 };
+	}
+	const first = Number(digits.charAt(0));
+	return exists(first) ? { group: first, length: 1 } : undefined;
+}
@@ -0,0 +1,263 @@
+/**
+ * Extract regex construction sites from Rust source
+ * Understands the regex crate constructors and builders as well as the
+ * lazy-regex macros; patterns inside once_cell::Lazy / lazy_static! blocks
+ * are found because the whole document is scanned, not single lines
+ */
+
+import type { ExtractedRegexPattern } from './extractPatterns';
+import { createLineIndex, type LineIndex } from './lineIndex';
+import {
+	findClosingBracket,
+	locationAt,
+	maskNonCode,
+	readIdentifier,
+	type SourceSyntax,
+	type StringLiteral,
+	skipTrivia,
+} from './sourceScanner';
+import { readRustString } from './stringLiterals';
+
+const RUST_SYNTAX: SourceSyntax = Object.freeze({
+	lineComments: Object.freeze(['//']),
+	blockComment: Object.freeze(['/*', '*/'] as const),
+	nestedBlockComments: true,
+	readString: readRustString,
+});
+
+/**
+ * RegexBuilder / RegexSetBuilder options and their inline flag equivalents
+ */
+const BUILDER_FLAGS: Readonly<Record<string, string>> = Object.freeze({
+	case_insensitive: 'i',
+	multi_line: 'm',
+	dot_matches_new_line: 's',
+	ignore_whitespace: 'x',
+	swap_greed: 'U',
+	crlf: 'R',
+});
+
+/**
+ * Flags accepted after the literal in lazy-regex macros, e.g. regex!("a"i)
+ * B selects the bytes API and has no flag equivalent
+ */
+const MACRO_FLAGS = 'imsxU';
+
+interface LocatedPattern {
+	readonly offset: number;
+	readonly pattern: ExtractedRegexPattern;
+}
+
+/**
+ * Extract all regex patterns from Rust source text
+ */
+export function extractRustPatterns(
+	text: string,
+): readonly ExtractedRegexPattern[] {
+	const masked = maskNonCode(text, RUST_SYNTAX);
+	const lines = createLineIndex(text);
+	const located: LocatedPattern[] = [];
+
+	// Regex::new(...), RegexBuilder::new(...), RegexSet::new([...]), ...
+	const constructorSite =
+		/\b(Regex|RegexBuilder|RegexSet|RegexSetBuilder)\s*::\s*new\s*\(/g;
+	for (const site of masked.matchAll(constructorSite)) {
+		const siteOffset = site.index ?? 0;
+		const kind = site[1] || '';
+		const openParen = siteOffset + site[0].length - 1;
+		const closeParen = findClosingBracket(masked, openParen);
+		if (closeParen === -1) {
+			continue;
+		}
+
+		const isSet = kind.startsWith('RegexSet');
+		const literals = isSet
+			? readSetLiterals(text, masked, openParen + 1)
+			: readSingleLiteral(text, openParen + 1, closeParen);
+
+		const chain = kind.endsWith('Builder')
+			? readBuilderChain(text, masked, closeParen + 1)
+			: { flags: '', end: closeParen + 1 };
+		const source = text.slice(siteOffset, chain.end);
+
+		for (const literal of literals) {
+			// Set members are reported at their own position
+			const offset = isSet ? literal.start : siteOffset;
+			located.push({
+				offset,
+				pattern: createPattern(
+					lines,
+					offset,
+					isSet ? literal.end : chain.end,
+					literal.value,
+					chain.flags,
+					source,
+				),
+			});
+		}
+	}
+
+	// lazy-regex macros: regex!("..."), lazy_regex!(r"..."i), bytes_regex!(...)
+	const macroSite = /\b(?:bytes_)?(?:lazy_)?regex!\s*\(/g;
+	for (const site of masked.matchAll(macroSite)) {
+		const siteOffset = site.index ?? 0;
+		const openParen = siteOffset + site[0].length - 1;
+		const closeParen = findClosingBracket(masked, openParen);
+		if (closeParen === -1) {
+			continue;
+		}
+
+		const literalStart = skipTrivia(text, openParen + 1, RUST_SYNTAX);
+		const literal = readRustString(text, literalStart);
+		if (!literal) {
+			continue;
+		}
+
+		const suffix = readIdentifier(text, literal.end);
+		const suffixEnd = literal.end + suffix.length;
+		if (skipTrivia(text, suffixEnd, RUST_SYNTAX) !== closeParen) {
+			continue;
+		}
+		const flags = [...suffix].filter((flag) => MACRO_FLAGS.includes(flag));
+
+		located.push({
+			offset: siteOffset,
+			pattern: createPattern(
+				lines,
+				siteOffset,
+				closeParen + 1,
+				literal.value,
+				flags.join(''),
+				text.slice(siteOffset, closeParen + 1),
+			),
+		});
+	}
+
+	located.sort((a, b) => a.offset - b.offset);
+	return Object.freeze(located.map((entry) => entry.pattern));
+}
+
+/**
+ * Read the only argument of Regex::new / RegexBuilder::new
+ * Returns nothing when the argument is not a plain string literal
+ */
+function readSingleLiteral(
+	text: string,
+	offset: number,
+	closeParen: number,
+): readonly StringLiteral[] {
+	let start = skipTrivia(text, offset, RUST_SYNTAX);
+	if (text[start] === '&') {
+		start = skipTrivia(text, start + 1, RUST_SYNTAX);
+	}
+
+	const literal = readRustString(text, start);
+	if (!literal || skipTrivia(text, literal.end, RUST_SYNTAX) !== closeParen) {
+		return [];
+	}
+	return [literal];
+}
+
+/**
+ * Read the literal members of a RegexSet argument: [..], &[..] or vec![..]
+ */
+function readSetLiterals(
+	text: string,
+	masked: string,
+	offset: number,
+): readonly StringLiteral[] {
+	let start = skipTrivia(text, offset, RUST_SYNTAX);
+	if (text[start] === '&') {
+		start = skipTrivia(text, start + 1, RUST_SYNTAX);
+	}
+	if (text.startsWith('vec!', start)) {
+		start = skipTrivia(text, start + 4, RUST_SYNTAX);
+	}
+	if (text[start] !== '[') {
+		return [];
+	}
+
+	const closeBracket = findClosingBracket(masked, start);
+	const literals: StringLiteral[] = [];
+	let cursor = skipTrivia(text, start + 1, RUST_SYNTAX);
+
+	while (cursor < closeBracket) {
+		const literal = readRustString(text, cursor);
+		if (!literal) {
+			break;
+		}
+		literals.push(literal);
+
+		cursor = skipTrivia(text, literal.end, RUST_SYNTAX);
+		if (text[cursor] !== ',') {
+			break;
+		}
+		cursor = skipTrivia(text, cursor + 1, RUST_SYNTAX);
+	}
+
+	return literals;
+}
+
+/**
+ * Follow a builder method chain and collect the flags it enables
+ */
+function readBuilderChain(
+	text: string,
+	masked: string,
+	offset: number,
+): { flags: string; end: number } {
+	const enabled = new Set<string>();
+	let end = offset;
+
+	for (;;) {
+		const dot = skipTrivia(text, end, RUST_SYNTAX);
+		if (text[dot] !== '.') {
+			break;
+		}
+
+		const nameStart = skipTrivia(text, dot + 1, RUST_SYNTAX);
+		const name = readIdentifier(text, nameStart);
+		const openParen = skipTrivia(text, nameStart + name.length, RUST_SYNTAX);
+		if (!name || text[openParen] !== '(') {
+			break;
+		}
+		const closeParen = findClosingBracket(masked, openParen);
+		if (closeParen === -1) {
+			break;
+		}
+
+		const flag = BUILDER_FLAGS[name];
+		if (flag) {
+			const argument = masked.slice(openParen + 1, closeParen).trim();
+			if (argument === 'true') {
+				enabled.add(flag);
+			} else {
+				enabled.delete(flag);
+			}
+		}
+
+		end = closeParen + 1;
+		if (name === 'build') {
+			break;
+		}
+	}
+
+	return { flags: [...enabled].join(''), end };
+}
+
+function createPattern(
+	lines: LineIndex,
+	offset: number,
+	end: number,
+	pattern: string,
+	flags: string,
+	match: string,
+): ExtractedRegexPattern {
+	return Object.freeze({
+		pattern,
+		flags,
+		...locationAt(lines, offset, end),
+		match,
+		dialect: 'rust' as const,
+	});
+}
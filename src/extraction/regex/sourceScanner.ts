@@ -0,0 +1,352 @@
+/**
+ * Language-agnostic helpers for scanning source code
+ * Used by the per-language extractors to find regex construction sites
+ * without being fooled by comments or string contents
+ */
+
+import type { LineIndex } from './lineIndex';
+
+export interface StringLiteral {
+	readonly value: string; // The decoded string value as the language sees it
+	readonly start: number; // Offset of the first character (including prefixes)
+	readonly end: number; // Offset just past the closing delimiter
+	readonly raw: boolean; // True when the literal has no escape processing
+	readonly interpolated?: boolean | undefined; // Contains substitutions, so the value is not constant
+	readonly character?: boolean | undefined; // A character literal, only read so it can be masked
+}
+
+/**
+ * Reads a string literal starting exactly at offset, or returns undefined
+ */
+export type StringLiteralReader = (
+	text: string,
+	offset: number,
+) => StringLiteral | undefined;
+
+export interface SourceRange {
+	readonly start: number;
+	readonly end: number; // Exclusive
+}
+
+export interface SourceSyntax {
+	readonly lineComments: readonly string[];
+	readonly blockComment?: readonly [string, string] | undefined;
+	readonly nestedBlockComments?: boolean | undefined;
+	readonly readString: StringLiteralReader;
+}
+
+/**
+ * Replace comments and string literals with spaces (newlines are kept)
+ * Offsets in the returned text line up with the original text, so call
+ * sites can be searched in the masked text and parsed from the original.
+ */
+export function maskNonCode(text: string, syntax: SourceSyntax): string {
+	const chars = text.split('');
+	walkSource(
+		text,
+		syntax,
+		(start, end) => blank(chars, start, end),
+		(literal) => blank(chars, literal.start, literal.end),
+	);
+	return chars.join('');
+}
+
+/**
+ * List the string literals in source order, skipping comments
+ */
+export function findStringLiterals(
+	text: string,
+	syntax: SourceSyntax,
+): readonly StringLiteral[] {
+	const literals: StringLiteral[] = [];
+	walkSource(text, syntax, () => {}, (literal) => literals.push(literal));
+	return literals;
+}
+
+/**
+ * Skip whitespace and comments starting at offset
+ */
+export function skipTrivia(
+	text: string,
+	offset: number,
+	syntax: SourceSyntax,
+): number {
+	let i = offset;
+	while (i < text.length) {
+		if (/\s/.test(text[i] || '')) {
+			i++;
+			continue;
+		}
+		const commentEnd = skipComment(text, i, syntax);
+		if (commentEnd === i) {
+			break;
+		}
+		i = commentEnd;
+	}
+	return i;
+}
+
+/**
+ * Find the offset of the bracket closing the one at openOffset
+ * Expects masked text so brackets inside strings and comments are ignored.
+ */
+export function findClosingBracket(
+	maskedText: string,
+	openOffset: number,
+): number {
+	const pairs: Record<string, string> = { '(': ')', '[': ']', '{': '}' };
+	const stack: string[] = [];
+
+	for (let i = openOffset; i < maskedText.length; i++) {
+		const char = maskedText[i] || '';
+		const closer = pairs[char];
+		if (closer) {
+			stack.push(closer);
+		} else if (char === ')' || char === ']' || char === '}') {
+			if (stack.pop() !== char) {
+				return -1;
+			}
+			if (stack.length === 0) {
+				return i;
+			}
+		}
+	}
+
+	return -1;
+}
+
+/**
+ * Split the arguments between a pair of brackets at top-level commas
+ * Expects masked text; string arguments are blank there, so ranges are
+ * returned even when they look empty (including after a trailing comma).
+ */
+export function splitArguments(
+	maskedText: string,
+	openOffset: number,
+	closeOffset: number,
+): readonly SourceRange[] {
+	const args: SourceRange[] = [];
+	let depth = 0;
+	let start = openOffset + 1;
+
+	for (let i = openOffset + 1; i <= closeOffset; i++) {
+		const char = maskedText[i] || '';
+		if (i === closeOffset || (char === ',' && depth === 0)) {
+			args.push({ start, end: i });
+			start = i + 1;
+		} else if (char === '(' || char === '[' || char === '{') {
+			depth++;
+		} else if (char === ')' || char === ']' || char === '}') {
+			depth--;
+		}
+	}
+
+	return args;
+}
+
+/**
+ * Read an argument that consists of exactly one constant string literal
+ * Character literals and interpolated strings are rejected.
+ */
+export function readLiteralArgument(
+	text: string,
+	argument: SourceRange,
+	syntax: SourceSyntax,
+): StringLiteral | undefined {
+	const start = skipTrivia(text, argument.start, syntax);
+	const literal = syntax.readString(text, start);
+	if (
+		!literal ||
+		literal.character ||
+		literal.interpolated ||
+		skipTrivia(text, literal.end, syntax) !== argument.end
+	) {
+		return undefined;
+	}
+	return literal;
+}
+
+export interface CallArguments {
+	readonly positional: readonly SourceRange[];
+	readonly named: ReadonlyMap<string, SourceRange>; // Ranges exclude the name
+}
+
+/**
+ * Sort call arguments into positional and named arguments
+ * namedPrefix matches the start of a named argument, e.g. /^\s*(\w+)\s*=/
+ */
+export function readCallArguments(
+	maskedText: string,
+	openOffset: number,
+	closeOffset: number,
+	namedPrefix?: RegExp,
+): CallArguments {
+	const positional: SourceRange[] = [];
+	const named = new Map<string, SourceRange>();
+
+	for (const { start, end } of splitArguments(
+		maskedText,
+		openOffset,
+		closeOffset,
+	)) {
+		const name = namedPrefix?.exec(maskedText.slice(start, end));
+		if (name) {
+			named.set(name[1] || '', { start: start + name[0].length, end });
+		} else if (named.size === 0) {
+			positional.push({ start, end });
+		}
+	}
+
+	return { positional, named };
+}
+
+/**
+ * Map the option constants in a flags expression to inline flags
+ * e.g. "Pattern.CASE_INSENSITIVE | Pattern.COMMENTS" gives "ix".
+ * Identifiers that are not in names (qualifiers, variables) are ignored.
+ */
+export function mapFlagNames(
+	expression: string,
+	names: Readonly<Record<string, string>>,
+): string {
+	const flags = new Set<string>();
+	for (const [name] of expression.matchAll(/[A-Za-z_$][\w$]*/g)) {
+		if (Object.prototype.hasOwnProperty.call(names, name)) {
+			flags.add(names[name] || '');
+		}
+	}
+	return [...flags].join('');
+}
+
+/**
+ * Read an identifier starting at offset (empty string if none)
+ */
+export function readIdentifier(text: string, offset: number): string {
+	let end = offset;
+	while (end < text.length && isIdentifierChar(text[end] || '')) {
+		end++;
+	}
+	return text.slice(offset, end);
+}
+
+/**
+ * Convert a character offset into a 1-based line and column
+ * Columns count UTF-16 code units, unlike the code point columns of match
+ * results: extracted locations are compared with editor selections and
+ * become editor ranges in places that no longer have the text to convert
+ * them, such as inventory entries.
+ */
+export function positionAt(
+	lines: LineIndex,
+	offset: number,
+): { line: number; column: number } {
+	const line = lines.lineAt(offset);
+	return { line, column: offset - lines.lineStart(line) + 1 };
+}
+
+export interface SourceLocation {
+	readonly line: number;
+	readonly column: number;
+	readonly endLine: number;
+	readonly endColumn: number; // Exclusive
+}
+
+/**
+ * Convert a start/end offset pair into 1-based lines and columns
+ */
+export function locationAt(
+	lines: LineIndex,
+	start: number,
+	end: number,
+): SourceLocation {
+	const from = positionAt(lines, start);
+	const to = positionAt(lines, end);
+	return {
+		line: from.line,
+		column: from.column,
+		endLine: to.line,
+		endColumn: to.column,
+	};
+}
+
+export function isIdentifierChar(char: string): boolean {
+	return /[\w$]/.test(char);
+}
+
+/**
+ * Visit every comment and string literal once, in source order
+ */
+function walkSource(
+	text: string,
+	syntax: SourceSyntax,
+	onComment: (start: number, end: number) => void,
+	onLiteral: (literal: StringLiteral) => void,
+): void {
+	let i = 0;
+
+	while (i < text.length) {
+		const commentEnd = skipComment(text, i, syntax);
+		if (commentEnd > i) {
+			onComment(i, commentEnd);
+			i = commentEnd;
+			continue;
+		}
+
+		const char = text[i] || '';
+		const previous = text[i - 1] || '';
+		if (isIdentifierChar(char) && isIdentifierChar(previous)) {
+			i++;
+			continue;
+		}
+
+		const literal = syntax.readString(text, i);
+		if (literal) {
+			onLiteral(literal);
+			i = literal.end;
+			continue;
+		}
+
+		i++;
+	}
+}
+
+function skipComment(
+	text: string,
+	offset: number,
+	syntax: SourceSyntax,
+): number {
+	for (const token of syntax.lineComments) {
+		if (text.startsWith(token, offset)) {
+			const newline = text.indexOf('\n', offset);
+			return newline === -1 ? text.length : newline;
+		}
+	}
+
+	const block = syntax.blockComment;
+	if (block && text.startsWith(block[0], offset)) {
+		let depth = 1;
+		let i = offset + block[0].length;
+		while (i < text.length && depth > 0) {
+			if (syntax.nestedBlockComments && text.startsWith(block[0], i)) {
+				depth++;
+				i += block[0].length;
+			} else if (text.startsWith(block[1], i)) {
+				depth--;
+				i += block[1].length;
+			} else {
+				i++;
+			}
+		}
+		return i;
+	}
+
+	return offset;
+}
+
+function blank(chars: string[], start: number, end: number): void {
+	for (let i = start; i < end; i++) {
+		if (chars[i] !== '\n' && chars[i] !== '\r') {
+			chars[i] = ' ';
+		}
+	}
+}
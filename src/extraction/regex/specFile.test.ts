@@ -0,0 +1,147 @@
+import { describe, expect, it } from 'vitest';
+import { type RegexRunner, timeoutError } from './regexRunner';
+import { testRegexPattern, withPerformance } from './regexTest';
+import { parseSpecFile, runSpecCase } from './specFile';
+
+const SPEC = `{
+	"name": "ISO date",
+	"pattern": "(?<year>\\\\d{4})-(\\\\d{2})",
+	"shouldMatch": ["2024-01", "on 1999-12"],
+	"shouldNotMatch": ["24-01"],
+	"expectedGroups": [
+		{ "input": "2024-01", "groups": { "year": "2024", "2": "01" } }
+	]
+}`;
+
+// The worker script only exists in a build, so cases run in process
+const inProcess: RegexRunner = async (pattern, flags, text, maxMatches) =>
+	withPerformance(
+		testRegexPattern(pattern, flags, text, maxMatches),
+		performance.now(),
+		text,
+	);
+
+function run(spec: string) {
+	const parsed = parseSpecFile(spec).specs[0];
+	const testCase = parsed?.cases[0];
+	return parsed && testCase
+		? runSpecCase(parsed, testCase, 1000, undefined, inProcess)
+		: undefined;
+}
+
+async function results(text: string): Promise<readonly boolean[]> {
+	const cases = parseSpecFile(text).specs.flatMap((spec) =>
+		spec.cases.map((testCase) =>
+			runSpecCase(spec, testCase, 1000, undefined, inProcess),
+		),
+	);
+	return (await Promise.all(cases)).map((result) => result.passed);
+}
+
+describe('parseSpecFile', () => {
+	it('should list the cases of a spec in file order', () => {
+		const { specs, errors } = parseSpecFile(SPEC);
+		const spec = specs[0];
+
+		expect(errors).toEqual([]);
+		expect(spec?.pattern).toBe('(?<year>\\d{4})-(\\d{2})');
+		expect(spec?.dialect).toBe('javascript');
+		expect(spec?.cases.map((c) => [c.kind, c.input])).toEqual([
+			['match', '2024-01'],
+			['match', 'on 1999-12'],
+			['no-match', '24-01'],
+			['groups', '2024-01'],
+		]);
+	});
+
+	it('should locate each case after its list key', () => {
+		const spec = parseSpecFile(SPEC).specs[0];
+
+		expect(spec?.cases.map((c) => c.offset)).toEqual([
+			SPEC.indexOf('"2024-01"'),
+			SPEC.indexOf('"on 1999-12"'),
+			SPEC.indexOf('"24-01"'),
+			SPEC.lastIndexOf('"2024-01"'),
+		]);
+	});
+
+	it('should keep valid specs when others are broken', () => {
+		const { specs, errors } = parseSpecFile(
+			'[{ "pattern": "a" }, { "pattern": 1 }, { "pattern": "b", "dialect": "perl" }]',
+		);
+
+		expect(specs.map((spec) => spec.pattern)).toEqual(['a']);
+		expect(errors).toHaveLength(2);
+		expect(errors[0]).toContain('Spec 2');
+		expect(errors[1]).toContain('"dialect"');
+	});
+
+	it('should report JSON syntax errors', () => {
+		expect(parseSpecFile('{ "pattern": ').errors).toHaveLength(1);
+	});
+});
+
+describe('runSpecCase', () => {
+	it('should pass cases the pattern satisfies', async () => {
+		expect(await results(SPEC)).toEqual([true, true, true, true]);
+	});
+
+	it('should report what matched when no match was expected', async () => {
+		const result = await run(
+			'{ "pattern": "\\\\d+", "shouldNotMatch": ["v12"] }',
+		);
+
+		expect(result?.passed).toBe(false);
+		expect(result?.message).toContain('matched "12" at offset 1');
+	});
+
+	it('should diff expected and actual groups', async () => {
+		const result = await run(
+			'{ "pattern": "(a)(b)?", "expectedGroups": [{ "input": "a", "groups": { "1": "a", "2": "b" } }] }',
+		);
+
+		expect(result?.passed).toBe(false);
+		expect(result?.expected).toBe('{\n  "1": "a",\n  "2": "b"\n}');
+		expect(result?.actual).toBe('{\n  "1": "a",\n  "2": null\n}');
+	});
+
+	it('should check other dialects against their own syntax', async () => {
+		const text =
+			'{ "pattern": "(?P<word>\\\\w+)", "dialect": "python", "expectedGroups": [{ "input": "hi", "groups": { "word": "hi" } }] }';
+
+		const lookbehind = text.replace('(?P', '(?<=x)(?P');
+
+		expect(await results(text)).toEqual([true]);
+		expect(await results(text.replace('python', 'go'))).toEqual([true]);
+		expect(await results(lookbehind.replace('python', 'go'))).toEqual([
+			false,
+		]);
+	});
+
+	it('should error instead of failing when matching times out', async () => {
+		const spec = parseSpecFile(
+			'{ "pattern": "(a+)+$", "shouldMatch": ["aaaa!"] }',
+		).specs[0];
+		const testCase = spec?.cases[0];
+		const timedOut: RegexRunner = async (pattern, flags, text) =>
+			withPerformance(
+				{
+					success: false,
+					pattern,
+					flags,
+					matches: [],
+					errors: [timeoutError(50, 0)],
+				},
+				performance.now(),
+				text,
+			);
+		const result =
+			spec && testCase
+				? await runSpecCase(spec, testCase, 50, undefined, timedOut)
+				: undefined;
+
+		expect(result?.passed).toBe(false);
+		expect(result?.errored).toBe(true);
+		expect(result?.message).toContain('Stopped after 50ms');
+	});
+});
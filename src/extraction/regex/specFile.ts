@@ -0,0 +1,336 @@
+/**
+ * Regex spec files
+ * A *.regex-test.json file keeps test cases for a pattern next to the code
+ * that uses it: inputs the pattern should match, inputs it should not, and
+ * the capture groups expected for an input. A file holds one spec or an
+ * array of them, and each case runs on its own in a regex worker.
+ */
+
+import type { RegexDialect } from '../../types';
+import { checkDialectSyntax, toJavaScriptPattern } from './dialects';
+import {
+	type CancellationSignal,
+	isCancelled,
+	isTimedOut,
+	type RegexRunner,
+	runRegexTest,
+} from './regexRunner';
+
+export const SPEC_FILE_GLOB = '**/*.regex-test.json';
+
+const DIALECTS: readonly RegexDialect[] = Object.freeze([
+	'javascript',
+	'rust',
+	'python',
+	'go',
+	'java',
+	'dotnet',
+]);
+
+export type SpecCaseKind = 'match' | 'no-match' | 'groups';
+
+export interface SpecCase {
+	readonly kind: SpecCaseKind;
+	readonly input: string;
+	/** Expected value by group number or name; null for a group that does not take part */
+	readonly groups?: Readonly<Record<string, string | null>> | undefined;
+	readonly offset: number; // Of the input in the file, -1 when not found
+}
+
+export interface RegexSpec {
+	readonly name?: string | undefined;
+	readonly pattern: string;
+	readonly flags: string;
+	readonly dialect: RegexDialect;
+	readonly cases: readonly SpecCase[];
+	readonly offset: number; // Of the pattern in the file, -1 when not found
+}
+
+export interface SpecFile {
+	readonly specs: readonly RegexSpec[];
+	readonly errors: readonly string[]; // Specs that could not be read
+}
+
+export interface SpecCaseResult {
+	readonly passed: boolean;
+	readonly errored?: boolean | undefined; // Could not finish: timed out or cancelled
+	readonly message?: string | undefined; // Why the case failed
+	readonly expected?: string | undefined; // Shown as a diff with actual
+	readonly actual?: string | undefined;
+}
+
+/**
+ * Read the specs of a file, keeping the valid ones when others are broken
+ * Offsets are found by searching for the JSON form of each string after
+ * the one before it, so they are approximate for unusually escaped text.
+ */
+export function parseSpecFile(text: string): SpecFile {
+	let json: unknown;
+	try {
+		json = JSON.parse(text);
+	} catch (error) {
+		const message = error instanceof Error ? error.message : String(error);
+		return Object.freeze({
+			specs: Object.freeze([]),
+			errors: Object.freeze([message]),
+		});
+	}
+
+	const entries = Array.isArray(json) ? json : [json];
+	const specs: RegexSpec[] = [];
+	const errors: string[] = [];
+	let cursor = 0;
+	entries.forEach((entry: unknown, index) => {
+		const spec = readSpec(entry, text, cursor);
+		if (typeof spec === 'string') {
+			errors.push(`Spec ${index + 1}: ${spec}`);
+			return;
+		}
+		specs.push(spec);
+		cursor = Math.max(cursor, spec.offset + 1);
+	});
+
+	return Object.freeze({
+		specs: Object.freeze(specs),
+		errors: Object.freeze(errors),
+	});
+}
+
+/**
+ * A spec from its JSON, or what is wrong with it
+ */
+function readSpec(
+	entry: unknown,
+	text: string,
+	from: number,
+): RegexSpec | string {
+	if (!isRecord(entry)) {
+		return 'expected an object';
+	}
+	const { name, pattern, flags = '', dialect = 'javascript' } = entry;
+	if (typeof pattern !== 'string') {
+		return '"pattern" must be a string';
+	}
+	if (typeof flags !== 'string') {
+		return '"flags" must be a string';
+	}
+	const knownDialect = DIALECTS.find((known) => known === dialect);
+	if (!knownDialect) {
+		return `"dialect" must be one of ${DIALECTS.join(', ')}`;
+	}
+	if (name !== undefined && typeof name !== 'string') {
+		return '"name" must be a string';
+	}
+
+	const offset = locate(text, pattern, from);
+	const start = Math.max(offset, from);
+	const shouldMatch = stringList(entry.shouldMatch);
+	const shouldNotMatch = stringList(entry.shouldNotMatch);
+	const expectedGroups = groupList(entry.expectedGroups);
+	if (!shouldMatch) {
+		return '"shouldMatch" must be an array of strings';
+	}
+	if (!shouldNotMatch) {
+		return '"shouldNotMatch" must be an array of strings';
+	}
+	if (!expectedGroups) {
+		return '"expectedGroups" must be an array of { "input", "groups" } objects whose groups are strings or null';
+	}
+
+	const cases = [
+		...listCases('match', 'shouldMatch', shouldMatch, text, start),
+		...listCases('no-match', 'shouldNotMatch', shouldNotMatch, text, start),
+		...listCases('groups', 'expectedGroups', expectedGroups, text, start),
+	];
+	return Object.freeze({
+		name,
+		pattern,
+		flags,
+		dialect: knownDialect,
+		cases: Object.freeze(cases),
+		offset,
+	});
+}
+
+interface GroupExpectation {
+	readonly input: string;
+	readonly groups: Readonly<Record<string, string | null>>;
+}
+
+/**
+ * Cases of one list, each located after the list's key and the case before
+ */
+function listCases(
+	kind: SpecCaseKind,
+	key: string,
+	entries: readonly (string | GroupExpectation)[],
+	text: string,
+	from: number,
+): readonly SpecCase[] {
+	let cursor = Math.max(locate(text, key, from), from);
+	return entries.map((entry): SpecCase => {
+		const input = typeof entry === 'string' ? entry : entry.input;
+		const offset = locate(text, input, cursor);
+		cursor = Math.max(cursor, offset + 1);
+		return Object.freeze({
+			kind,
+			input,
+			groups: typeof entry === 'string' ? undefined : entry.groups,
+			offset,
+		});
+	});
+}
+
+function stringList(value: unknown): readonly string[] | undefined {
+	if (value === undefined) {
+		return [];
+	}
+	return Array.isArray(value) &&
+		value.every((item: unknown) => typeof item === 'string')
+		? value
+		: undefined;
+}
+
+function groupList(value: unknown): readonly GroupExpectation[] | undefined {
+	if (value === undefined) {
+		return [];
+	}
+	if (!Array.isArray(value)) {
+		return undefined;
+	}
+	const expectations: GroupExpectation[] = [];
+	for (const item of value) {
+		if (!isRecord(item) || typeof item.input !== 'string') {
+			return undefined;
+		}
+		if (!isRecord(item.groups)) {
+			return undefined;
+		}
+		const groups: Record<string, string | null> = {};
+		for (const [key, group] of Object.entries(item.groups)) {
+			if (group !== null && typeof group !== 'string') {
+				return undefined;
+			}
+			groups[key] = group;
+		}
+		expectations.push({ input: item.input, groups: Object.freeze(groups) });
+	}
+	return expectations;
+}
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+	return typeof value === 'object' && value !== null && !Array.isArray(value);
+}
+
+/**
+ * Offset of a string's JSON form at or after from, -1 when not found
+ */
+function locate(text: string, value: string, from: number): number {
+	return text.indexOf(JSON.stringify(value), from);
+}
+
+/**
+ * Run one case against the first match of its spec's pattern
+ * Patterns of other dialects are checked against their own syntax rules
+ * and run as their JavaScript translation, as Test Regex does. Matching
+ * runs in a worker, so a case that backtracks catastrophically errors
+ * after timeoutMs instead of blocking the extension host.
+ */
+export async function runSpecCase(
+	spec: RegexSpec,
+	testCase: SpecCase,
+	timeoutMs: number,
+	cancellation?: CancellationSignal,
+	run: RegexRunner = runRegexTest,
+): Promise<SpecCaseResult> {
+	const check = checkDialectSyntax(spec.pattern, spec.flags, spec.dialect);
+	if (!check.valid) {
+		return failed(check.error ?? `Invalid ${spec.dialect} pattern`);
+	}
+
+	// Only the first match is needed, so matching is never global
+	const translation = toJavaScriptPattern(
+		spec.pattern,
+		spec.flags,
+		spec.dialect,
+	);
+	const result = await run(
+		translation.pattern,
+		translation.flags.replace('g', ''),
+		testCase.input,
+		1,
+		timeoutMs,
+		undefined,
+		cancellation,
+	);
+	if (isTimedOut(result) || isCancelled(result)) {
+		return Object.freeze({
+			passed: false,
+			errored: true,
+			message: result.errors.map((error) => error.message).join('; '),
+		});
+	}
+	if (!result.success) {
+		return failed(result.errors.map((error) => error.message).join('; '));
+	}
+
+	const match = result.matches[0];
+	const shown = `/${spec.pattern}/${spec.flags}`;
+	const input = JSON.stringify(testCase.input);
+	switch (testCase.kind) {
+		case 'match':
+			return match ? passed() : failed(`Expected ${shown} to match ${input}`);
+		case 'no-match':
+			return match
+				? failed(
+						`Expected ${shown} not to match ${input}, but it matched ${JSON.stringify(match.match)} at offset ${match.index}`,
+					)
+				: passed();
+		default: {
+			const expectedGroups = testCase.groups ?? {};
+			const expected = JSON.stringify(expectedGroups, null, 2);
+			if (!match) {
+				return failed(
+					`Expected ${shown} to match ${input}`,
+					expected,
+					'(no match)',
+				);
+			}
+
+			// Only the groups the case names are compared
+			const actualGroups = Object.fromEntries(
+				Object.keys(expectedGroups).map((key): [string, string | null] => {
+					if (key === '0') {
+						return [key, match.match];
+					}
+					const group = match.groups?.find((candidate) =>
+						/^\d+$/.test(key)
+							? candidate.index === Number(key) - 1
+							: candidate.name === key,
+					);
+					return [key, group?.value ?? null];
+				}),
+			);
+			const actual = JSON.stringify(actualGroups, null, 2);
+			return actual === expected
+				? passed()
+				: failed(
+						`Capture groups of ${shown} differ for ${input}`,
+						expected,
+						actual,
+					);
+		}
+	}
+}
+
+function passed(): SpecCaseResult {
+	return Object.freeze({ passed: true });
+}
+
+function failed(
+	message: string,
+	expected?: string,
+	actual?: string,
+): SpecCaseResult {
+	return Object.freeze({ passed: false, message, expected, actual });
+}
@@ -0,0 +1,730 @@
+/**
+ * Host-language string literal readers
+ * Each reader decodes a literal exactly as the language would, so the
+ * extracted pattern is the string the regex engine actually receives
+ */
+
+import type { StringLiteral } from './sourceScanner';
+
+/**
+ * Read a Rust string literal: "...", r"...", r#"..."#, b"...", br"..."
+ * Character literals ('x', '"') are also consumed so they can be masked.
+ */
+export function readRustString(
+	text: string,
+	offset: number,
+): StringLiteral | undefined {
+	let i = offset;
+	if (text[i] === 'b') {
+		i++;
+	}
+
+	if (text[i] === "'") {
+		return readRustChar(text, offset, i);
+	}
+
+	if (text[i] === 'r') {
+		i++;
+		let hashes = 0;
+		while (text[i] === '#') {
+			hashes++;
+			i++;
+		}
+		if (text[i] !== '"') {
+			return undefined;
+		}
+		const terminator = `"${'#'.repeat(hashes)}`;
+		const close = text.indexOf(terminator, i + 1);
+		if (close === -1) {
+			return undefined;
+		}
+		return Object.freeze({
+			value: text.slice(i + 1, close),
+			start: offset,
+			end: close + terminator.length,
+			raw: true,
+		});
+	}
+
+	if (text[i] !== '"') {
+		return undefined;
+	}
+
+	let value = '';
+	i++;
+	while (i < text.length) {
+		const char = text[i] || '';
+		if (char === '"') {
+			return Object.freeze({ value, start: offset, end: i + 1, raw: false });
+		}
+		if (char !== '\\') {
+			value += char;
+			i++;
+			continue;
+		}
+
+		const next = text[i + 1] || '';
+		if (next === '\n' || next === '\r') {
+			// Line continuation: skip the newline and leading whitespace
+			i += 2;
+			while (i < text.length && /\s/.test(text[i] || '')) {
+				i++;
+			}
+			continue;
+		}
+		if (next === 'x') {
+			const code = Number.parseInt(text.slice(i + 2, i + 4), 16);
+			value += String.fromCharCode(code);
+			i += 4;
+			continue;
+		}
+		if (next === 'u' && text[i + 2] === '{') {
+			const close = text.indexOf('}', i + 3);
+			if (close === -1) {
+				return undefined;
+			}
+			const codePoint = Number.parseInt(
+				text.slice(i + 3, close).replace(/_/g, ''),
+				16,
+			);
+			value += String.fromCodePoint(codePoint);
+			i = close + 1;
+			continue;
+		}
+		value += decodeSimpleEscape(next);
+		i += 2;
+	}
+
+	return undefined;
+}
+
+/**
+ * Decode the single-character escapes shared by C-family languages
+ * Unknown escapes keep their backslash so nothing is silently lost
+ */
+export function decodeSimpleEscape(char: string): string {
+	switch (char) {
+		case 'n':
+			return '\n';
+		case 'r':
+			return '\r';
+		case 't':
+			return '\t';
+		case '0':
+			return '\0';
+		case '\\':
+			return '\\';
+		case '"':
+			return '"';
+		case "'":
+			return "'";
+		default:
+			return `\\${char}`;
+	}
+}
+
+function readRustChar(
+	text: string,
+	start: number,
+	quote: number,
+): StringLiteral | undefined {
+	// Distinguish 'x' / '\n' / '\u{1F600}' from lifetimes like 'a
+	const charLiteral =
+		/^'(?:\\(?:x[0-9a-fA-F]{2}|u\{[0-9a-fA-F_]+\}|.)|[^'\\\n])'/u;
+	const match = charLiteral.exec(text.slice(quote, quote + 16));
+	if (!match) {
+		return undefined;
+	}
+	return Object.freeze({
+		value: match[0].slice(1, -1),
+		start,
+		end: quote + match[0].length,
+		raw: true,
+		character: true,
+	});
+}
+
+/**
+ * Read a Python string literal with any r/b/u/f prefix combination,
+ * single or triple quoted. f-strings are flagged as interpolated when
+ * they contain replacement fields.
+ */
+export function readPythonString(
+	text: string,
+	offset: number,
+): StringLiteral | undefined {
+	const prefix = /^(?:[rR][bBfF]?|[bBfF][rR]?|[uU])?/.exec(
+		text.slice(offset, offset + 2),
+	)?.[0];
+	let i = offset + (prefix?.length ?? 0);
+	const quote = text[i];
+	if (quote !== '"' && quote !== "'") {
+		return undefined;
+	}
+
+	const flags = (prefix ?? '').toLowerCase();
+	const raw = flags.includes('r');
+	const bytes = flags.includes('b');
+	const formatted = flags.includes('f');
+	const terminator = text.startsWith(quote.repeat(3), i)
+		? quote.repeat(3)
+		: quote;
+	i += terminator.length;
+
+	let value = '';
+	let interpolated = false;
+	while (i < text.length) {
+		if (text.startsWith(terminator, i)) {
+			return Object.freeze({
+				value,
+				start: offset,
+				end: i + terminator.length,
+				raw,
+				interpolated,
+			});
+		}
+
+		const char = text[i] || '';
+		if (char === '\n' && terminator.length === 1) {
+			return undefined;
+		}
+		if (formatted && (char === '{' || char === '}')) {
+			if (text[i + 1] === char) {
+				value += char;
+				i += 2;
+				continue;
+			}
+			interpolated = true;
+		}
+		if (char !== '\\') {
+			value += char;
+			i++;
+			continue;
+		}
+
+		const next = text[i + 1] || '';
+		if (raw) {
+			// Raw strings keep the backslash but it still protects a quote
+			value += char + next;
+			i += 2;
+			continue;
+		}
+		if (next === '\n') {
+			i += 2;
+			continue;
+		}
+
+		const octal = /^[0-7]{1,3}/.exec(text.slice(i + 1, i + 4));
+		if (octal) {
+			value += String.fromCharCode(Number.parseInt(octal[0], 8));
+			i += 1 + octal[0].length;
+			continue;
+		}
+		// \u and \U are only escapes in str literals, not bytes
+		const hexLengths: Record<string, number> = bytes
+			? { x: 2 }
+			: { x: 2, u: 4, U: 8 };
+		const hexLength = hexLengths[next];
+		const hex = hexLength
+			? new RegExp(`^[0-9A-Fa-f]{${hexLength}}`).exec(text.slice(i + 2))
+			: null;
+		if (hex) {
+			value += String.fromCodePoint(Number.parseInt(hex[0], 16));
+			i += 2 + hex[0].length;
+			continue;
+		}
+
+		const control: Record<string, string> = {
+			a: '\x07',
+			b: '\b',
+			f: '\f',
+			v: '\v',
+		};
+		value += control[next] ?? decodeSimpleEscape(next);
+		i += 2;
+	}
+
+	return undefined;
+}
+
+/**
+ * Read a Go string literal: `raw` or "interpreted"
+ * Rune literals ('"') are also consumed so they can be masked.
+ */
+export function readGoString(
+	text: string,
+	offset: number,
+): StringLiteral | undefined {
+	const quote = text[offset];
+	if (quote === '`') {
+		const close = text.indexOf('`', offset + 1);
+		if (close === -1) {
+			return undefined;
+		}
+		return Object.freeze({
+			// Carriage returns are discarded from raw string values
+			value: text.slice(offset + 1, close).replace(/\r/g, ''),
+			start: offset,
+			end: close + 1,
+			raw: true,
+		});
+	}
+	if (quote !== '"' && quote !== "'") {
+		return undefined;
+	}
+
+	let value = '';
+	let i = offset + 1;
+	while (i < text.length) {
+		const char = text[i] || '';
+		if (char === quote) {
+			return Object.freeze({
+				value,
+				start: offset,
+				end: i + 1,
+				raw: false,
+				character: quote === "'",
+			});
+		}
+		if (char === '\n') {
+			return undefined;
+		}
+		if (char !== '\\') {
+			value += char;
+			i++;
+			continue;
+		}
+
+		const next = text[i + 1] || '';
+		const octal = /^[0-7]{3}/.exec(text.slice(i + 1, i + 4));
+		if (octal) {
+			value += String.fromCharCode(Number.parseInt(octal[0], 8));
+			i += 4;
+			continue;
+		}
+		const hexLengths: Record<string, number> = { x: 2, u: 4, U: 8 };
+		const hexLength = hexLengths[next];
+		const hex = hexLength
+			? new RegExp(`^[0-9A-Fa-f]{${hexLength}}`).exec(text.slice(i + 2))
+			: null;
+		if (hex) {
+			value += String.fromCodePoint(Number.parseInt(hex[0], 16));
+			i += 2 + hex[0].length;
+			continue;
+		}
+
+		const control: Record<string, string> = {
+			a: '\x07',
+			b: '\b',
+			f: '\f',
+			v: '\v',
+		};
+		value += control[next] ?? decodeSimpleEscape(next);
+		i += 2;
+	}
+
+	return undefined;
+}
+
+/**
+ * Read a Java string literal: "..." or a """ text block
+ * Text blocks have their incidental indentation removed like javac does.
+ */
+export function readJavaString(
+	text: string,
+	offset: number,
+): StringLiteral | undefined {
+	if (text[offset] === "'") {
+		return readQuotedChar(text, offset);
+	}
+	if (text[offset] !== '"') {
+		return undefined;
+	}
+
+	if (text.startsWith('"""', offset)) {
+		const opening = /^"""[ \t\f]*\r?\n/.exec(text.slice(offset));
+		const close = opening
+			? findUnescaped(text, '"""', offset + opening[0].length)
+			: -1;
+		if (!opening || close === -1) {
+			return undefined;
+		}
+		const content = stripIncidentalIndent(
+			text.slice(offset + opening[0].length, close),
+		);
+		return Object.freeze({
+			value: decodeJavaEscapes(content),
+			start: offset,
+			end: close + 3,
+			raw: false,
+		});
+	}
+
+	const close = findUnescaped(text, '"', offset + 1);
+	if (close === -1 || text.slice(offset, close).includes('\n')) {
+		return undefined;
+	}
+	return Object.freeze({
+		value: decodeJavaEscapes(text.slice(offset + 1, close)),
+		start: offset,
+		end: close + 1,
+		raw: false,
+	});
+}
+
+/**
+ * Read a Kotlin string literal: "..." or a """raw""" string
+ * Literals containing $name or ${...} templates are flagged as interpolated.
+ */
+export function readKotlinString(
+	text: string,
+	offset: number,
+): StringLiteral | undefined {
+	if (text[offset] === "'") {
+		return readQuotedChar(text, offset);
+	}
+	if (text[offset] !== '"') {
+		return undefined;
+	}
+
+	const template = /\$(?:[A-Za-z_{])/;
+	if (text.startsWith('"""', offset)) {
+		let close = text.indexOf('"""', offset + 3);
+		if (close === -1) {
+			return undefined;
+		}
+		// Extra quotes before the delimiter belong to the content
+		while (text[close + 3] === '"') {
+			close++;
+		}
+		const value = text.slice(offset + 3, close);
+		return Object.freeze({
+			value,
+			start: offset,
+			end: close + 3,
+			raw: true,
+			interpolated: template.test(value),
+		});
+	}
+
+	const close = findUnescaped(text, '"', offset + 1);
+	if (close === -1 || text.slice(offset, close).includes('\n')) {
+		return undefined;
+	}
+	const body = text.slice(offset + 1, close);
+	return Object.freeze({
+		value: decodeJavaEscapes(body),
+		start: offset,
+		end: close + 1,
+		raw: false,
+		interpolated: template.test(body.replace(/\\\$/g, '')),
+	});
+}
+
+/**
+ * Read a C# string literal: "...", @"verbatim", """raw""" and their
+ * $-interpolated forms
+ */
+export function readCSharpString(
+	text: string,
+	offset: number,
+): StringLiteral | undefined {
+	if (text[offset] === "'") {
+		return readQuotedChar(text, offset);
+	}
+
+	const prefix = /^(?:\$+@?|@\$*)?/.exec(text.slice(offset, offset + 8))?.[0];
+	const quoteStart = offset + (prefix?.length ?? 0);
+	if (text[quoteStart] !== '"') {
+		return undefined;
+	}
+	const dollars = prefix?.replace('@', '').length ?? 0;
+	const interpolation = '{'.repeat(Math.max(dollars, 1));
+
+	const quotes = /^"{3,}/.exec(text.slice(quoteStart))?.[0];
+	if (quotes) {
+		const close = text.indexOf(quotes, quoteStart + quotes.length);
+		if (close === -1) {
+			return undefined;
+		}
+		let value = text.slice(quoteStart + quotes.length, close);
+		if (value.includes('\n')) {
+			// Multi-line raw strings drop the delimiter lines and the
+			// indentation of the closing delimiter
+			const lines = value.split(/\r?\n/);
+			const indent = lines.pop() ?? '';
+			value = lines
+				.slice(1)
+				.map((line) =>
+					line.startsWith(indent) ? line.slice(indent.length) : line,
+				)
+				.join('\n');
+		}
+		return Object.freeze({
+			value,
+			start: offset,
+			end: close + quotes.length,
+			raw: true,
+			interpolated: dollars > 0 && value.includes(interpolation),
+		});
+	}
+
+	const verbatim = prefix?.includes('@') ?? false;
+	let value = '';
+	let interpolated = false;
+	let i = quoteStart + 1;
+	while (i < text.length) {
+		const char = text[i] || '';
+		if (char === '"') {
+			if (verbatim && text[i + 1] === '"') {
+				value += '"';
+				i += 2;
+				continue;
+			}
+			return Object.freeze({
+				value,
+				start: offset,
+				end: i + 1,
+				raw: verbatim,
+				interpolated,
+			});
+		}
+		if (char === '\n' && !verbatim) {
+			return undefined;
+		}
+		if (dollars > 0 && (char === '{' || char === '}')) {
+			if (text[i + 1] === char) {
+				value += char;
+				i += 2;
+				continue;
+			}
+			interpolated = true;
+		}
+		if (char !== '\\' || verbatim) {
+			value += char;
+			i++;
+			continue;
+		}
+
+		const next = text[i + 1] || '';
+		const hexDigits: Record<string, RegExp> = {
+			x: /^[0-9A-Fa-f]{1,4}/,
+			u: /^[0-9A-Fa-f]{4}/,
+			U: /^[0-9A-Fa-f]{8}/,
+		};
+		const hex = hexDigits[next]?.exec(text.slice(i + 2));
+		if (hex) {
+			value += String.fromCodePoint(Number.parseInt(hex[0], 16));
+			i += 2 + hex[0].length;
+			continue;
+		}
+
+		const control: Record<string, string> = {
+			a: '\x07',
+			b: '\b',
+			e: '\x1b',
+			f: '\f',
+			v: '\v',
+		};
+		value += control[next] ?? decodeSimpleEscape(next);
+		i += 2;
+	}
+
+	return undefined;
+}
+
+/**
+ * Read a JavaScript string literal: '...', "..." or a `template`
+ * Templates with ${...} substitutions are flagged as interpolated. Unknown
+ * escapes drop their backslash, so '\d' is just "d" as in the engine.
+ */
+export function readJavaScriptString(
+	text: string,
+	offset: number,
+): StringLiteral | undefined {
+	const quote = text[offset];
+	if (quote !== "'" && quote !== '"' && quote !== '`') {
+		return undefined;
+	}
+
+	let value = '';
+	let interpolated = false;
+	let i = offset + 1;
+	while (i < text.length) {
+		const char = text[i] || '';
+		if (char === quote) {
+			return Object.freeze({
+				value,
+				start: offset,
+				end: i + 1,
+				raw: false,
+				interpolated,
+			});
+		}
+		if (quote === '`' && char === '$' && text[i + 1] === '{') {
+			interpolated = true;
+		}
+		if (char === '\n' && quote !== '`') {
+			return undefined;
+		}
+		if (char === '\r' && quote === '`') {
+			// Template line breaks are normalized to \n
+			value += '\n';
+			i += text[i + 1] === '\n' ? 2 : 1;
+			continue;
+		}
+		if (char !== '\\') {
+			value += char;
+			i++;
+			continue;
+		}
+
+		const next = text[i + 1] || '';
+		const continuation = /^(?:\r\n|[\r\n\u2028\u2029])/.exec(
+			text.slice(i + 1, i + 3),
+		);
+		if (continuation) {
+			i += 1 + continuation[0].length;
+			continue;
+		}
+		const hexDigits: Record<string, RegExp> = {
+			x: /^[0-9A-Fa-f]{2}/,
+			u: /^(?:[0-9A-Fa-f]{4}|\{[0-9A-Fa-f]+\})/,
+		};
+		const hex = hexDigits[next]?.exec(text.slice(i + 2));
+		if (hex) {
+			value += String.fromCodePoint(
+				Number.parseInt(hex[0].replace(/[{}]/g, ''), 16),
+			);
+			i += 2 + hex[0].length;
+			continue;
+		}
+		// Legacy octal escapes are only allowed outside templates
+		const octal =
+			quote === '`'
+				? null
+				: /^(?:[0-3][0-7]{0,2}|[4-7][0-7]?)/.exec(text.slice(i + 1, i + 4));
+		if (octal) {
+			value += String.fromCharCode(Number.parseInt(octal[0], 8));
+			i += 1 + octal[0].length;
+			continue;
+		}
+
+		const control: Record<string, string> = {
+			n: '\n',
+			r: '\r',
+			t: '\t',
+			b: '\b',
+			f: '\f',
+			v: '\v',
+		};
+		value += control[next] ?? next;
+		i += 2;
+	}
+
+	return undefined;
+}
+
+/**
+ * Decode the escapes of Java string literals; Kotlin shares them and adds \$
+ */
+function decodeJavaEscapes(body: string): string {
+	let value = '';
+	let i = 0;
+	while (i < body.length) {
+		const char = body[i] || '';
+		if (char !== '\\') {
+			value += char;
+			i++;
+			continue;
+		}
+
+		const next = body[i + 1] || '';
+		if (next === '\n') {
+			// Text block line continuation
+			i += 2;
+			continue;
+		}
+		const octal = /^(?:[0-3][0-7]{2}|[0-7]{1,2})/.exec(body.slice(i + 1));
+		if (octal) {
+			value += String.fromCharCode(Number.parseInt(octal[0], 8));
+			i += 1 + octal[0].length;
+			continue;
+		}
+		const unicode = /^u+([0-9A-Fa-f]{4})/.exec(body.slice(i + 1));
+		if (unicode) {
+			value += String.fromCharCode(Number.parseInt(unicode[1] || '', 16));
+			i += 1 + unicode[0].length;
+			continue;
+		}
+
+		const control: Record<string, string> = {
+			b: '\b',
+			f: '\f',
+			s: ' ',
+			$: '$',
+		};
+		value += control[next] ?? decodeSimpleEscape(next);
+		i += 2;
+	}
+	return value;
+}
+
+/**
+ * Remove the common leading whitespace of a text block and the trailing
+ * whitespace of each line; the closing delimiter line takes part too
+ */
+function stripIncidentalIndent(content: string): string {
+	const lines = content.split(/\r?\n/);
+	const significant = lines.filter(
+		(line, index) => line.trim() !== '' || index === lines.length - 1,
+	);
+	const indent = Math.min(
+		...significant.map((line) => /^[ \t]*/.exec(line)?.[0].length ?? 0),
+	);
+	return lines
+		.map((line) => (line.trim() === '' ? '' : line.slice(indent)))
+		.map((line) => line.replace(/[ \t]+$/, ''))
+		.join('\n');
+}
+
+/**
+ * Find the next occurrence of delimiter that is not preceded by an escape
+ */
+function findUnescaped(text: string, delimiter: string, from: number): number {
+	let i = from;
+	while (i < text.length) {
+		if (text[i] === '\\') {
+			i += 2;
+			continue;
+		}
+		if (text.startsWith(delimiter, i)) {
+			return i;
+		}
+		i++;
+	}
+	return -1;
+}
+
+/**
+ * Read a 'c' character literal so quotes inside it are not mistaken for
+ * the start of a string
+ */
+function readQuotedChar(
+	text: string,
+	offset: number,
+): StringLiteral | undefined {
+	const charLiteral =
+		/^'(?:\\(?:u[0-9A-Fa-f]{4}|x[0-9A-Fa-f]{1,4}|[^\n])|[^'\\\n])'/;
+	const match = charLiteral.exec(text.slice(offset, offset + 12));
+	if (!match) {
+		return undefined;
+	}
+	return Object.freeze({
+		value: match[0].slice(1, -1),
+		start: offset,
+		end: offset + match[0].length,
+		raw: true,
+		character: true,
+	});
+}
@@ -0,0 +1,308 @@
+import * as vscode from 'vscode';
+import * as nls from 'vscode-nls';
+import type { ScannedFile } from '../commands/scanWorkspace';
+import { groupPatterns } from '../extraction/regex/extractPatterns';
+import {
+	compareFileRisk,
+	type InventoryEntry,
+	inventoryFile,
+} from '../extraction/regex/inventory';
+
+const localize = nls.config({ messageFormat: nls.MessageFormat.file })();
+
+/**
+ * A scanned file, with its patterns as children
+ */
+export interface InventoryFileNode {
+	readonly kind: 'file';
+	readonly file: ScannedFile;
+}
+
+/**
+ * One unique pattern in a file, with its occurrences as children when it
+ * appears more than once
+ */
+export interface InventoryPatternNode {
+	readonly kind: 'pattern';
+	readonly uri: vscode.Uri;
+	readonly entries: readonly InventoryEntry[];
+}
+
+export interface InventoryOccurrenceNode {
+	readonly kind: 'occurrence';
+	readonly uri: vscode.Uri;
+	readonly entry: InventoryEntry;
+}
+
+export type InventoryNode =
+	| InventoryFileNode
+	| InventoryPatternNode
+	| InventoryOccurrenceNode;
+
+export interface InventoryTree extends vscode.TreeDataProvider<InventoryNode> {
+	setFiles(files: readonly ScannedFile[]): void;
+	/** Re-analyse a saved document that is already in the inventory */
+	updateDocument(
+		document: vscode.TextDocument,
+		redosDetectionEnabled: boolean,
+	): void;
+	dispose(): void;
+}
+
+/**
+ * Create the data provider behind the regex inventory view
+ * Files are ordered riskiest first; patterns keep their source order.
+ */
+export function createInventoryTree(): InventoryTree {
+	const changeEmitter = new vscode.EventEmitter<InventoryNode | undefined>();
+	let files: readonly ScannedFile[] = [];
+
+	return Object.freeze({
+		onDidChangeTreeData: changeEmitter.event,
+
+		setFiles(scanned: readonly ScannedFile[]): void {
+			files = scanned.filter((file) => file.inventory.entries.length > 0);
+			changeEmitter.fire(undefined);
+		},
+
+		updateDocument(
+			document: vscode.TextDocument,
+			redosDetectionEnabled: boolean,
+		): void {
+			const key = document.uri.toString();
+			const index = files.findIndex((file) => file.uri.toString() === key);
+			const existing = files[index];
+			if (!existing) {
+				return;
+			}
+
+			const inventory = inventoryFile(
+				existing.inventory.file,
+				document.getText(),
+				document.languageId,
+				{ redosDetectionEnabled },
+			);
+			const updated = [...files];
+			if (inventory.entries.length > 0) {
+				updated[index] = Object.freeze({ uri: existing.uri, inventory });
+			} else {
+				updated.splice(index, 1);
+			}
+			files = updated;
+			changeEmitter.fire(undefined);
+		},
+
+		getTreeItem(node: InventoryNode): vscode.TreeItem {
+			switch (node.kind) {
+				case 'file':
+					return fileItem(node);
+				case 'pattern':
+					return patternItem(node);
+				default:
+					return occurrenceItem(node);
+			}
+		},
+
+		getChildren(node?: InventoryNode): InventoryNode[] {
+			if (!node) {
+				return [...files]
+					.sort((a, b) => compareFileRisk(a.inventory, b.inventory))
+					.map((file): InventoryNode => ({ kind: 'file', file }));
+			}
+			if (node.kind === 'file') {
+				const uri = node.file.uri;
+				return groupEntries(node.file.inventory.entries).map(
+					(entries): InventoryNode => ({ kind: 'pattern', uri, entries }),
+				);
+			}
+			if (node.kind === 'pattern' && node.entries.length > 1) {
+				const uri = node.uri;
+				return node.entries.map(
+					(entry): InventoryNode => ({ kind: 'occurrence', uri, entry }),
+				);
+			}
+			return [];
+		},
+
+		dispose(): void {
+			changeEmitter.dispose();
+		},
+	});
+}
+
+/**
+ * Arguments for revealing an occurrence in its source file
+ */
+export function revealArguments(
+	uri: vscode.Uri,
+	entry: InventoryEntry,
+): [vscode.Uri, vscode.TextDocumentShowOptions] {
+	const p = entry.pattern;
+	return [
+		uri,
+		{
+			selection: new vscode.Range(
+				p.line - 1,
+				p.column - 1,
+				p.endLine - 1,
+				p.endColumn - 1,
+			),
+		},
+	];
+}
+
+function fileItem(node: InventoryFileNode): vscode.TreeItem {
+	const entries = node.file.inventory.entries;
+	const item = new vscode.TreeItem(
+		node.file.uri,
+		vscode.TreeItemCollapsibleState.Collapsed,
+	);
+	item.label = node.file.inventory.file;
+	const high = entries.filter((entry) => entry.risk === 'high').length;
+	item.description =
+		high > 0
+			? localize(
+					'runtime.inventory.file.high-risk',
+					'{0} pattern(s), {1} high risk',
+					entries.length,
+					high,
+				)
+			: localize(
+					'runtime.inventory.file.patterns',
+					'{0} pattern(s)',
+					entries.length,
+				);
+	item.iconPath = vscode.ThemeIcon.File;
+	item.contextValue = 'regexFile';
+	return item;
+}
+
+function patternItem(node: InventoryPatternNode): vscode.TreeItem {
+	const first = node.entries[0];
+	const item = new vscode.TreeItem(
+		first ? `/${first.pattern.pattern}/${first.pattern.flags}` : '',
+		node.entries.length > 1
+			? vscode.TreeItemCollapsibleState.Collapsed
+			: vscode.TreeItemCollapsibleState.None,
+	);
+	if (!first) {
+		return item;
+	}
+
+	const where =
+		node.entries.length > 1
+			? localize(
+					'runtime.inventory.pattern.occurrences',
+					'{0} occurrences',
+					node.entries.length,
+				)
+			: localize('runtime.inventory.line', 'Line {0}', first.pattern.line);
+	item.description =
+		first.pattern.dialect === 'javascript'
+			? where
+			: `${where} · ${first.pattern.dialect}`;
+	item.iconPath = statusIcon(node.entries);
+	item.tooltip = entryTooltip(node.entries);
+	item.contextValue = 'regexPattern';
+	if (node.entries.length === 1) {
+		item.command = revealCommand(node.uri, first);
+	}
+	return item;
+}
+
+function occurrenceItem(node: InventoryOccurrenceNode): vscode.TreeItem {
+	const p = node.entry.pattern;
+	const item = new vscode.TreeItem(
+		localize('runtime.inventory.line', 'Line {0}', `${p.line}:${p.column}`),
+		vscode.TreeItemCollapsibleState.None,
+	);
+	if (p.partial) {
+		item.description = localize(
+			'runtime.inventory.partial',
+			'partially resolved',
+		);
+	}
+	item.iconPath = statusIcon([node.entry]);
+	item.tooltip = entryTooltip([node.entry]);
+	item.contextValue = 'regexOccurrence';
+	item.command = revealCommand(node.uri, node.entry);
+	return item;
+}
+
+function revealCommand(
+	uri: vscode.Uri,
+	entry: InventoryEntry,
+): vscode.Command {
+	return {
+		title: localize('runtime.inventory.reveal', 'Reveal in Source'),
+		command: 'vscode.open',
+		arguments: revealArguments(uri, entry),
+	};
+}
+
+/**
+ * Icon for validity first, then the worst ReDoS severity
+ */
+function statusIcon(entries: readonly InventoryEntry[]): vscode.ThemeIcon {
+	if (entries.some((entry) => !entry.valid)) {
+		return new vscode.ThemeIcon(
+			'error',
+			new vscode.ThemeColor('problemsErrorIcon.foreground'),
+		);
+	}
+	const severities = entries.map((entry) => entry.redosSeverity);
+	if (severities.includes('high')) {
+		return new vscode.ThemeIcon(
+			'warning',
+			new vscode.ThemeColor('problemsErrorIcon.foreground'),
+		);
+	}
+	if (severities.includes('medium')) {
+		return new vscode.ThemeIcon(
+			'warning',
+			new vscode.ThemeColor('problemsWarningIcon.foreground'),
+		);
+	}
+	if (severities.includes('low')) {
+		return new vscode.ThemeIcon(
+			'info',
+			new vscode.ThemeColor('problemsInfoIcon.foreground'),
+		);
+	}
+	return new vscode.ThemeIcon(
+		'pass',
+		new vscode.ThemeColor('testing.iconPassed'),
+	);
+}
+
+function entryTooltip(
+	entries: readonly InventoryEntry[],
+): vscode.MarkdownString {
+	const reasons = [...new Set(entries.flatMap((entry) => entry.reasons))];
+	const tooltip = new vscode.MarkdownString();
+	tooltip.appendMarkdown(
+		entries.every((entry) => entry.valid)
+			? localize('runtime.inventory.valid', '✅ Valid')
+			: localize('runtime.inventory.invalid', '❌ Invalid'),
+	);
+	for (const reason of reasons) {
+		tooltip.appendMarkdown('\n\n- ');
+		tooltip.appendText(reason);
+	}
+	return tooltip;
+}
+
+/**
+ * Group a file's entries by pattern, flags and dialect in source order
+ */
+function groupEntries(
+	entries: readonly InventoryEntry[],
+): readonly (readonly InventoryEntry[])[] {
+	const entryOf = new Map(entries.map((entry) => [entry.pattern, entry]));
+	return groupPatterns(entries.map((entry) => entry.pattern)).map((group) =>
+		group.occurrences.flatMap((pattern) => {
+			const entry = entryOf.get(pattern);
+			return entry ? [entry] : [];
+		}),
+	);
+}
@@ -0,0 +1,43 @@
+import * as vscode from 'vscode';
+
+export const REPLACE_PREVIEW_SCHEME = 'regex-le-preview';
+
+export interface ReplacePreview extends vscode.TextDocumentContentProvider {
+	/** Store the replaced text of a file and return a URI showing it */
+	show(source: vscode.Uri, content: string): vscode.Uri;
+	dispose(): void;
+}
+
+/**
+ * Create the read-only documents shown on the right of a replace diff
+ * Each file keeps one preview at a time; showing a new one updates an
+ * open diff in place.
+ */
+export function createReplacePreview(): ReplacePreview {
+	const changeEmitter = new vscode.EventEmitter<vscode.Uri>();
+	const contents = new Map<string, string>();
+
+	return Object.freeze({
+		onDidChange: changeEmitter.event,
+
+		show(source: vscode.Uri, content: string): vscode.Uri {
+			const uri = vscode.Uri.from({
+				scheme: REPLACE_PREVIEW_SCHEME,
+				path: source.path,
+				query: source.toString(),
+			});
+			contents.set(uri.toString(), content);
+			changeEmitter.fire(uri);
+			return uri;
+		},
+
+		provideTextDocumentContent(uri: vscode.Uri): string {
+			return contents.get(uri.toString()) ?? '';
+		},
+
+		dispose(): void {
+			contents.clear();
+			changeEmitter.dispose();
+		},
+	});
+}
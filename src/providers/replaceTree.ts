@@ -0,0 +1,245 @@
+import * as vscode from 'vscode';
+import * as nls from 'vscode-nls';
+import { createLineIndex, type LineIndex } from '../extraction/regex/lineIndex';
+import type { Replacement } from '../extraction/regex/replace';
+import { positionAt } from '../extraction/regex/sourceScanner';
+
+const localize = nls.config({ messageFormat: nls.MessageFormat.file })();
+
+/**
+ * A file with matches, and the text the replacements were planned for
+ */
+export interface ReplaceFile {
+	readonly uri: vscode.Uri;
+	readonly file: string; // Workspace-relative path
+	readonly text: string;
+	readonly replacements: readonly Replacement[];
+	readonly limited?: boolean | undefined; // Hit the match limit; later matches are not listed
+}
+
+export interface ReplaceFileNode {
+	readonly kind: 'file';
+	readonly file: ReplaceFile;
+}
+
+export interface ReplaceMatchNode {
+	readonly kind: 'match';
+	readonly file: ReplaceFile;
+	readonly index: number; // Into file.replacements
+}
+
+export type ReplaceNode = ReplaceFileNode | ReplaceMatchNode;
+
+export interface ReplaceTree extends vscode.TreeDataProvider<ReplaceNode> {
+	setFiles(files: readonly ReplaceFile[]): void;
+	/** Record a checkbox change; a file checks or unchecks all its matches */
+	setChecked(node: ReplaceNode, checked: boolean): void;
+	/** Files with their checked replacements, leaving out unchecked files */
+	checkedFiles(): readonly ReplaceFile[];
+	dispose(): void;
+}
+
+/**
+ * Create the data provider behind the workspace replace preview
+ * Every match starts checked except those of files the match limit cut
+ * short, whose edit would be partial; unchecked ones are left out when
+ * applying.
+ */
+export function createReplaceTree(): ReplaceTree {
+	const changeEmitter = new vscode.EventEmitter<ReplaceNode | undefined>();
+	let files: readonly ReplaceFile[] = [];
+	const unchecked = new Set<string>();
+	// Built when a file's matches are first shown, for their editor ranges
+	const lineIndexes = new WeakMap<ReplaceFile, LineIndex>();
+
+	const linesOf = (file: ReplaceFile): LineIndex => {
+		let lines = lineIndexes.get(file);
+		if (!lines) {
+			lines = createLineIndex(file.text);
+			lineIndexes.set(file, lines);
+		}
+		return lines;
+	};
+
+	const isChecked = (file: ReplaceFile, index: number): boolean =>
+		!unchecked.has(matchKey(file, index));
+
+	return Object.freeze({
+		onDidChangeTreeData: changeEmitter.event,
+
+		setFiles(replaced: readonly ReplaceFile[]): void {
+			files = replaced;
+			unchecked.clear();
+			for (const file of replaced.filter((file) => file.limited)) {
+				file.replacements.forEach((_replacement, index) => {
+					unchecked.add(matchKey(file, index));
+				});
+			}
+			changeEmitter.fire(undefined);
+		},
+
+		setChecked(node: ReplaceNode, checked: boolean): void {
+			const indexes =
+				node.kind === 'file'
+					? node.file.replacements.map((_replacement, index) => index)
+					: [node.index];
+			for (const index of indexes) {
+				const key = matchKey(node.file, index);
+				if (checked) {
+					unchecked.delete(key);
+				} else {
+					unchecked.add(key);
+				}
+			}
+			changeEmitter.fire(undefined);
+		},
+
+		checkedFiles(): readonly ReplaceFile[] {
+			const checked: ReplaceFile[] = [];
+			for (const file of files) {
+				const replacements = file.replacements.filter((_replacement, index) =>
+					isChecked(file, index),
+				);
+				if (replacements.length > 0) {
+					checked.push(Object.freeze({ ...file, replacements }));
+				}
+			}
+			return Object.freeze(checked);
+		},
+
+		getTreeItem(node: ReplaceNode): vscode.TreeItem {
+			if (node.kind === 'file') {
+				const count = node.file.replacements.filter((_replacement, index) =>
+					isChecked(node.file, index),
+				).length;
+				return fileItem(node, count);
+			}
+			return matchItem(
+				node,
+				isChecked(node.file, node.index),
+				linesOf(node.file),
+			);
+		},
+
+		getChildren(node?: ReplaceNode): ReplaceNode[] {
+			if (!node) {
+				return files.map((file): ReplaceNode => ({ kind: 'file', file }));
+			}
+			if (node.kind === 'file') {
+				const file = node.file;
+				return file.replacements.map(
+					(_replacement, index): ReplaceNode => ({
+						kind: 'match',
+						file,
+						index,
+					}),
+				);
+			}
+			return [];
+		},
+
+		dispose(): void {
+			changeEmitter.dispose();
+		},
+	});
+}
+
+function matchKey(file: ReplaceFile, index: number): string {
+	return `${file.uri.toString()}#${index}`;
+}
+
+function fileItem(
+	node: ReplaceFileNode,
+	checkedCount: number,
+): vscode.TreeItem {
+	const item = new vscode.TreeItem(
+		node.file.uri,
+		vscode.TreeItemCollapsibleState.Expanded,
+	);
+	item.label = node.file.file;
+	item.description = node.file.limited
+		? localize(
+				'runtime.workspace-replace.file.limited',
+				'{0} of first {1} selected (match limit reached)',
+				checkedCount,
+				node.file.replacements.length,
+			)
+		: localize(
+				'runtime.workspace-replace.file.matches',
+				'{0} of {1} selected',
+				checkedCount,
+				node.file.replacements.length,
+			);
+	if (node.file.limited) {
+		item.tooltip = localize(
+			'runtime.workspace-replace.file.limited.tooltip',
+			'This file reached the match limit. Only its first {0} matches are listed, so applying would leave the rest unchanged.',
+			node.file.replacements.length,
+		);
+	}
+	item.iconPath = vscode.ThemeIcon.File;
+	item.checkboxState =
+		checkedCount > 0
+			? vscode.TreeItemCheckboxState.Checked
+			: vscode.TreeItemCheckboxState.Unchecked;
+	item.contextValue = 'regexReplaceFile';
+	item.command = {
+		title: localize('runtime.workspace-replace.preview', 'Preview File'),
+		command: 'regex-le.workspaceReplace.preview',
+		arguments: [node],
+	};
+	return item;
+}
+
+function matchItem(
+	node: ReplaceMatchNode,
+	checked: boolean,
+	lines: LineIndex,
+): vscode.TreeItem {
+	const replacement = node.file.replacements[node.index];
+	const label = replacement
+		? `${oneLine(replacement.original)} → ${oneLine(replacement.replacement)}`
+		: '';
+	const item = new vscode.TreeItem(
+		label,
+		vscode.TreeItemCollapsibleState.None,
+	);
+	item.checkboxState = checked
+		? vscode.TreeItemCheckboxState.Checked
+		: vscode.TreeItemCheckboxState.Unchecked;
+	item.contextValue = 'regexReplaceMatch';
+	if (!replacement) {
+		return item;
+	}
+
+	item.description = localize(
+		'runtime.workspace-replace.line',
+		'Line {0}',
+		replacement.line,
+	);
+	const start = positionAt(lines, replacement.start);
+	const end = positionAt(lines, replacement.end);
+	item.command = {
+		title: localize('runtime.workspace-replace.reveal', 'Reveal in Source'),
+		command: 'vscode.open',
+		arguments: [
+			node.file.uri,
+			{
+				selection: new vscode.Range(
+					start.line - 1,
+					start.column - 1,
+					end.line - 1,
+					end.column - 1,
+				),
+			},
+		],
+	};
+	return item;
+}
+
+/**
+ * Show line breaks as ⏎ so multi-line matches fit in one label
+ */
+function oneLine(text: string): string {
+	return text.replace(/\r?\n/g, '⏎');
+}
@@ -6,6 +6,7 @@
 import { readFileSync } from 'node:fs';
 import { join } from 'node:path';
 import { describe, expect, it } from 'vitest';
+import { extractRegexPatterns } from './extraction/regex/extractPatterns';
 import { testRegexPattern } from './extraction/regex/regexTest';
 
 const SAMPLE_DIR = join(process.cwd(), 'sample');
@@ -44,6 +45,32 @@ describe('Sample Files Integration', () => {
 			expect(result.success).toBe(true);
 			expect(result.matches.length).toBeGreaterThan(0);
 		});
+
+		it('should extract only genuine regex literals from app.js', () => {
+			const content = readSampleFile('app.js');
+			const patterns = extractRegexPatterns(content, 'javascript');
+
+			// Comments, URL strings and division are not patterns; constructor
+			// strings are unescaped to the pattern the engine sees
+			expect(patterns.map((p) => p.pattern)).toEqual([
+				'\\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\\.[A-Z|a-z]{2,}\\b',
+				'^\\+?[\\d\\s\\-\\(\\)]+$',
+				'^v(\\d+)\\.(\\d+)$',
+				'^(\\d{4}-\\d{2}-\\d{2}) (INFO|WARN|ERROR) (.*)$',
+				'^\\/docs\\/[^/]+\\/?$',
+			]);
+		});
+
+		it('should join the multi-line constructor call in app.js', () => {
+			const content = readSampleFile('app.js');
+			const patterns = extractRegexPatterns(content, 'javascript');
+			const logLine = patterns[3];
+
+			expect(logLine?.flags).toBe('m');
+			expect(logLine?.line).toBe(43);
+			expect(logLine?.endLine).toBe(48);
+			expect(logLine?.partial).toBeUndefined();
+		});
 	});
 
 	describe('TypeScript files', () => {
@@ -72,6 +99,14 @@ describe('Sample Files Integration', () => {
 			expect(result.success).toBe(true);
 			expect(result.matches.length).toBeGreaterThan(0);
 		});
+
+		it('should extract only genuine regex literals from app.ts', () => {
+			const content = readSampleFile('app.ts');
+			const patterns = extractRegexPatterns(content, 'typescript');
+
+			expect(patterns.map((p) => p.match)).toEqual(['/\\/+/']);
+			expect(patterns[0]?.line).toBe(60);
+		});
 	});
 
 	describe('Python files', () => {
@@ -90,6 +125,17 @@ describe('Sample Files Integration', () => {
 			expect(result.success).toBe(true);
 			expect(result.matches.length).toBeGreaterThan(0);
 		});
+
+		it('should extract re module calls from app.py', () => {
+			const content = readSampleFile('app.py');
+			const patterns = extractRegexPatterns(content, 'python');
+
+			expect(patterns.length).toBe(5);
+			expect(patterns.every((p) => p.dialect === 'python')).toBe(true);
+			expect(patterns.map((p) => p.flags)).toEqual(['m', 'im', 'x', '', 'i']);
+			// Concatenated literals are joined into one pattern
+			expect(patterns[1]?.pattern).toContain('(?P<message>.*)$');
+		});
 	});
 
 	describe('Go files', () => {
@@ -108,6 +154,17 @@ describe('Sample Files Integration', () => {
 			expect(result.success).toBe(true);
 			expect(result.matches.length).toBeGreaterThan(0);
 		});
+
+		it('should extract regexp calls from app.go', () => {
+			const content = readSampleFile('app.go');
+			const patterns = extractRegexPatterns(content, 'go');
+
+			expect(patterns.length).toBe(4);
+			expect(patterns.every((p) => p.dialect === 'go')).toBe(true);
+			// Interpreted and raw literals decode to the same pattern syntax
+			expect(patterns[1]?.pattern).toBe('^v?(\\d+)\\.(\\d+)\\.(\\d+)$');
+			expect(patterns[3]?.pattern).toBe('^/api/users/\\d+$');
+		});
 	});
 
 	describe('Rust files', () => {
@@ -126,6 +183,17 @@ describe('Sample Files Integration', () => {
 			expect(result.success).toBe(true);
 			expect(result.matches.length).toBeGreaterThan(0);
 		});
+
+		it('should extract regex construction sites from app.rs', () => {
+			const content = readSampleFile('app.rs');
+			const patterns = extractRegexPatterns(content, 'rust');
+
+			expect(patterns.length).toBe(5);
+			expect(patterns.every((p) => p.dialect === 'rust')).toBe(true);
+			expect(patterns.some((p) => p.flags === 'im')).toBe(true);
+			// Path strings must not be mistaken for /.../ literals
+			expect(patterns.some((p) => p.pattern.startsWith('api/'))).toBe(false);
+		});
 	});
 
 	describe('JSON files', () => {
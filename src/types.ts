@@ -2,6 +2,27 @@
  * Core type definitions for regex-le extension
  */
 
+/**
+ * Regex engine a pattern was written for
+ */
+export type RegexDialect =
+	| 'javascript'
+	| 'rust'
+	| 'python'
+	| 'go'
+	| 'java'
+	| 'dotnet';
+
+/**
+ * Document formats the Extract command can write
+ */
+export type ExtractOutputFormat = 'text' | 'json' | 'csv' | 'tsv' | 'markdown';
+
+/**
+ * Document formats the Extract Matches command can write
+ */
+export type MatchOutputFormat = 'csv' | 'tsv' | 'json';
+
 export interface RegexTestResult {
 	readonly success: boolean;
 	readonly pattern: string;
@@ -12,6 +33,8 @@ export interface RegexTestResult {
 	readonly performance?: PerformanceMetrics | undefined;
 	readonly redosDetected?: boolean | undefined;
 	readonly redosSeverity?: 'low' | 'medium' | 'high' | undefined;
+	readonly truncated?: boolean | undefined; // Matching stopped at maxMatches
+	readonly totalMatches?: number | undefined; // Including those past the limit
 }
 
 export interface RegexMatch {
@@ -19,7 +42,7 @@ export interface RegexMatch {
 	readonly index: number;
 	readonly groups?: readonly RegexGroup[] | undefined;
 	readonly line?: number | undefined;
-	readonly column?: number | undefined;
+	readonly column?: number | undefined; // 0-based, in code points
 }
 
 export interface RegexGroup {
@@ -27,7 +50,9 @@ export interface RegexGroup {
 	readonly name?: string | undefined;
 	readonly value: string;
 	readonly start: number;
-	readonly end: number;
+	readonly end: number; // Exclusive
+	readonly line?: number | undefined;
+	readonly column?: number | undefined; // 0-based, in code points
 }
 
 export interface RegexValidationResult {
@@ -60,7 +85,12 @@ export interface RegexExtractionResult {
 }
 
 export interface ParseError {
-	readonly type: 'parse-error' | 'validation-error' | 'redos-error';
+	readonly type:
+		| 'parse-error'
+		| 'validation-error'
+		| 'redos-error'
+		| 'timeout'
+		| 'cancelled';
 	readonly message: string;
 	readonly filepath?: string | undefined;
 	readonly line?: number | undefined;
@@ -72,6 +102,11 @@ export interface Configuration {
 	readonly copyToClipboardEnabled: boolean;
 	readonly notificationsLevel: 'all' | 'important' | 'silent';
 	readonly openResultsSideBySide: boolean;
+	readonly extractOutputFormat: ExtractOutputFormat | 'ask';
+	readonly scanInclude: string;
+	readonly scanExclude: readonly string[];
+	readonly scanRespectGitignore: boolean;
+	readonly scanMaxFiles: number;
 	readonly safetyEnabled: boolean;
 	readonly safetyFileSizeWarnBytes: number;
 	readonly safetyLargeOutputLinesThreshold: number;
@@ -0,0 +1,60 @@
+import { describe, expect, it } from 'vitest';
+import { combineGlobs, expandBraces, gitignoreToGlobs } from './ignoreGlobs';
+
+describe('gitignoreToGlobs', () => {
+	it('should match unanchored entries at any depth', () => {
+		expect(gitignoreToGlobs('node_modules/\n*.log')).toEqual([
+			'**/node_modules/**',
+			'**/*.log/**',
+			'**/*.log',
+		]);
+	});
+
+	it('should anchor entries containing a slash to the folder root', () => {
+		expect(gitignoreToGlobs('/dist\nsrc/generated/')).toEqual([
+			'dist/**',
+			'dist',
+			'src/generated/**',
+		]);
+	});
+
+	it('should scope entries of a nested .gitignore to its directory', () => {
+		expect(gitignoreToGlobs('*.log\n/out/\n', 'packages/app')).toEqual([
+			'packages/app/**/*.log/**',
+			'packages/app/**/*.log',
+			'packages/app/out/**',
+		]);
+	});
+
+	it('should skip comments, blank lines and negations', () => {
+		expect(gitignoreToGlobs('# build output\n\n!keep.js\n\\#file\n')).toEqual([
+			'**/#file/**',
+			'**/#file',
+		]);
+	});
+});
+
+describe('expandBraces', () => {
+	it('should expand nested brace groups', () => {
+		expect(expandBraces('**/*.{js,ts{,x}}')).toEqual([
+			'**/*.js',
+			'**/*.ts',
+			'**/*.tsx',
+		]);
+		expect(expandBraces('src/**')).toEqual(['src/**']);
+	});
+});
+
+describe('combineGlobs', () => {
+	it('should flatten globs into one brace group', () => {
+		const combined = combineGlobs([
+			'**/dist/**',
+			'**/*.{min.js,map}',
+			'**/dist/**',
+		]);
+
+		expect(combineGlobs([])).toBeUndefined();
+		expect(combineGlobs(['**/dist/**'])).toBe('**/dist/**');
+		expect(combined).toBe('{**/dist/**,**/*.min.js,**/*.map}');
+	});
+});
@@ -0,0 +1,113 @@
+/**
+ * Glob helpers for workspace file searches
+ * vscode.workspace.findFiles takes a single exclude pattern, and VS Code
+ * globs do not nest braces, so exclusions from settings and .gitignore are
+ * flattened into one {a,b,c} group.
+ */
+
+/**
+ * Convert .gitignore lines into workspace-relative exclude globs
+ * directory is where the .gitignore is, relative to the folder root ('' for
+ * the root itself); its entries only apply below it. Negated patterns
+ * (!keep.js) cannot be expressed as an exclusion and are left out.
+ */
+export function gitignoreToGlobs(
+	content: string,
+	directory = '',
+): readonly string[] {
+	const prefix = directory === '' ? '' : `${directory}/`;
+	const globs: string[] = [];
+	for (const rawLine of content.split(/\r?\n/)) {
+		let line = rawLine.replace(/(?<!\\)\s+$/, '');
+		if (line === '' || line.startsWith('#') || line.startsWith('!')) {
+			continue;
+		}
+		// \# and \! escape a leading comment or negation marker
+		line = line.replace(/^\\(?=[#!])/, '');
+
+		const directoryOnly = line.endsWith('/');
+		if (directoryOnly) {
+			line = line.slice(0, -1);
+		}
+		// A slash anywhere but the end anchors the pattern to the root
+		const anchored = line.includes('/');
+		line = line.replace(/^\//, '');
+		if (line === '') {
+			continue;
+		}
+
+		const base =
+			anchored || line.startsWith('**')
+				? `${prefix}${line}`
+				: `${prefix}**/${line}`;
+		globs.push(`${base}/**`);
+		if (!directoryOnly) {
+			globs.push(base);
+		}
+	}
+	return Object.freeze(globs);
+}
+
+/**
+ * Expand brace groups, e.g. src/*.{js,ts} into src/*.js and src/*.ts
+ */
+export function expandBraces(glob: string): readonly string[] {
+	const open = glob.indexOf('{');
+	const close = open === -1 ? -1 : findClosingBrace(glob, open);
+	if (close === -1) {
+		return Object.freeze([glob]);
+	}
+
+	const prefix = glob.slice(0, open);
+	const suffix = glob.slice(close + 1);
+	const expanded: string[] = [];
+	for (const alternative of splitAlternatives(glob.slice(open + 1, close))) {
+		expanded.push(...expandBraces(`${prefix}${alternative}${suffix}`));
+	}
+	return Object.freeze(expanded);
+}
+
+/**
+ * Merge globs into one pattern for findFiles, or undefined when empty
+ */
+export function combineGlobs(globs: readonly string[]): string | undefined {
+	const unique = [...new Set(globs.flatMap((glob) => expandBraces(glob)))];
+	if (unique.length <= 1) {
+		return unique[0];
+	}
+	return `{${unique.join(',')}}`;
+}
+
+function findClosingBrace(glob: string, open: number): number {
+	let depth = 0;
+	for (let i = open; i < glob.length; i++) {
+		if (glob[i] === '{') {
+			depth++;
+		} else if (glob[i] === '}') {
+			depth--;
+			if (depth === 0) {
+				return i;
+			}
+		}
+	}
+	return -1;
+}
+
+function splitAlternatives(body: string): readonly string[] {
+	const alternatives: string[] = [];
+	let depth = 0;
+	let start = 0;
+	for (let i = 0; i < body.length; i++) {
+		const char = body[i];
+		if (char === '{') {
+			depth++;
+		} else if (char === '}') {
+			depth--;
+		} else if (char === ',' && depth === 0) {
+			alternatives.push(body.slice(start, i));
+			start = i + 1;
+		}
+	}
+	alternatives.push(body.slice(start));
+	return alternatives;
+}
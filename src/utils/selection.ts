@@ -0,0 +1,105 @@
+import * as vscode from 'vscode';
+import * as nls from 'vscode-nls';
+import type { TextRange } from '../extraction/regex/regexTest';
+import type { SourceLocation } from '../extraction/regex/sourceScanner';
+
+const localize = nls.config({ messageFormat: nls.MessageFormat.file })();
+
+/**
+ * What the editor selections are used for
+ * - patterns: only patterns inside the selections are used
+ * - input: the selected text is what patterns are tested against
+ * - document: selections are ignored and the whole file is used
+ */
+export type SelectionScope = 'patterns' | 'input' | 'document';
+
+interface ScopeChoice extends vscode.QuickPickItem {
+	readonly scope: SelectionScope;
+}
+
+/**
+ * Non-empty selections of an editor, in document order
+ * Multi-cursor editing can leave selections in any order.
+ */
+export function nonEmptySelections(
+	editor: vscode.TextEditor,
+): readonly vscode.Selection[] {
+	return Object.freeze(
+		editor.selections
+			.filter((selection) => !selection.isEmpty)
+			.sort((a, b) => a.start.compareTo(b.start)),
+	);
+}
+
+/**
+ * Convert an editor range to the 1-based lines and columns of extraction
+ */
+export function toSourceLocation(range: vscode.Range): SourceLocation {
+	return Object.freeze({
+		line: range.start.line + 1,
+		column: range.start.character + 1,
+		endLine: range.end.line + 1,
+		endColumn: range.end.character + 1,
+	});
+}
+
+/**
+ * Convert an editor range to character offsets in its document
+ */
+export function toTextRange(
+	document: vscode.TextDocument,
+	range: vscode.Range,
+): TextRange {
+	return Object.freeze({
+		start: document.offsetAt(range.start),
+		end: document.offsetAt(range.end),
+	});
+}
+
+/**
+ * Ask how the selections should be used
+ * Returns undefined when the quick pick is dismissed.
+ */
+export async function pickSelectionScope(
+	selectionCount: number,
+	options: Readonly<{ allowInput: boolean }>,
+): Promise<SelectionScope | undefined> {
+	const choices: ScopeChoice[] = [
+		{
+			label: localize('runtime.selection.patterns', 'Patterns in Selection'),
+			description: localize(
+				'runtime.selection.patterns.description',
+				'Only use regex patterns inside the {0} selection(s)',
+				selectionCount,
+			),
+			scope: 'patterns',
+		},
+	];
+	if (options.allowInput) {
+		choices.push({
+			label: localize('runtime.selection.input', 'Selection as Test Input'),
+			description: localize(
+				'runtime.selection.input.description',
+				'Test a pattern from the file against the {0} selection(s)',
+				selectionCount,
+			),
+			scope: 'input',
+		});
+	}
+	choices.push({
+		label: localize('runtime.selection.document', 'Whole File'),
+		description: localize(
+			'runtime.selection.document.description',
+			'Ignore the selection',
+		),
+		scope: 'document',
+	});
+
+	const picked = await vscode.window.showQuickPick(choices, {
+		placeHolder: localize(
+			'runtime.selection.placeholder',
+			'How should the selected text be used?',
+		),
+	});
+	return picked?.scope;
+}
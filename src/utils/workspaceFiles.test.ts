@@ -0,0 +1,82 @@
+import * as vscode from 'vscode';
+import { afterEach, describe, expect, it, vi } from 'vitest';
+import type { Configuration } from '../types';
+import { findWorkspaceFiles, readWorkspaceText } from './workspaceFiles';
+
+const GITIGNORES: Record<string, string> = {
+	'/ws/.gitignore': 'node_modules/\n',
+	'/ws/packages/app/.gitignore': '/out/\n*.gen.ts\n',
+};
+
+const CONFIG = {
+	scanMaxFiles: 100,
+	scanExclude: [],
+	scanRespectGitignore: true,
+} as unknown as Configuration;
+
+describe('findWorkspaceFiles', () => {
+	afterEach(() => {
+		vi.restoreAllMocks();
+		(vscode.workspace as any).workspaceFolders = undefined;
+	});
+
+	it('should exclude entries of nested .gitignore files below their directory', async () => {
+		(vscode.workspace as any).workspaceFolders = [{ uri: { path: '/ws' } }];
+		vi.spyOn(vscode.workspace.fs, 'readFile').mockImplementation(
+			async (uri: any) => {
+				const content = GITIGNORES[uri.path];
+				if (content === undefined) {
+					throw new Error('not found');
+				}
+				return new TextEncoder().encode(content);
+			},
+		);
+		const findFiles = vi
+			.spyOn(vscode.workspace, 'findFiles')
+			.mockImplementation(async (include: any) =>
+				include.pattern === '*/**/.gitignore'
+					? [{ path: '/ws/packages/app/.gitignore' }]
+					: [],
+			);
+		const token = { isCancellationRequested: false } as any;
+
+		await findWorkspaceFiles(CONFIG, '**/*.ts', token);
+
+		// Nested .gitignore files are only looked for outside ignored folders
+		expect(findFiles).toHaveBeenCalledWith(
+			expect.objectContaining({ pattern: '*/**/.gitignore' }),
+			'**/node_modules/**',
+			100,
+			token,
+		);
+		expect(findFiles).toHaveBeenCalledWith(
+			expect.objectContaining({ pattern: '**/*.ts' }),
+			'{**/node_modules/**,packages/app/out/**,packages/app/**/*.gen.ts/**,packages/app/**/*.gen.ts}',
+			100,
+			token,
+		);
+	});
+});
+
+describe('readWorkspaceText', () => {
+	afterEach(() => {
+		(vscode.workspace as any).textDocuments = [];
+	});
+
+	it('should measure open documents in bytes, like files on disk', async () => {
+		const uri = { toString: () => 'file:///ws/a.txt' } as any;
+		(vscode.workspace as any).textDocuments = [
+			{ uri, getText: () => 'é'.repeat(6) },
+		];
+		const config = {
+			safetyEnabled: true,
+			safetyFileSizeWarnBytes: 10,
+		} as unknown as Configuration;
+
+		// 6 characters, but 12 bytes in UTF-8
+		expect(await readWorkspaceText(uri, config)).toBeUndefined();
+		expect(
+			await readWorkspaceText(uri, { ...config, safetyFileSizeWarnBytes: 12 }),
+		).toBe('é'.repeat(6));
+	});
+});
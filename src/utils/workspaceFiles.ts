@@ -0,0 +1,135 @@
+/**
+ * Workspace file discovery and reading shared by workspace-wide commands
+ */
+
+import * as vscode from 'vscode';
+import type { Configuration } from '../types';
+import { combineGlobs, gitignoreToGlobs } from './ignoreGlobs';
+
+/**
+ * Find files matching include in every workspace folder, skipping
+ * scan.exclude, files.exclude and (optionally) .gitignore entries
+ * At most scan.maxFiles files are returned, sorted by path.
+ */
+export async function findWorkspaceFiles(
+	config: Configuration,
+	include: string,
+	token: vscode.CancellationToken,
+): Promise<readonly vscode.Uri[]> {
+	const uris: vscode.Uri[] = [];
+	for (const folder of vscode.workspace.workspaceFolders ?? []) {
+		const remaining = config.scanMaxFiles - uris.length;
+		if (remaining <= 0 || token.isCancellationRequested) {
+			break;
+		}
+
+		const exclude = combineGlobs([
+			...config.scanExclude,
+			...enabledGlobs(
+				vscode.workspace
+					.getConfiguration('files', folder.uri)
+					.get<Record<string, unknown>>('exclude', {}),
+			),
+			...(config.scanRespectGitignore
+				? await readGitignores(folder, config.scanMaxFiles, token)
+				: []),
+		]);
+
+		const found = await vscode.workspace.findFiles(
+			new vscode.RelativePattern(folder, include),
+			exclude ?? null,
+			remaining,
+			token,
+		);
+		uris.push(...found);
+	}
+
+	// Stable order so reports of the same workspace can be compared
+	return uris.sort((a, b) => a.path.localeCompare(b.path));
+}
+
+/**
+ * Globs switched on in a files.exclude style object; conditional entries
+ * ({ "when": ... }) cannot be expressed in one exclude pattern
+ */
+function enabledGlobs(setting: Record<string, unknown>): readonly string[] {
+	return Object.keys(setting).filter((glob) => setting[glob] === true);
+}
+
+/**
+ * Exclude globs of every .gitignore in a folder, each applying below its
+ * own directory
+ * Nested files are searched outside what the root .gitignore ignores, so
+ * those in e.g. node_modules are not read; like the scan itself, the search
+ * stops at maxFiles files or when cancelled.
+ */
+async function readGitignores(
+	folder: vscode.WorkspaceFolder,
+	maxFiles: number,
+	token: vscode.CancellationToken,
+): Promise<readonly string[]> {
+	const root = await readGitignore(
+		vscode.Uri.joinPath(folder.uri, '.gitignore'),
+		'',
+	);
+	const nested = await vscode.workspace.findFiles(
+		new vscode.RelativePattern(folder, '*/**/.gitignore'),
+		combineGlobs(root),
+		maxFiles,
+		token,
+	);
+	const globs = [...root];
+	for (const uri of nested) {
+		if (token.isCancellationRequested) {
+			break;
+		}
+		const relative = uri.path.slice(folder.uri.path.length + 1);
+		const directory = relative.slice(0, -'/.gitignore'.length);
+		globs.push(...(await readGitignore(uri, directory)));
+	}
+	return globs;
+}
+
+async function readGitignore(
+	uri: vscode.Uri,
+	directory: string,
+): Promise<readonly string[]> {
+	try {
+		const bytes = await vscode.workspace.fs.readFile(uri);
+		return gitignoreToGlobs(new TextDecoder().decode(bytes), directory);
+	} catch {
+		return [];
+	}
+}
+
+/**
+ * Read a file, preferring unsaved editor contents
+ * Returns undefined for unreadable files and, with safety checks on, for
+ * files over the size threshold.
+ */
+export async function readWorkspaceText(
+	uri: vscode.Uri,
+	config: Configuration,
+): Promise<string | undefined> {
+	const limit = config.safetyEnabled
+		? config.safetyFileSizeWarnBytes
+		: Number.POSITIVE_INFINITY;
+	const open = vscode.workspace.textDocuments.find(
+		(document) => document.uri.toString() === uri.toString(),
+	);
+	if (open) {
+		// Measured in bytes like the file on disk, not UTF-16 code units
+		const text = open.getText();
+		return Buffer.byteLength(text, 'utf8') > limit ? undefined : text;
+	}
+
+	try {
+		const stat = await vscode.workspace.fs.stat(uri);
+		if (stat.size > limit) {
+			return undefined;
+		}
+		return new TextDecoder().decode(await vscode.workspace.fs.readFile(uri));
+	} catch {
+		return undefined;
+	}
+}